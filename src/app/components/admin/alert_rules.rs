@@ -0,0 +1,118 @@
+use leptos::prelude::*;
+
+use crate::{
+    app::{components::atoms::icons, states::settings},
+    backend::settings::set_alert_rules,
+    common::types::AlertRules,
+};
+
+/// Parses a threshold input, an empty field disables the rule
+fn parse_threshold(value: &str) -> Option<u32> {
+    value.trim().parse::<u32>().ok()
+}
+
+#[component]
+fn ThresholdInput(
+    label: &'static str,
+    hint: &'static str,
+    value: ReadSignal<Option<u32>>,
+    set_value: WriteSignal<Option<u32>>,
+) -> impl IntoView {
+    view! {
+        <div>
+            <label class="block text-sm font-medium text-text mb-1">{label}</label>
+            <input
+                type="number"
+                min="0"
+                placeholder="Disabled"
+                prop:value=move || value.get().map(|v| v.to_string()).unwrap_or_default()
+                on:input=move |ev| set_value.set(parse_threshold(&event_target_value(&ev)))
+                class="w-full px-3 py-2 border border-border bg-surface placeholder-text-muted text-text rounded-md focus:outline-none focus:ring-primary focus:border-primary sm:text-sm"
+            />
+            <p class="mt-1 text-xs text-text-muted">{hint}</p>
+        </div>
+    }
+}
+
+#[component]
+pub fn AlertRulesForm() -> impl IntoView {
+    let settings_state = settings::get();
+    let settings = settings_state.get_settings();
+
+    let (low_stock, set_low_stock) = signal::<Option<u32>>(None);
+    let (backlog, set_backlog) = signal::<Option<u32>>(None);
+    let (max_wait, set_max_wait) = signal::<Option<u32>>(None);
+
+    // Keep the form in sync with the stored rules
+    Effect::new(move |_| {
+        let rules = settings.get().map(|s| s.alert_rules).unwrap_or_default();
+        set_low_stock.set(rules.low_stock_threshold);
+        set_backlog.set(rules.backlog_threshold);
+        set_max_wait.set(rules.max_wait_minutes);
+    });
+
+    let save_action = Action::new(|rules: &AlertRules| {
+        let rules = rules.clone();
+        async move { set_alert_rules(rules).await }
+    });
+
+    view! {
+        <div class="max-w-2xl w-full space-y-6">
+            <div class="grid grid-cols-1 md:grid-cols-3 gap-4">
+                <ThresholdInput
+                    label="Low stock below"
+                    hint="Units left of a stock tracked product"
+                    value=low_stock
+                    set_value=set_low_stock
+                />
+                <ThresholdInput
+                    label="Station backlog above"
+                    hint="Pending units at a single station"
+                    value=backlog
+                    set_value=set_backlog
+                />
+                <ThresholdInput
+                    label="Waiting longer than"
+                    hint="Minutes since the item was ordered"
+                    value=max_wait
+                    set_value=set_max_wait
+                />
+            </div>
+
+            <Show when=move || save_action.value().get().as_ref().map(|result| result.is_err()).unwrap_or(false)>
+                <div class="bg-red-50 border border-red-200 rounded-md p-4">
+                    <h3 class="text-sm font-medium text-red-800">
+                        {move || {
+                            save_action.value().get()
+                                .and_then(|result| result.err())
+                                .map(|err| err.to_string())
+                                .unwrap_or_else(|| "An error occurred".to_string())
+                        }}
+                    </h3>
+                </div>
+            </Show>
+
+            <button
+                class="w-full flex justify-center py-2 px-4 border border-transparent text-sm font-medium rounded-md text-white bg-primary hover:opacity-90 disabled:opacity-50 disabled:cursor-not-allowed"
+                disabled=move || save_action.pending().get()
+                on:click=move |_| {
+                    save_action.dispatch(AlertRules {
+                        low_stock_threshold: low_stock.get_untracked(),
+                        backlog_threshold: backlog.get_untracked(),
+                        max_wait_minutes: max_wait.get_untracked(),
+                    });
+                }
+            >
+                <Show
+                    when=move || save_action.pending().get()
+                    fallback=|| view! { "Save Alert Rules" }
+                >
+                    <span class="flex items-center">
+                        <icons::Spinner attr:class="animate-spin -ml-1 mr-3 h-5 w-5 text-white"/>
+                        "Saving..."
+                    </span>
+                </Show>
+            </button>
+        </div>
+    }
+}
@@ -19,11 +19,13 @@ fn CategoryDisplayItem(
 
     let id = category.id.clone();
     let name = category.name.clone();
+    let tax_rate = category.tax_rate;
 
     view! {
         <div class="flex items-center justify-between p-3 bg-surface-elevated rounded-md border border-border">
             <div class="flex-1 flex items-center justify-between">
                 <span class="text-text font-medium">{name}</span>
+                <span class="text-text-muted text-sm ml-4">{format!("{}% VAT", tax_rate)}</span>
                 <span class="text-text-muted text-sm ml-4">{"ID: "}{id.clone()}</span>
             </div>
             
@@ -62,6 +64,7 @@ fn CategoryEditItem(
     on_cancel: WriteSignal<Option<String>>,
 ) -> impl IntoView {
     let (edit_name, set_edit_name) = signal(category.name.clone());
+    let (edit_tax_rate, set_edit_tax_rate) = signal(category.tax_rate.to_string());
     let update_action = ServerAction::<UpdateCategory>::new();
     
     // Close edit mode when update succeeds
@@ -73,6 +76,7 @@ fn CategoryEditItem(
     
     let id = category.id.clone();
     let original_name = category.name.clone();
+    let original_tax_rate = category.tax_rate.to_string();
 
     view! {
         <div class="flex items-center justify-between p-3 bg-surface-elevated rounded-md border border-border">
@@ -85,6 +89,18 @@ fn CategoryEditItem(
                     }
                     class="flex-1 px-2 py-1 border border-border bg-surface text-text rounded focus:outline-none focus:ring-primary focus:border-primary text-sm"
                 />
+                <input
+                    type="number"
+                    step="0.1"
+                    min="0"
+                    max="100"
+                    title="VAT (%)"
+                    prop:value=move || edit_tax_rate.get()
+                    on:input=move |ev| {
+                        set_edit_tax_rate.set(event_target_value(&ev));
+                    }
+                    class="w-20 ml-2 px-2 py-1 border border-border bg-surface text-text rounded focus:outline-none focus:ring-primary focus:border-primary text-sm"
+                />
                 <span class="text-text-muted text-sm ml-4">{"ID: "}{id.clone()}</span>
             </div>
             
@@ -97,6 +113,7 @@ fn CategoryEditItem(
                 >
                     <input type="hidden" name="id" value={id.clone()} />
                     <input type="hidden" name="update[name]" value=move || edit_name.get() />
+                    <input type="hidden" name="update[tax_rate]" value=move || edit_tax_rate.get() />
                     <button
                         type="submit"
                         class="bg-border/80 text-green-600 hover:bg-border hover:scale-105 p-2 rounded"
@@ -109,6 +126,7 @@ fn CategoryEditItem(
                     class="bg-border/80 text-gray-600 hover:bg-border hover:scale-105 p-2 rounded"
                     on:click=move |_| {
                         set_edit_name.set(original_name.clone());
+                        set_edit_tax_rate.set(original_tax_rate.clone());
                         on_cancel.set(None);
                     }
                 >
@@ -34,6 +34,21 @@ pub fn CreateCategory() -> impl IntoView {
                             placeholder="Enter category name"
                         />
                     </div>
+
+                    <div>
+                        <label for="tax_rate" class="block text-sm font-medium text-text mb-2">"VAT (%)"</label>
+                        <input
+                            id="tax_rate"
+                            name="req[tax_rate]"
+                            type="number"
+                            step="0.1"
+                            min="0"
+                            max="100"
+                            value="19"
+                            required
+                            class="relative block w-full px-3 py-2 border border-border bg-surface placeholder-text-muted text-text rounded-md focus:outline-none focus:ring-primary focus:border-primary focus:z-10 sm:text-sm"
+                        />
+                    </div>
                 </div>
 
                 <Show when=move || create_action.value().get().as_ref().map(|result| result.is_err()).unwrap_or(false)>
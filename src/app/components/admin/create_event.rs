@@ -34,6 +34,27 @@ pub fn CreateEvent() -> impl IntoView {
                             placeholder="Enter event name"
                         />
                     </div>
+                    <div class="grid grid-cols-2 gap-4">
+                        <div>
+                            <label for="starts_at" class="block text-sm font-medium text-text mb-2">"Opens"</label>
+                            <input
+                                id="starts_at"
+                                name="req[starts_at]"
+                                type="datetime-local"
+                                class="relative block w-full px-3 py-2 border border-border bg-surface text-text rounded-md focus:outline-none focus:ring-primary focus:border-primary focus:z-10 sm:text-sm"
+                            />
+                        </div>
+                        <div>
+                            <label for="ends_at" class="block text-sm font-medium text-text mb-2">"Closes"</label>
+                            <input
+                                id="ends_at"
+                                name="req[ends_at]"
+                                type="datetime-local"
+                                class="relative block w-full px-3 py-2 border border-border bg-surface text-text rounded-md focus:outline-none focus:ring-primary focus:border-primary focus:z-10 sm:text-sm"
+                            />
+                        </div>
+                    </div>
+                    <p class="text-xs text-text-muted">"New events are planned and open on their own at the given time, or when opened by hand."</p>
                 </div>
 
                 <Show when=move || create_action.value().get().as_ref().map(|result| result.is_err()).unwrap_or(false)>
@@ -13,6 +13,7 @@ pub fn CreateProduct() -> impl IntoView {
     let create_action = ServerAction::<CreateProduct>::new();
     let category_state = category::get();
     let categories = category_state.get_categories();
+    let (stock, set_stock) = signal::<Option<u32>>(None);
 
     // Handle successful product creation
     Effect::new(move |_| {
@@ -79,6 +80,22 @@ pub fn CreateProduct() -> impl IntoView {
                             />
                         </div>
                     </div>
+
+                    <div>
+                        <label for="stock" class="block text-sm font-medium text-text mb-2">"Stock"</label>
+                        <input
+                            id="stock"
+                            type="number"
+                            min="0"
+                            on:input=move |ev| set_stock.set(event_target_value(&ev).trim().parse::<u32>().ok())
+                            class="relative block w-full px-3 py-2 border border-border bg-surface placeholder-text-muted text-text rounded-md focus:outline-none focus:ring-primary focus:border-primary focus:z-10 sm:text-sm"
+                            placeholder="Leave empty to not track stock"
+                        />
+                        // Only submitted when set, an untracked stock is sent as a missing field
+                        {move || stock.get().map(|value| view! {
+                            <input type="hidden" name="req[stock]" value=value />
+                        })}
+                    </div>
                 </div>
 
                 <Show when=move || create_action.value().get().as_ref().map(|result| result.is_err()).unwrap_or(false)>
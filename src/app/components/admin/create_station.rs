@@ -3,12 +3,82 @@ use leptos::prelude::*;
 use crate::{
     backend::station::CreateStation,
     app::{
-        states::category,
+        states::{category, event, settings},
         components::atoms::icons,
     },
-    common::types::OrderStatus,
+    common::types::{OrderStatus, SlaThresholds},
 };
 
+/// Statuses stations can be configured with, including the custom statuses of every active event
+pub fn status_options() -> Signal<Vec<OrderStatus>> {
+    let events = event::get().get_events();
+    let settings = settings::get().get_settings();
+    Signal::derive(move || {
+        let Some(settings) = settings.get() else {
+            return OrderStatus::available(&[]);
+        };
+        let mut custom_statuses: Vec<String> = Vec::new();
+        for event in events.get().into_iter().filter(|e| settings.is_active(&e.id)) {
+            for status in event.custom_statuses {
+                if !custom_statuses.contains(&status) {
+                    custom_statuses.push(status);
+                }
+            }
+        }
+        OrderStatus::available(&custom_statuses)
+    })
+}
+
+/// Inputs for the waiting times after which order cards turn yellow and red, an empty field disables the colour
+#[component]
+pub fn SlaInputs(sla: RwSignal<SlaThresholds>) -> impl IntoView {
+    let parse = |value: String| value.trim().parse::<u32>().ok();
+    let class = "w-full px-2 py-1 border border-border bg-surface placeholder-text-muted text-text rounded focus:outline-none focus:ring-primary focus:border-primary text-sm";
+
+    view! {
+        <div class="grid grid-cols-2 gap-2">
+            <div>
+                <label class="block text-sm font-medium text-text mb-1">"Yellow after (min)"</label>
+                <input
+                    type="number"
+                    min="1"
+                    placeholder="Disabled"
+                    prop:value=move || sla.get().warn_after_minutes.map(|m| m.to_string()).unwrap_or_default()
+                    on:input=move |ev| sla.update(|sla| sla.warn_after_minutes = parse(event_target_value(&ev)))
+                    class=class
+                />
+            </div>
+            <div>
+                <label class="block text-sm font-medium text-text mb-1">"Red after (min)"</label>
+                <input
+                    type="number"
+                    min="1"
+                    placeholder="Disabled"
+                    prop:value=move || sla.get().late_after_minutes.map(|m| m.to_string()).unwrap_or_default()
+                    on:input=move |ev| sla.update(|sla| sla.late_after_minutes = parse(event_target_value(&ev)))
+                    class=class
+                />
+            </div>
+        </div>
+    }
+}
+
+/// Checkbox turning a station into an expo station, which hands out whole orders once every item is ready
+#[component]
+pub fn ExpoToggle(expo: RwSignal<bool>) -> impl IntoView {
+    view! {
+        <label class="flex items-center space-x-2 text-sm cursor-pointer">
+            <input
+                type="checkbox"
+                prop:checked=move || expo.get()
+                on:change=move |ev| expo.set(event_target_checked(&ev))
+                class="h-4 w-4 text-primary focus:ring-primary border-border rounded"
+            />
+            <span class="text-text">"Expo station (hands out whole orders, takes every category)"</span>
+        </label>
+    }
+}
+
 #[component]
 pub fn CreateStation() -> impl IntoView {
     let create_action = ServerAction::<CreateStation>::new();
@@ -18,6 +88,8 @@ pub fn CreateStation() -> impl IntoView {
     let (selected_categories, set_selected_categories) = signal::<Vec<String>>(Vec::new());
     let (selected_input_statuses, set_selected_input_statuses) = signal::<Vec<OrderStatus>>(Vec::new());
     let (selected_output_status, set_selected_output_status) = signal(OrderStatus::Ready);
+    let sla = RwSignal::new(SlaThresholds::default());
+    let expo = RwSignal::new(false);
 
     // Handle successful station creation
     Effect::new(move |_| {
@@ -27,16 +99,12 @@ pub fn CreateStation() -> impl IntoView {
             set_selected_categories.set(Vec::new());
             set_selected_input_statuses.set(Vec::new());
             set_selected_output_status.set(OrderStatus::Ready);
+            sla.set(SlaThresholds::default());
+            expo.set(false);
         }
     });
 
-    let all_statuses = || vec![
-        OrderStatus::Draft,
-        OrderStatus::Ordered,
-        OrderStatus::Ready,
-        OrderStatus::Completed,
-        OrderStatus::Cancelled,
-    ];
+    let all_statuses = status_options();
 
     let toggle_category = move |category_id: String| {
         let mut current_ids = selected_categories.get();
@@ -125,21 +193,22 @@ pub fn CreateStation() -> impl IntoView {
                         <label class="block text-sm font-medium text-text mb-2">"Input Statuses"</label>
                         <div class="grid grid-cols-2 gap-2 p-3 border border-border bg-surface rounded-md">
                             <For
-                                each=move || all_statuses()
-                                key=|status| format!("{:?}", status)
+                                each=move || all_statuses.get()
+                                key=|status| status.to_string()
                                 children=move |status| {
-                                    let status_for_toggle = status;
+                                    let status_for_toggle = status.clone();
+                                    let status_for_check = status.clone();
                                     view! {
                                         <label class="flex items-center space-x-2 p-2 border border-border rounded text-sm hover:bg-background cursor-pointer">
                                             <input
                                                 type="checkbox"
-                                                prop:checked=move || selected_input_statuses.get().contains(&status)
+                                                prop:checked=move || selected_input_statuses.get().contains(&status_for_check)
                                                 on:change=move |_| {
-                                                    toggle_input_status(status_for_toggle);
+                                                    toggle_input_status(status_for_toggle.clone());
                                                 }
                                                 class="h-4 w-4 text-primary focus:ring-primary border-border rounded"
                                             />
-                                            <span class="text-text">{format!("{:?}", status)}</span>
+                                            <span class="text-text">{status.to_string()}</span>
                                         </label>
                                     }
                                 }
@@ -161,25 +230,17 @@ pub fn CreateStation() -> impl IntoView {
                             id="output_status"
                             name="output_status"
                             required
-                            prop:value=move || format!("{:?}", selected_output_status.get())
+                            prop:value=move || selected_output_status.get().to_string()
                             on:change=move |ev| {
-                                let value = event_target_value(&ev);
-                                match value.as_str() {
-                                    "Draft" => set_selected_output_status.set(OrderStatus::Draft),
-                                    "Ordered" => set_selected_output_status.set(OrderStatus::Ordered),
-                                    "Ready" => set_selected_output_status.set(OrderStatus::Ready),
-                                    "Completed" => set_selected_output_status.set(OrderStatus::Completed),
-                                    "Cancelled" => set_selected_output_status.set(OrderStatus::Cancelled),
-                                    _ => {}
-                                }
+                                set_selected_output_status.set(OrderStatus::from_label(&event_target_value(&ev)));
                             }
                             class="relative block w-full px-3 py-2 border border-border bg-surface placeholder-text-muted text-text rounded-md focus:outline-none focus:ring-primary focus:border-primary focus:z-10 sm:text-sm"
                         >
                             <For
-                                each=move || all_statuses()
-                                key=|status| format!("{:?}", status)
+                                each=move || all_statuses.get()
+                                key=|status| status.to_string()
                                 children=move |status| {
-                                    let status_str = format!("{:?}", status);
+                                    let status_str = status.to_string();
                                     let status_str_clone = status_str.clone();
                                     view! {
                                         <option value={status_str} selected=move || selected_output_status.get() == status>
@@ -190,6 +251,31 @@ pub fn CreateStation() -> impl IntoView {
                             />
                         </select>
                     </div>
+
+                    <div>
+                        <SlaInputs sla=sla />
+                        <input
+                            type="hidden"
+                            name="sla_json"
+                            value=move || serde_json::to_string(&sla.get()).unwrap_or_default()
+                        />
+                    </div>
+
+                    <div>
+                        <label for="printer" class="block text-sm font-medium text-text mb-2">"Ticket Printer"</label>
+                        <input
+                            id="printer"
+                            name="printer"
+                            type="text"
+                            class="relative block w-full px-3 py-2 border border-border bg-surface placeholder-text-muted text-text rounded-md focus:outline-none focus:ring-primary focus:border-primary focus:z-10 sm:text-sm"
+                            placeholder="Optional, e.g. 192.168.1.50 or 192.168.1.50:9100"
+                        />
+                    </div>
+
+                    <div>
+                        <ExpoToggle expo=expo />
+                        <input type="hidden" name="expo" value=move || expo.get().to_string() />
+                    </div>
                 </div>
 
                 <Show when=move || create_action.value().get().as_ref().map(|result| result.is_err()).unwrap_or(false)>
@@ -212,7 +298,7 @@ pub fn CreateStation() -> impl IntoView {
                 <div>
                     <button
                         type="submit"
-                        disabled=move || create_action.pending().get() || selected_categories.get().is_empty() || selected_input_statuses.get().is_empty()
+                        disabled=move || create_action.pending().get() || (selected_categories.get().is_empty() && !expo.get()) || selected_input_statuses.get().is_empty()
                         class="group relative w-full flex justify-center py-2 px-4 border border-transparent text-sm font-medium rounded-md text-white bg-primary hover:opacity-90 focus:outline-none focus:ring-2 focus:ring-offset-2 focus:ring-primary disabled:opacity-50 disabled:cursor-not-allowed"
                     >
                         <Show
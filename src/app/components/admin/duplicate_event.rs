@@ -0,0 +1,94 @@
+use leptos::prelude::*;
+
+use crate::{
+    app::states::product,
+    backend::event::DuplicateEvent,
+    common::{menu::existing_entries, types::Event},
+};
+
+/// Shows what a copy of the event takes over and creates it under a new name and schedule
+#[component]
+pub fn DuplicateEventPanel(event: Event, on_close: WriteSignal<bool>) -> impl IntoView {
+    let products = product::get().get_products();
+    let duplicate_action = ServerAction::<DuplicateEvent>::new();
+    Effect::new(move |_| {
+        if let Some(Ok(_)) = duplicate_action.value().get() {
+            on_close.set(false);
+        }
+    });
+
+    let status_flow = if event.custom_statuses.is_empty() {
+        "Default".to_string()
+    } else {
+        event.custom_statuses.join(" → ")
+    };
+    let menu = event.menu.clone();
+    let menu_summary = move || {
+        let copied = products.with(|products| existing_entries(&menu, products));
+        if menu.is_empty() {
+            "Every product at its list price".to_string()
+        } else {
+            let overrides = copied.iter().filter(|entry| entry.price.is_some()).count();
+            let dropped = menu.len() - copied.len();
+            let mut summary = format!("{} products, {} with their own price", copied.len(), overrides);
+            if dropped > 0 {
+                summary.push_str(&format!(", {} deleted products left out", dropped));
+            }
+            summary
+        }
+    };
+    let input_class = "px-2 py-1 border border-border bg-surface text-text rounded focus:outline-none focus:ring-primary focus:border-primary text-sm";
+
+    view! {
+        <div class="mt-2 p-3 rounded-md border border-border bg-surface space-y-2 text-sm">
+            <p class="font-medium text-text">"The copy takes over"</p>
+            <ul class="list-disc list-inside text-text-muted">
+                <li>{"Status flow: "}{status_flow}</li>
+                <li>{"Pickup names: "}{event.name_pool.label()}</li>
+                <li>{"Menu: "}{menu_summary}</li>
+            </ul>
+            <p class="font-medium text-text">"Not copied"</p>
+            <ul class="list-disc list-inside text-text-muted">
+                <li>"Stations and their printers, shared by all events"</li>
+                <li>"Stock, kept per product and shared by all events"</li>
+                <li>"Alert rules, shared by all events"</li>
+                <li>"Orders, numbering starts at #001"</li>
+            </ul>
+            <ActionForm action=duplicate_action attr:class="space-y-2">
+                <input type="hidden" name="id" value=event.id.clone() />
+                <input
+                    type="text"
+                    name="req[name]"
+                    required
+                    value=format!("{} (copy)", event.name)
+                    class=format!("w-full {}", input_class)
+                />
+                <div class="flex flex-wrap items-center gap-2 text-text">
+                    "Schedule"
+                    <input type="datetime-local" name="req[starts_at]" class=input_class />
+                    "–"
+                    <input type="datetime-local" name="req[ends_at]" class=input_class />
+                </div>
+                {move || duplicate_action.value().get().and_then(|result| result.err()).map(|err| view! {
+                    <p class="text-sm text-red-600">{err.to_string()}</p>
+                })}
+                <div class="flex gap-2">
+                    <button
+                        type="submit"
+                        class="px-3 py-1 rounded text-sm font-semibold bg-primary text-white hover:bg-primary/90 disabled:opacity-50"
+                        disabled=move || duplicate_action.pending().get()
+                    >
+                        "Duplicate event"
+                    </button>
+                    <button
+                        type="button"
+                        class="px-3 py-1 rounded text-sm text-text hover:bg-border"
+                        on:click=move |_| on_close.set(false)
+                    >
+                        "Cancel"
+                    </button>
+                </div>
+            </ActionForm>
+        </div>
+    }
+}
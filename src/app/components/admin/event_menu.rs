@@ -0,0 +1,171 @@
+use leptos::prelude::*;
+use std::collections::HashMap;
+
+use crate::{
+    app::states::product,
+    backend::event::set_event_menu,
+    common::types::{Event, MenuEntry},
+};
+
+/// Form state of one product, prices and positions are kept as typed until saving
+#[derive(Debug, Clone, Default, PartialEq)]
+struct MenuRow {
+    offered: bool,
+    price: String,
+    sort: String,
+}
+
+fn to_entries(product_ids: &[String], rows: &HashMap<String, MenuRow>) -> Result<Vec<MenuEntry>, String> {
+    let parse_price = |input: &str| -> Result<Option<f64>, String> {
+        let input = input.trim().replace(',', ".");
+        if input.is_empty() {
+            return Ok(None);
+        }
+        input.parse().map(Some).map_err(|_| format!("'{}' is not a price", input))
+    };
+    let parse_sort = |input: &str| -> Result<Option<i32>, String> {
+        let input = input.trim();
+        if input.is_empty() {
+            return Ok(None);
+        }
+        input.parse().map(Some).map_err(|_| format!("'{}' is not a position", input))
+    };
+
+    product_ids
+        .iter()
+        .filter_map(|id| rows.get(id).filter(|row| row.offered).map(|row| (id, row)))
+        .map(|(id, row)| {
+            Ok(MenuEntry {
+                product_id: id.clone(),
+                price: parse_price(&row.price)?,
+                sort: parse_sort(&row.sort)?,
+            })
+        })
+        .collect()
+}
+
+/// Which products an event sells, at which price and position
+#[component]
+pub fn EventMenu(event: Event, on_close: WriteSignal<bool>) -> impl IntoView {
+    let products = product::get().get_products();
+    let initial: HashMap<String, MenuRow> = event
+        .menu
+        .iter()
+        .map(|entry| {
+            let row = MenuRow {
+                offered: true,
+                price: entry.price.map(|price| format!("{:.2}", price)).unwrap_or_default(),
+                sort: entry.sort.map(|sort| sort.to_string()).unwrap_or_default(),
+            };
+            (entry.product_id.clone(), row)
+        })
+        .collect();
+    let rows = RwSignal::new(initial);
+    let (error, set_error) = signal::<Option<String>>(None);
+    let everything_offered = event.menu.is_empty();
+
+    let event_id = event.id.clone();
+    let save_action = Action::new(move |menu: &Vec<MenuEntry>| {
+        let event_id = event_id.clone();
+        let menu = menu.clone();
+        async move { set_event_menu(event_id, menu).await }
+    });
+    Effect::new(move |_| match save_action.value().get() {
+        Some(Ok(_)) => on_close.set(false),
+        Some(Err(e)) => set_error.set(Some(e.to_string())),
+        None => {}
+    });
+
+    let save = move |_| {
+        let product_ids: Vec<String> = products.get_untracked().into_iter().map(|p| p.id).collect();
+        match rows.with_untracked(|rows| to_entries(&product_ids, rows)) {
+            Ok(menu) => {
+                set_error.set(None);
+                save_action.dispatch(menu);
+            }
+            Err(e) => set_error.set(Some(e)),
+        }
+    };
+    let input_class = "w-20 px-2 py-1 border border-border bg-surface text-text rounded focus:outline-none focus:ring-primary focus:border-primary text-sm";
+
+    view! {
+        <div class="mt-2 p-3 rounded-md border border-border bg-surface space-y-2">
+            {everything_offered.then(|| view! {
+                <p class="text-sm text-text-muted">"No menu yet, every product is offered at its list price."</p>
+            })}
+            <div class="grid grid-cols-[auto_1fr_auto_auto] items-center gap-x-3 gap-y-1 text-sm">
+                <span></span>
+                <span class="text-text-muted">"Product"</span>
+                <span class="text-text-muted">"Price"</span>
+                <span class="text-text-muted">"Position"</span>
+                <For
+                    each=move || products.get()
+                    key=|product| product.id.clone()
+                    children=move |product| {
+                        let id = StoredValue::new(product.id.clone());
+                        let row = move || id.with_value(|id| rows.with(|rows| rows.get(id).cloned().unwrap_or_default()));
+                        let update = move |change: &dyn Fn(&mut MenuRow)| {
+                            id.with_value(|id| rows.update(|rows| change(rows.entry(id.clone()).or_default())));
+                        };
+                        view! {
+                            <input
+                                type="checkbox"
+                                prop:checked=move || row().offered
+                                on:change=move |ev| {
+                                    let offered = event_target_checked(&ev);
+                                    update(&|row| row.offered = offered);
+                                }
+                            />
+                            <span class="text-text">{product.name.clone()}</span>
+                            <input
+                                type="text"
+                                inputmode="decimal"
+                                placeholder=format!("{:.2}", product.price)
+                                prop:value=move || row().price
+                                on:input=move |ev| {
+                                    let price = event_target_value(&ev);
+                                    update(&|row| row.price = price.clone());
+                                }
+                                class=input_class
+                            />
+                            <input
+                                type="text"
+                                inputmode="numeric"
+                                prop:value=move || row().sort
+                                on:input=move |ev| {
+                                    let sort = event_target_value(&ev);
+                                    update(&|row| row.sort = sort.clone());
+                                }
+                                class=input_class
+                            />
+                        }
+                    }
+                />
+            </div>
+            {move || error.get().map(|e| view! { <p class="text-sm text-red-600">{e}</p> })}
+            <div class="flex gap-2">
+                <button
+                    class="px-3 py-1 rounded text-sm font-semibold bg-primary text-white hover:bg-primary/90 disabled:opacity-50"
+                    disabled=move || save_action.pending().get()
+                    on:click=save
+                >
+                    "Save menu"
+                </button>
+                <button
+                    class="px-3 py-1 rounded text-sm text-text hover:bg-border"
+                    on:click=move |_| {
+                        save_action.dispatch(Vec::new());
+                    }
+                >
+                    "Offer every product"
+                </button>
+                <button
+                    class="px-3 py-1 rounded text-sm text-text hover:bg-border"
+                    on:click=move |_| on_close.set(false)
+                >
+                    "Cancel"
+                </button>
+            </div>
+        </div>
+    }
+}
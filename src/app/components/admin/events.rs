@@ -1,10 +1,77 @@
 use leptos::prelude::*;
 
 use crate::{
-    app::{components::atoms::icons::{self, CheckCircle, Star}, states::{event, settings}},
-    backend::{event::{delete_event, UpdateEvent}, settings::set_active_event},
+    app::{components::{admin::{duplicate_event::DuplicateEventPanel, event_menu::EventMenu}, atoms::icons::{self, CheckCircle, Star}}, states::{event, settings}},
+    backend::{event::{close_event, count_open_orders, delete_event, update_event, UpdateEvent}, settings::{activate_event, deactivate_event}},
+    common::{requests, types::{EventState, NamePool, OrderStatus}},
 };
 
+/// Asks for confirmation before closing an event, showing how many of its orders are still open
+#[component]
+fn CloseEventPanel(event_id: String, on_done: WriteSignal<bool>) -> impl IntoView {
+    let (cancel_open, set_cancel_open) = signal(false);
+    let open_orders = Resource::new(
+        {
+            let event_id = event_id.clone();
+            move || event_id.clone()
+        },
+        |event_id| async move { count_open_orders(event_id).await.ok() },
+    );
+    let close_action = Action::new(move |cancel_open_orders: &bool| {
+        let event_id = event_id.clone();
+        let cancel_open_orders = *cancel_open_orders;
+        async move { close_event(event_id, cancel_open_orders).await }
+    });
+    Effect::new(move |_| {
+        if let Some(Ok(_)) = close_action.value().get() {
+            on_done.set(false);
+        }
+    });
+
+    view! {
+        <div class="mt-2 p-3 rounded-md border border-error/40 bg-error/5 space-y-2">
+            <Suspense fallback=|| view! { <p class="text-sm text-text-muted">"Counting open orders..."</p> }>
+                {move || open_orders.get().flatten().map(|count| if count > 0 {
+                    view! {
+                        <p class="text-sm text-error font-medium">{format!("{} orders are still open", count)}</p>
+                        <label class="flex items-center gap-2 text-sm text-text">
+                            <input
+                                type="checkbox"
+                                prop:checked=move || cancel_open.get()
+                                on:change=move |ev| set_cancel_open.set(event_target_checked(&ev))
+                            />
+                            "Cancel the open orders"
+                        </label>
+                    }.into_any()
+                } else {
+                    view! { <p class="text-sm text-text-muted">"All orders are done"</p> }.into_any()
+                })}
+            </Suspense>
+            <p class="text-xs text-text-muted">"A closed event takes no orders and its data can no longer change."</p>
+            {move || close_action.value().get().and_then(|result| result.err()).map(|err| view! {
+                <p class="text-sm text-red-600">{err.to_string()}</p>
+            })}
+            <div class="flex gap-2">
+                <button
+                    class="px-3 py-1 rounded text-sm font-semibold bg-error text-white hover:bg-error/90 disabled:opacity-50"
+                    disabled=move || close_action.pending().get()
+                    on:click=move |_| {
+                        close_action.dispatch(cancel_open.get_untracked());
+                    }
+                >
+                    "Close event"
+                </button>
+                <button
+                    class="px-3 py-1 rounded text-sm text-text hover:bg-border"
+                    on:click=move |_| on_done.set(false)
+                >
+                    "Keep open"
+                </button>
+            </div>
+        </div>
+    }
+}
+
 #[component]
 fn EventDisplayItem(
     event: crate::common::types::Event,
@@ -18,73 +85,201 @@ fn EventDisplayItem(
         }
     });
 
-    let set_active_action = Action::new(|input: &String| {
-        let input = input.clone();
+    // Several events can be active at once, one per booth
+    let set_active_action = Action::new(|(id, active): &(String, bool)| {
+        let (id, active) = (id.clone(), *active);
         async move {
-            let _ = set_active_event(input.clone()).await;
+            let _ = if active { activate_event(id).await } else { deactivate_event(id).await };
         }
     });
 
     let id = event.id.clone();
     let name = event.name.clone();
+    // Only worth showing once the event deviates from the built-in flow
+    let status_flow = (!event.custom_statuses.is_empty()).then(|| {
+        OrderStatus::available(&event.custom_statuses)
+            .iter()
+            .filter(|status| **status != OrderStatus::Draft && **status != OrderStatus::Cancelled)
+            .map(|status| status.to_string())
+            .collect::<Vec<String>>()
+            .join(" → ")
+    });
     let id_for_active = id.clone();
     let id_for_edit = id.clone();
     let id_for_delete = id.clone();
+    let id_for_close = id.clone();
+    let state = event.state;
+    let schedule = match (&event.starts_at, &event.ends_at) {
+        (None, None) => None,
+        (starts_at, ends_at) => Some(format!(
+            "{} – {}",
+            starts_at.as_deref().unwrap_or("…").replace('T', " "),
+            ends_at.as_deref().unwrap_or("…").replace('T', " ")
+        )),
+    };
+    let (closing, set_closing) = signal(false);
+    let (editing_menu, set_editing_menu) = signal(false);
+    let menu_size = event.menu.len();
+    let event_for_menu = event.clone();
+    let (duplicating, set_duplicating) = signal(false);
+    let event_for_duplicate = event.clone();
+
+    let state_action = Action::new({
+        let id = id.clone();
+        move |state: &EventState| {
+            let id = id.clone();
+            let update = requests::event::Update {
+                name: None,
+                custom_statuses: None,
+                name_pool: None,
+                state: Some(*state),
+                starts_at: None,
+                ends_at: None,
+            };
+            async move {
+                let _ = update_event(id, update).await;
+            }
+        }
+    });
+    let state_button = move |target: EventState, label: &'static str| view! {
+        <button
+            class="bg-border/80 text-text hover:bg-border px-2 py-1 rounded text-sm"
+            on:click=move |_| {
+                state_action.dispatch(target);
+            }
+        >
+            {label}
+        </button>
+    };
 
     view! {
         <div class="flex items-center justify-between p-3 bg-surface-elevated rounded-md border border-border">
             <div class="flex-1 flex items-center justify-between">
-                <div class="flex items-center space-x-2">
-                    <span class="text-text font-medium">{name}</span>
-                    <Show when=move || is_active>
-                        <span class="inline-flex items-center px-2.5 py-0.5 rounded-full text-xs font-medium bg-green-100 text-green-800">
-                            <CheckCircle attr:class="w-3 h-3 mr-1"/>
-                            "Active"
-                        </span>
+                <div>
+                    <div class="flex items-center space-x-2">
+                        <span class="text-text font-medium">{name}</span>
+                        <span class=format!(
+                            "px-2 py-0.5 rounded-full text-xs font-medium {}",
+                            match state {
+                                EventState::Planned => "bg-blue-100 text-blue-800",
+                                EventState::Open => "bg-green-100 text-green-800",
+                                EventState::Closed => "bg-red-100 text-red-800",
+                                EventState::Archived => "bg-gray-100 text-gray-800",
+                            }
+                        )>{state.label()}</span>
+                        <Show when=move || is_active>
+                            <span class="inline-flex items-center px-2.5 py-0.5 rounded-full text-xs font-medium bg-green-100 text-green-800">
+                                <CheckCircle attr:class="w-3 h-3 mr-1"/>
+                                "Active"
+                            </span>
+                        </Show>
+                    </div>
+                    {status_flow.map(|flow| view! {
+                        <div class="mt-1 text-sm text-text-muted">{"Status Flow: "}{flow}</div>
+                    })}
+                    <div class="mt-1 text-sm text-text-muted">{"Pickup Names: "}{event.name_pool.label()}</div>
+                    <div class="mt-1 text-sm text-text-muted">
+                        {"Menu: "}{if menu_size == 0 { "every product".to_string() } else { format!("{} products", menu_size) }}
+                        <Show when=move || !state.is_locked()>
+                            <button class="ml-2 underline hover:text-text" on:click=move |_| set_editing_menu.update(|open| *open = !*open)>"Edit"</button>
+                        </Show>
+                    </div>
+                    <Show when=move || editing_menu.get()>
+                        <EventMenu event=event_for_menu.clone() on_close=set_editing_menu />
+                    </Show>
+                    <Show when=move || duplicating.get()>
+                        <DuplicateEventPanel event=event_for_duplicate.clone() on_close=set_duplicating />
+                    </Show>
+                    {schedule.map(|schedule| view! {
+                        <div class="mt-1 text-sm text-text-muted">{"Schedule: "}{schedule}</div>
+                    })}
+                    <Show when=move || closing.get()>
+                        <CloseEventPanel event_id=id_for_close.clone() on_done=set_closing />
                     </Show>
                 </div>
                 <span class="text-text-muted text-sm ml-4">{"ID: "}{id.clone()}</span>
             </div>
             
             <div class="flex items-center space-x-2 ml-4">
-                <Show when=move || !is_active>
+                {match state {
+                    EventState::Planned => view! {
+                        {state_button(EventState::Open, "Open")}
+                        <button class="bg-border/80 text-red-600 hover:bg-border px-2 py-1 rounded text-sm" on:click=move |_| set_closing.set(true)>"Close"</button>
+                    }.into_any(),
+                    EventState::Open => view! {
+                        <button class="bg-border/80 text-red-600 hover:bg-border px-2 py-1 rounded text-sm" on:click=move |_| set_closing.set(true)>"Close"</button>
+                    }.into_any(),
+                    EventState::Closed => view! {
+                        {state_button(EventState::Open, "Reopen")}
+                        {state_button(EventState::Archived, "Archive")}
+                    }.into_any(),
+                    EventState::Archived => ().into_any(),
+                }}
+                <button
+                    class="bg-border/80 text-text hover:bg-border px-2 py-1 rounded text-sm"
+                    on:click=move |_| set_duplicating.update(|open| *open = !*open)
+                    title="Duplicate the event's configuration"
+                >
+                    "Duplicate"
+                </button>
+                <Show
+                    when=move || !is_active
+                    fallback={
+                        let id = id_for_active.clone();
+                        move || {
+                            let id = id.clone();
+                            view! {
+                                <button
+                                    class="bg-border/80 text-text hover:bg-border px-2 py-1 rounded text-sm"
+                                    on:click=move |_| {
+                                        set_active_action.dispatch((id.clone(), false));
+                                    }
+                                    title="Stop taking this event's orders on the devices"
+                                >
+                                    "Deactivate"
+                                </button>
+                            }
+                        }
+                    }
+                >
                     <button
                         class="bg-border/80 text-green-600 hover:bg-border hover:scale-105 p-2 rounded"
                         on:click={
                             let id = id_for_active.clone();
                             move |_| {
-                                set_active_action.dispatch(id.clone());
+                                set_active_action.dispatch((id.clone(), true));
                             }
                         }
-                        title="Set as active event"
+                        title="Activate the event, next to the events already active"
                     >
                         <Star attr:class="w-4 h-4"/>
                     </button>
                 </Show>
                 
-                <button
-                    class="bg-border/80 text-blue-600 hover:bg-border hover:scale-105 p-2 rounded"
-                    on:click={
-                        let id = id_for_edit.clone();
-                        move |_| {
-                            on_edit.set(Some(id.clone()));
+                <Show when=move || !state.is_locked()>
+                    <button
+                        class="bg-border/80 text-blue-600 hover:bg-border hover:scale-105 p-2 rounded"
+                        on:click={
+                            let id = id_for_edit.clone();
+                            move |_| {
+                                on_edit.set(Some(id.clone()));
+                            }
                         }
-                    }
-                >
-                    <icons::Edit />
-                </button>
-                
-                <button
-                    class="bg-border/80 text-red-600 hover:bg-border hover:scale-105 p-2 rounded"
-                    on:click={
-                        let id = id_for_delete.clone();
-                        move |_| {
-                            delete_action.dispatch(id.clone());
+                    >
+                        <icons::Edit />
+                    </button>
+                    <button
+                        class="bg-border/80 text-red-600 hover:bg-border hover:scale-105 p-2 rounded"
+                        on:click={
+                            let id = id_for_delete.clone();
+                            move |_| {
+                                delete_action.dispatch(id.clone());
+                            }
                         }
-                    }
-                >
-                    <icons::Trash />
-                </button>
+                    >
+                        <icons::Trash />
+                    </button>
+                </Show>
             </div>
         </div>
     }
@@ -96,6 +291,10 @@ fn EventEditItem(
     on_cancel: WriteSignal<Option<String>>,
 ) -> impl IntoView {
     let (edit_name, set_edit_name) = signal(event.name.clone());
+    let (edit_statuses, set_edit_statuses) = signal(event.custom_statuses.join(", "));
+    let (edit_pool, set_edit_pool) = signal(event.name_pool);
+    let (edit_starts_at, set_edit_starts_at) = signal(event.starts_at.clone().unwrap_or_default());
+    let (edit_ends_at, set_edit_ends_at) = signal(event.ends_at.clone().unwrap_or_default());
     let update_action = ServerAction::<UpdateEvent>::new();
     
     // Close edit mode when update succeeds
@@ -107,30 +306,81 @@ fn EventEditItem(
     
     let id = event.id.clone();
     let original_name = event.name.clone();
+    let original_statuses = event.custom_statuses.join(", ");
+    let original_pool = event.name_pool;
+    let original_starts_at = event.starts_at.clone().unwrap_or_default();
+    let original_ends_at = event.ends_at.clone().unwrap_or_default();
 
     view! {
         <div class="flex items-center justify-between p-3 bg-surface-elevated rounded-md border border-border">
-            <div class="flex-1 flex items-center justify-between">
+            <div class="flex-1 space-y-2">
+                <div class="flex items-center justify-between">
+                    <input
+                        type="text"
+                        prop:value=move || edit_name.get()
+                        on:input=move |ev| {
+                            set_edit_name.set(event_target_value(&ev));
+                        }
+                        class="flex-1 px-2 py-1 border border-border bg-surface text-text rounded focus:outline-none focus:ring-primary focus:border-primary text-sm"
+                    />
+                    <span class="text-text-muted text-sm ml-4">{"ID: "}{id.clone()}</span>
+                </div>
                 <input
                     type="text"
-                    prop:value=move || edit_name.get()
+                    placeholder="Custom statuses between Ordered and Ready, e.g. Grilled, Assembled"
+                    prop:value=move || edit_statuses.get()
                     on:input=move |ev| {
-                        set_edit_name.set(event_target_value(&ev));
+                        set_edit_statuses.set(event_target_value(&ev));
                     }
-                    class="flex-1 px-2 py-1 border border-border bg-surface text-text rounded focus:outline-none focus:ring-primary focus:border-primary text-sm"
+                    class="w-full px-2 py-1 border border-border bg-surface placeholder-text-muted text-text rounded focus:outline-none focus:ring-primary focus:border-primary text-sm"
                 />
-                <span class="text-text-muted text-sm ml-4">{"ID: "}{id.clone()}</span>
+                <label class="flex items-center gap-2 text-sm text-text">
+                    "Pickup names"
+                    <select
+                        on:change=move |ev| {
+                            let label = event_target_value(&ev);
+                            if let Some(pool) = NamePool::ALL.into_iter().find(|pool| pool.label() == label) {
+                                set_edit_pool.set(pool);
+                            }
+                        }
+                        class="px-2 py-1 border border-border bg-surface text-text rounded focus:outline-none focus:ring-primary focus:border-primary text-sm"
+                    >
+                        {NamePool::ALL.into_iter().map(|pool| view! {
+                            <option value=pool.label() selected=move || edit_pool.get() == pool>{pool.label()}</option>
+                        }).collect_view()}
+                    </select>
+                </label>
+                <div class="flex flex-wrap items-center gap-2 text-sm text-text">
+                    "Schedule"
+                    <input
+                        type="datetime-local"
+                        prop:value=move || edit_starts_at.get()
+                        on:input=move |ev| set_edit_starts_at.set(event_target_value(&ev))
+                        class="px-2 py-1 border border-border bg-surface text-text rounded focus:outline-none focus:ring-primary focus:border-primary text-sm"
+                    />
+                    "–"
+                    <input
+                        type="datetime-local"
+                        prop:value=move || edit_ends_at.get()
+                        on:input=move |ev| set_edit_ends_at.set(event_target_value(&ev))
+                        class="px-2 py-1 border border-border bg-surface text-text rounded focus:outline-none focus:ring-primary focus:border-primary text-sm"
+                    />
+                </div>
+                {move || {
+                    update_action.value().get()
+                        .and_then(|result| result.err())
+                        .map(|err| view! { <p class="text-sm text-red-600">{err.to_string()}</p> })
+                }}
             </div>
             
             <div class="flex items-center space-x-2 ml-4">
-                <ActionForm 
-                    action=update_action
-                    on:submit=move |_| {
-                        on_cancel.set(None);
-                    }
-                >
+                <ActionForm action=update_action>
                     <input type="hidden" name="id" value={id.clone()} />
                     <input type="hidden" name="update[name]" value=move || edit_name.get() />
+                    <input type="hidden" name="update[custom_statuses]" value=move || edit_statuses.get() />
+                    <input type="hidden" name="update[name_pool]" value=move || format!("{:?}", edit_pool.get()) />
+                    <input type="hidden" name="update[starts_at]" value=move || edit_starts_at.get() />
+                    <input type="hidden" name="update[ends_at]" value=move || edit_ends_at.get() />
                     <button
                         type="submit"
                         class="bg-border/80 text-green-600 hover:bg-border hover:scale-105 p-2 rounded"
@@ -143,6 +393,10 @@ fn EventEditItem(
                     class="bg-border/80 text-gray-600 hover:bg-border hover:scale-105 p-2 rounded"
                     on:click=move |_| {
                         set_edit_name.set(original_name.clone());
+                        set_edit_statuses.set(original_statuses.clone());
+                        set_edit_pool.set(original_pool);
+                        set_edit_starts_at.set(original_starts_at.clone());
+                        set_edit_ends_at.set(original_ends_at.clone());
                         on_cancel.set(None);
                     }
                 >
@@ -196,8 +450,7 @@ pub fn Events() -> impl IntoView {
                                             .find(|e| e.id == event_id_for_display)
                                             .cloned()
                                             .unwrap_or_else(|| event_fallback.clone());
-                                        let active_event_id = settings.get().and_then(|s| s.active_event_id);
-                                        let is_current_active = active_event_id.as_ref() == Some(&current_event.id);
+                                        let is_current_active = settings.get().is_some_and(|s| s.is_active(&current_event.id));
                                         view! {
                                             <EventDisplayItem 
                                                 event=current_event
@@ -0,0 +1,104 @@
+use leptos::prelude::*;
+
+use crate::app::components::station_metrics::{format_wait, live_station_metrics};
+use crate::common::metrics::{format_duration, WINDOW_COUNT};
+use crate::common::types;
+
+/// Processed units per 15 minute window as a small bar chart, the newest window on the right
+#[component]
+fn Throughput(processed: Vec<u32>) -> impl IntoView {
+    let max = processed.iter().copied().max().unwrap_or_default().max(1);
+    let bars = processed
+        .into_iter()
+        .enumerate()
+        .map(|(index, count)| {
+            let minutes_ago = (WINDOW_COUNT - 1 - index) * 15;
+            view! {
+                <div
+                    class="flex-1 bg-primary/70 rounded-t"
+                    style=format!("height: {}%", count * 100 / max)
+                    title=format!("{} done, {} to {} min ago", count, minutes_ago + 15, minutes_ago)
+                ></div>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <div class="flex items-end gap-1 h-12 border-b border-border">{bars}</div>
+    }
+}
+
+#[component]
+fn StationMetricsCard(metrics: types::StationMetrics) -> impl IntoView {
+    let last_window = metrics.processed.last().copied().unwrap_or_default();
+
+    view! {
+        <div class="p-4 bg-surface-elevated rounded-md border border-border">
+            <div class="flex items-center justify-between mb-3">
+                <h3 class="text-text font-medium">{metrics.station_name.clone()}</h3>
+                <span class="text-sm text-text-muted">{metrics.backlog}" waiting"</span>
+            </div>
+
+            <dl class="grid grid-cols-3 gap-2 text-sm mb-3">
+                <div>
+                    <dt class="text-text-muted">"Last 15 min"</dt>
+                    <dd class="text-text font-semibold">{last_window}</dd>
+                </div>
+                <div>
+                    <dt class="text-text-muted">"Median wait"</dt>
+                    <dd class="text-text font-semibold">{format_wait(metrics.median_wait_secs)}</dd>
+                </div>
+                <div>
+                    <dt class="text-text-muted">"P90 wait"</dt>
+                    <dd class="text-text font-semibold">{format_wait(metrics.p90_wait_secs)}</dd>
+                </div>
+            </dl>
+
+            <Throughput processed=metrics.processed.clone()/>
+            <p class="text-xs text-text-muted mt-1 mb-3">"Units done per 15 minutes, last two hours"</p>
+
+            <h4 class="text-sm text-text-muted mb-1">"Slowest products"</h4>
+            {if metrics.slowest_products.is_empty() {
+                view! { <p class="text-sm text-text-muted">"No finished items yet"</p> }.into_any()
+            } else {
+                view! {
+                    <ul class="text-sm space-y-1">
+                        {metrics.slowest_products.iter().map(|timing| view! {
+                            <li class="flex justify-between">
+                                <span class="text-text">{timing.product_name.clone()}</span>
+                                <span class="text-text-muted">
+                                    {format!("{} ({} items)", format_duration(timing.median_secs), timing.count)}
+                                </span>
+                            </li>
+                        }).collect_view()}
+                    </ul>
+                }.into_any()
+            }}
+        </div>
+    }
+}
+
+/// Live performance of every station in the active event
+#[component]
+pub fn Metrics() -> impl IntoView {
+    let metrics = live_station_metrics();
+
+    view! {
+        <div class="bg-surface rounded-lg border border-border p-6">
+            <h2 class="text-xl font-semibold text-text mb-4">"Station Performance"</h2>
+            <Show
+                when=move || !metrics.get().is_empty()
+                fallback=|| view! { <p class="text-text-muted">"No stations configured"</p> }
+            >
+                <div class="grid grid-cols-1 md:grid-cols-2 xl:grid-cols-3 gap-4">
+                    {move || {
+                        metrics.get()
+                            .into_iter()
+                            .map(|m| view! { <StationMetricsCard metrics=m/> })
+                            .collect_view()
+                    }}
+                </div>
+            </Show>
+        </div>
+    }
+}
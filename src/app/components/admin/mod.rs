@@ -6,4 +6,9 @@ pub mod create_product;
 pub mod products;
 pub mod create_station;
 pub mod stations;
-pub mod users;
\ No newline at end of file
+pub mod users;
+pub mod alert_rules;
+pub mod pipeline;
+pub mod metrics;
+pub mod event_menu;
+pub mod duplicate_event;
\ No newline at end of file
@@ -0,0 +1,254 @@
+use leptos::prelude::*;
+
+use crate::{
+    app::states::{category, station},
+    common::{
+        pipeline::{analyze, is_sink, CategoryRoute, PipelineIssue, ENTRY_STATUS},
+        types::OrderStatus,
+    },
+};
+
+const NODE_WIDTH: i32 = 120;
+const NODE_HEIGHT: i32 = 36;
+const COLUMN_GAP: i32 = 110;
+const ROW_GAP: i32 = 24;
+const PADDING: i32 = 8;
+
+/// Position of a status node in the graph, statuses are laid out in columns by their distance from the entry status
+#[derive(Debug, Clone)]
+struct Node {
+    status: OrderStatus,
+    x: i32,
+    y: i32,
+    reachable: bool,
+    dead_end: bool,
+}
+
+/// All stations moving items between the same two statuses share one arrow
+#[derive(Debug, Clone)]
+struct Arrow {
+    from: OrderStatus,
+    to: OrderStatus,
+    label: String,
+}
+
+fn layout(route: &CategoryRoute) -> (Vec<Node>, Vec<Arrow>, i32, i32) {
+    let mut columns: Vec<Vec<OrderStatus>> = Vec::new();
+    let mut unreachable: Vec<OrderStatus> = Vec::new();
+
+    for status in &route.reachable {
+        let depth = route.depth(status).unwrap_or_default();
+        if columns.len() <= depth {
+            columns.resize(depth + 1, Vec::new());
+        }
+        columns[depth].push(status.clone());
+    }
+    for edge in &route.edges {
+        for status in [&edge.from, &edge.to] {
+            if !route.reachable.contains(status) && !unreachable.contains(status) {
+                unreachable.push(status.clone());
+            }
+        }
+    }
+    if !unreachable.is_empty() {
+        columns.push(unreachable);
+    }
+
+    let mut nodes = Vec::new();
+    for (column, statuses) in columns.iter().enumerate() {
+        for (row, status) in statuses.iter().enumerate() {
+            let reachable = route.reachable.contains(status);
+            nodes.push(Node {
+                status: status.clone(),
+                x: PADDING + column as i32 * (NODE_WIDTH + COLUMN_GAP),
+                y: PADDING + row as i32 * (NODE_HEIGHT + ROW_GAP),
+                reachable,
+                dead_end: reachable
+                    && !is_sink(status)
+                    && !route.edges.iter().any(|e| e.from == *status && e.to != *status),
+            });
+        }
+    }
+
+    let mut arrows: Vec<Arrow> = Vec::new();
+    for edge in route.edges.iter().filter(|e| e.from != e.to) {
+        match arrows.iter_mut().find(|a| a.from == edge.from && a.to == edge.to) {
+            Some(arrow) => {
+                arrow.label.push_str(", ");
+                arrow.label.push_str(&edge.station_name);
+            }
+            None => arrows.push(Arrow {
+                from: edge.from.clone(),
+                to: edge.to.clone(),
+                label: edge.station_name.clone(),
+            }),
+        }
+    }
+
+    let rows = columns.iter().map(Vec::len).max().unwrap_or(1) as i32;
+    let width = PADDING * 2 + columns.len() as i32 * (NODE_WIDTH + COLUMN_GAP) - COLUMN_GAP;
+    // One extra gap below the last row leaves room for backward edges
+    let height = PADDING * 2 + rows * (NODE_HEIGHT + ROW_GAP);
+    (nodes, arrows, width, height)
+}
+
+#[component]
+fn RouteGraph(route: CategoryRoute) -> impl IntoView {
+    let (nodes, arrows, width, height) = layout(&route);
+    let position = |status: OrderStatus| {
+        nodes
+            .iter()
+            .find(|n| n.status == status)
+            .map(|n| (n.x, n.y))
+            .unwrap_or_default()
+    };
+
+    let arrow_views = arrows
+        .iter()
+        .map(|arrow| {
+            let (fx, fy) = position(arrow.from.clone());
+            let (tx, ty) = position(arrow.to.clone());
+            let (x1, y1) = (fx + NODE_WIDTH, fy + NODE_HEIGHT / 2);
+            let (x2, y2) = (tx, ty + NODE_HEIGHT / 2);
+            // Backward edges loop below the nodes so they don't cross the forward flow
+            let path = if x2 > x1 {
+                format!("M {} {} C {} {}, {} {}, {} {}", x1, y1, x1 + 40, y1, x2 - 40, y2, x2, y2)
+            } else {
+                let bottom = fy.max(ty) + NODE_HEIGHT + ROW_GAP / 2;
+                format!(
+                    "M {} {} C {} {}, {} {}, {} {}",
+                    fx + NODE_WIDTH / 2, fy + NODE_HEIGHT, fx + NODE_WIDTH / 2, bottom, tx + NODE_WIDTH / 2, bottom, tx + NODE_WIDTH / 2, ty + NODE_HEIGHT
+                )
+            };
+            let (lx, ly) = if x2 > x1 {
+                ((x1 + x2) / 2, (y1 + y2) / 2 - 6)
+            } else {
+                ((fx + tx + NODE_WIDTH) / 2, fy.max(ty) + NODE_HEIGHT + ROW_GAP / 2)
+            };
+            view! {
+                <g>
+                    <path d=path fill="none" class="stroke-text-muted" stroke-width="1.5" marker-end="url(#pipeline-arrow)"/>
+                    <text x=lx y=ly text-anchor="middle" class="fill-text-muted text-[10px]">{arrow.label.clone()}</text>
+                </g>
+            }
+        })
+        .collect_view();
+
+    let node_views = nodes
+        .iter()
+        .map(|node| {
+            let stroke = if !node.reachable {
+                "stroke-border"
+            } else if node.dead_end {
+                "stroke-error"
+            } else if is_sink(&node.status) {
+                "stroke-success"
+            } else if node.status == ENTRY_STATUS {
+                "stroke-primary"
+            } else {
+                "stroke-text-muted"
+            };
+            let dash = if node.reachable { "" } else { "4 3" };
+            view! {
+                <g>
+                    <rect
+                        x=node.x
+                        y=node.y
+                        width=NODE_WIDTH
+                        height=NODE_HEIGHT
+                        rx="6"
+                        stroke-width="1.5"
+                        stroke-dasharray=dash
+                        class=format!("fill-surface-elevated {}", stroke)
+                    />
+                    <text
+                        x=node.x + NODE_WIDTH / 2
+                        y=node.y + NODE_HEIGHT / 2 + 4
+                        text-anchor="middle"
+                        class=if node.reachable { "fill-text text-xs" } else { "fill-text-muted text-xs" }
+                    >
+                        {node.status.to_string()}
+                    </text>
+                </g>
+            }
+        })
+        .collect_view();
+
+    view! {
+        <div class="overflow-x-auto">
+            <svg width=width height=height viewBox=format!("0 0 {} {}", width, height)>
+                <defs>
+                    <marker id="pipeline-arrow" viewBox="0 0 10 10" refX="10" refY="5" markerWidth="6" markerHeight="6" orient="auto-start-reverse">
+                        <path d="M 0 0 L 10 5 L 0 10 z" class="fill-text-muted"/>
+                    </marker>
+                </defs>
+                {arrow_views}
+                {node_views}
+            </svg>
+        </div>
+    }
+}
+
+#[component]
+fn IssueRow(issue: PipelineIssue) -> impl IntoView {
+    let class = if issue.is_error() {
+        "p-2 rounded-md border text-sm bg-red-50 border-red-200 text-red-800"
+    } else {
+        "p-2 rounded-md border text-sm bg-yellow-50 border-yellow-200 text-yellow-800"
+    };
+    view! {
+        <li class=class>
+            <span class="font-medium">{if issue.is_error() { "Error: " } else { "Warning: " }}</span>
+            {issue.message()}
+        </li>
+    }
+}
+
+/// Validates the station setup and shows how items of every category move through the stations
+#[component]
+pub fn Pipeline() -> impl IntoView {
+    let station_state = station::get();
+    let category_state = category::get();
+    let stations = station_state.get_stations();
+    let categories = category_state.get_categories();
+
+    let report = Signal::derive(move || analyze(&stations.get(), &categories.get()));
+
+    view! {
+        <div class="bg-surface rounded-lg border border-border p-6">
+            <h2 class="text-xl font-semibold text-text mb-4">"Pipeline"</h2>
+
+            <Show
+                when=move || !report.get().issues.is_empty()
+                fallback=|| view! {
+                    <p class="mb-4 p-2 rounded-md border text-sm bg-green-50 border-green-200 text-green-800">
+                        "Every category is routed through to completion"
+                    </p>
+                }
+            >
+                <ul class="mb-4 space-y-2">
+                    {move || {
+                        report.get().issues
+                            .into_iter()
+                            .map(|issue| view! { <IssueRow issue=issue/> })
+                            .collect_view()
+                    }}
+                </ul>
+            </Show>
+
+            <div class="space-y-4">
+                {move || {
+                    report.get().routes
+                        .into_iter()
+                        .map(|route| view! {
+                            <div class="p-3 bg-surface-elevated rounded-md border border-border">
+                                <h3 class="text-text font-medium mb-2">{route.category.name.clone()}</h3>
+                                <RouteGraph route=route/>
+                            </div>
+                        })
+                        .collect_view()
+                }}
+            </div>
+        </div>
+    }
+}
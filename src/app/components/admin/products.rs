@@ -28,6 +28,7 @@ fn ProductDisplayItem(
     let category_id = product.category_id.clone();
     let price = product.price;
     let active = product.active;
+    let stock = product.stock;
     
     let status_text = if active { "Active" } else { "Inactive" };
     let status_class = if active { "text-green-600" } else { "text-red-600" };
@@ -56,6 +57,7 @@ fn ProductDisplayItem(
                     </div>
                     <div class="flex items-center justify-between mt-1 text-sm text-text-muted">
                         <span class="font-medium">{format!("€{:.2}", price)}</span>
+                        {stock.map(|stock| view! { <span>{format!("Stock: {}", stock)}</span> })}
                     </div>
                     <div class="mt-1">
                         <span class={format!("text-xs font-medium {}", status_class)}>{status_text}</span>
@@ -104,6 +106,7 @@ fn ProductEditItem(
     let (edit_category_id, set_edit_category_id) = signal(product.category_id.clone());
     let (edit_price, set_edit_price) = signal(product.price);
     let (edit_active, set_edit_active) = signal(product.active);
+    let (edit_stock, set_edit_stock) = signal(product.stock);
     
     let update_action = ServerAction::<UpdateProduct>::new();
     
@@ -113,6 +116,7 @@ fn ProductEditItem(
     let original_category_id = product.category_id.clone();
     let original_price = product.price;
     let original_active = product.active;
+    let original_stock = product.stock;
 
     view! {
         <div class="p-3 bg-surface-elevated rounded-md border border-border">
@@ -174,6 +178,26 @@ fn ProductEditItem(
                         />
                     </div>
                     
+                    <div>
+                        <label class="block text-sm font-medium text-text mb-1">"Stock"</label>
+                        <input
+                            type="number"
+                            min="0"
+                            placeholder="Not tracked"
+                            prop:value=move || edit_stock.get().map(|s| s.to_string()).unwrap_or_default()
+                            on:input=move |ev| {
+                                // An emptied input stops tracking the stock
+                                let value = event_target_value(&ev);
+                                if value.trim().is_empty() {
+                                    set_edit_stock.set(None);
+                                } else if let Ok(val) = value.trim().parse::<u32>() {
+                                    set_edit_stock.set(Some(val));
+                                }
+                            }
+                            class="w-full px-2 py-1 border border-border bg-surface text-text rounded focus:outline-none focus:ring-primary focus:border-primary text-sm"
+                        />
+                    </div>
+                    
                     <div class="flex items-center">
                         <input
                             type="checkbox"
@@ -199,6 +223,8 @@ fn ProductEditItem(
                         <input type="hidden" name="update[category_id]" value=move || edit_category_id.get() />
                         <input type="hidden" name="update[price]" value=move || edit_price.get() />
                         <input type="hidden" name="update[active]" value=move || edit_active.get().to_string() />
+                        // Empty while untracked, which clears a stock that was tracked before
+                        <input type="hidden" name="update[stock]" value=move || edit_stock.get().map(|stock| stock.to_string()).unwrap_or_default() />
                         <button
                             type="submit"
                             class="bg-border/80 text-green-600 hover:bg-border hover:scale-105 p-2 rounded"
@@ -214,6 +240,7 @@ fn ProductEditItem(
                             set_edit_category_id.set(original_category_id.clone());
                             set_edit_price.set(original_price);
                             set_edit_active.set(original_active);
+                            set_edit_stock.set(original_stock);
                             on_cancel.set(None);
                         }
                     >
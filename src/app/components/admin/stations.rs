@@ -2,7 +2,7 @@ use leptos::prelude::*;
 
 use crate::{
     app::{
-        components::atoms::icons,
+        components::{admin::create_station::{status_options, ExpoToggle, SlaInputs}, atoms::icons},
         states::{category, station},
     },
     backend::station::{delete_station, UpdateStation},
@@ -47,10 +47,18 @@ fn StationDisplayItem(
     let status_display = move || {
         let input_str = input_statuses
             .iter()
-            .map(|s| format!("{:?}", s))
+            .map(|s| s.to_string())
             .collect::<Vec<String>>()
             .join(", ");
-        format!("[{}] → {:?}", input_str, output_status)
+        format!("[{}] → {}", input_str, output_status)
+    };
+
+    let sla_display = match (station.sla.warn_after_minutes, station.sla.late_after_minutes) {
+        (None, None) => "Off".to_string(),
+        (warn, late) => {
+            let minutes = |m: Option<u32>| m.map(|m| format!("{} min", m)).unwrap_or_else(|| "–".to_string());
+            format!("yellow after {}, red after {}", minutes(warn), minutes(late))
+        }
     };
 
     view! {
@@ -66,6 +74,9 @@ fn StationDisplayItem(
                     <div class="mt-1 text-sm text-text-muted">
                         <div>{"Categories: "}{move || category_names()}</div>
                         <div class="mt-1">{"Status Flow: "}{move || status_display()}</div>
+                        <div class="mt-1">{"Waiting Colours: "}{sla_display}</div>
+                        {station.expo.then(|| view! { <div class="mt-1">"Expo station, hands out whole orders"</div> })}
+                        {station.printer.clone().map(|printer| view! { <div class="mt-1">{"Ticket Printer: "}{printer}</div> })}
                     </div>
                 </div>
                 
@@ -110,7 +121,10 @@ fn StationEditItem(
     let (edit_name, set_edit_name) = signal(station.name.clone());
     let (edit_category_ids, set_edit_category_ids) = signal(station.category_ids.clone());
     let (edit_input_statuses, set_edit_input_statuses) = signal(station.input_statuses.clone());
-    let (edit_output_status, set_edit_output_status) = signal(station.output_status);
+    let (edit_output_status, set_edit_output_status) = signal(station.output_status.clone());
+    let edit_sla = RwSignal::new(station.sla.clone());
+    let edit_expo = RwSignal::new(station.expo);
+    let edit_printer = RwSignal::new(station.printer.clone().unwrap_or_default());
     
     let update_action = ServerAction::<UpdateStation>::new();
     
@@ -119,14 +133,11 @@ fn StationEditItem(
     let original_category_ids = station.category_ids.clone();
     let original_input_statuses = station.input_statuses.clone();
     let original_output_status = station.output_status;
+    let original_sla = station.sla;
+    let original_expo = station.expo;
+    let original_printer = station.printer.clone().unwrap_or_default();
 
-    let all_statuses = || vec![
-        OrderStatus::Draft,
-        OrderStatus::Ordered,
-        OrderStatus::Ready,
-        OrderStatus::Completed,
-        OrderStatus::Cancelled,
-    ];
+    let all_statuses = status_options();
 
     let toggle_category = move |category_id: String| {
         let mut current_ids = edit_category_ids.get();
@@ -199,21 +210,22 @@ fn StationEditItem(
                         <label class="block text-sm font-medium text-text mb-2">"Input Statuses"</label>
                         <div class="grid grid-cols-2 gap-2">
                             <For
-                                each=move || all_statuses()
-                                key=|status| format!("{:?}", status)
+                                each=move || all_statuses.get()
+                                key=|status| status.to_string()
                                 children=move |status| {
-                                    let status_for_toggle = status;
+                                    let status_for_toggle = status.clone();
+                                    let status_for_check = status.clone();
                                     view! {
                                         <label class="flex items-center space-x-2 p-2 border border-border rounded text-sm">
                                             <input
                                                 type="checkbox"
-                                                prop:checked=move || edit_input_statuses.get().contains(&status)
+                                                prop:checked=move || edit_input_statuses.get().contains(&status_for_check)
                                                 on:change=move |_| {
-                                                    toggle_input_status(status_for_toggle);
+                                                    toggle_input_status(status_for_toggle.clone());
                                                 }
                                                 class="h-4 w-4 text-primary focus:ring-primary border-border rounded"
                                             />
-                                            <span class="text-text">{format!("{:?}", status)}</span>
+                                            <span class="text-text">{status.to_string()}</span>
                                         </label>
                                     }
                                 }
@@ -224,25 +236,17 @@ fn StationEditItem(
                     <div>
                         <label class="block text-sm font-medium text-text mb-1">"Output Status"</label>
                         <select
-                            prop:value=move || format!("{:?}", edit_output_status.get())
+                            prop:value=move || edit_output_status.get().to_string()
                             on:change=move |ev| {
-                                let value = event_target_value(&ev);
-                                match value.as_str() {
-                                    "Draft" => set_edit_output_status.set(OrderStatus::Draft),
-                                    "Ordered" => set_edit_output_status.set(OrderStatus::Ordered),
-                                    "Ready" => set_edit_output_status.set(OrderStatus::Ready),
-                                    "Completed" => set_edit_output_status.set(OrderStatus::Completed),
-                                    "Cancelled" => set_edit_output_status.set(OrderStatus::Cancelled),
-                                    _ => {}
-                                }
+                                set_edit_output_status.set(OrderStatus::from_label(&event_target_value(&ev)));
                             }
                             class="w-full px-2 py-1 border border-border bg-surface text-text rounded focus:outline-none focus:ring-primary focus:border-primary text-sm"
                         >
                             <For
-                                each=move || all_statuses()
-                                key=|status| format!("{:?}", status)
+                                each=move || all_statuses.get()
+                                key=|status| status.to_string()
                                 children=move |status| {
-                                    let status_str = format!("{:?}", status);
+                                    let status_str = status.to_string();
                                     let status_str_clone = status_str.clone();
                                     view! {
                                         <option value={status_str} selected=move || edit_output_status.get() == status>
@@ -253,6 +257,16 @@ fn StationEditItem(
                             />
                         </select>
                     </div>
+
+                    <SlaInputs sla=edit_sla />
+                    <ExpoToggle expo=edit_expo />
+                    <input
+                        type="text"
+                        placeholder="Ticket printer, e.g. 192.168.1.50:9100"
+                        prop:value=move || edit_printer.get()
+                        on:input=move |ev| edit_printer.set(event_target_value(&ev))
+                        class="w-full px-2 py-1 border border-border bg-surface placeholder-text-muted text-text rounded focus:outline-none focus:ring-primary focus:border-primary text-sm"
+                    />
                 </div>
                 
                 <div class="flex justify-end space-x-2">
@@ -278,7 +292,14 @@ fn StationEditItem(
                                 serde_json::to_string(&edit_input_statuses.get()).unwrap_or_default()
                             }
                         />
-                        <input type="hidden" name="output_status" value=move || format!("{:?}", edit_output_status.get()) />
+                        <input type="hidden" name="output_status" value=move || edit_output_status.get().to_string() />
+                        <input
+                            type="hidden"
+                            name="sla_json"
+                            value=move || serde_json::to_string(&edit_sla.get()).unwrap_or_default()
+                        />
+                        <input type="hidden" name="expo" value=move || edit_expo.get().to_string() />
+                        <input type="hidden" name="printer" value=move || edit_printer.get() />
                         <button
                             type="submit"
                             class="bg-border/80 text-green-600 hover:bg-border hover:scale-105 p-2 rounded"
@@ -293,7 +314,10 @@ fn StationEditItem(
                             set_edit_name.set(original_name.clone());
                             set_edit_category_ids.set(original_category_ids.clone());
                             set_edit_input_statuses.set(original_input_statuses.clone());
-                            set_edit_output_status.set(original_output_status);
+                            set_edit_output_status.set(original_output_status.clone());
+                            edit_sla.set(original_sla.clone());
+                            edit_expo.set(original_expo);
+                            edit_printer.set(original_printer.clone());
                             on_cancel.set(None);
                         }
                     >
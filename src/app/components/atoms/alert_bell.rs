@@ -0,0 +1,84 @@
+use leptos::prelude::*;
+use leptos_router::components::A;
+use crate::app::states::alert;
+use crate::common::types::{Alert, AlertKind};
+use super::icons::Bell;
+
+fn alert_icon(kind: AlertKind) -> &'static str {
+    match kind {
+        AlertKind::LowStock => "📦",
+        AlertKind::StationBacklog => "🔥",
+        AlertKind::ItemWaiting => "⏱️",
+        AlertKind::StationDisconnected => "🔌",
+        AlertKind::PrinterError => "🖨️",
+    }
+}
+
+#[component]
+fn AlertEntry(alert: Alert) -> impl IntoView {
+    let content = view! {
+        <span class="mr-2">{alert_icon(alert.kind)}</span>
+        <span>{alert.message.clone()}</span>
+    };
+
+    // Station related alerts link to the affected station
+    match alert.station_id {
+        Some(station_id) => view! {
+            <A
+                href=format!("/station/{}", station_id)
+                attr:class="flex items-start px-4 py-2 text-sm text-text hover:bg-surface-elevated hover:text-primary transition-colors"
+            >
+                {content}
+            </A>
+        }.into_any(),
+        None => view! {
+            <div class="flex items-start px-4 py-2 text-sm text-text">
+                {content}
+            </div>
+        }.into_any(),
+    }
+}
+
+#[component]
+pub fn AlertBell() -> impl IntoView {
+    let alert_state = alert::get();
+    let alerts = alert_state.get_alerts();
+    let (is_open, set_is_open) = signal(false);
+
+    view! {
+        <div class="relative">
+            <button
+                class="relative p-2 text-text-muted hover:text-text transition-colors rounded-md hover:bg-surface-elevated"
+                title="Alerts"
+                on:click=move |_| set_is_open.update(|open| *open = !*open)
+            >
+                <Bell attr:class="w-5 h-5"/>
+                <Show when=move || !alerts.get().is_empty()>
+                    <span class="absolute -top-0.5 -right-0.5 min-w-4 h-4 px-1 bg-error text-white text-xs font-bold rounded-full flex items-center justify-center">
+                        {move || alerts.get().len()}
+                    </span>
+                </Show>
+            </button>
+
+            <Show when=move || is_open.get()>
+                <div class="absolute right-0 mt-1 w-80 bg-surface border border-border rounded-md shadow-lg z-50">
+                    <div class="px-4 py-2 border-b border-border text-sm font-semibold text-text">"Alerts"</div>
+                    <Show
+                        when=move || !alerts.get().is_empty()
+                        fallback=|| view! {
+                            <div class="px-4 py-3 text-sm text-text-muted">"Everything is running smoothly"</div>
+                        }
+                    >
+                        <div class="py-1 max-h-96 overflow-y-auto" on:click=move |_| set_is_open.set(false)>
+                            <For
+                                each=move || alerts.get()
+                                key=|alert| (alert.id.clone(), alert.message.clone())
+                                children=move |alert| view! { <AlertEntry alert=alert /> }
+                            />
+                        </div>
+                    </Show>
+                </div>
+            </Show>
+        </div>
+    }
+}
@@ -0,0 +1,54 @@
+use leptos::prelude::*;
+
+use crate::app::states::{event, settings};
+
+/// Booth selector for this device, only shown while several events are active
+#[component]
+pub fn EventPicker() -> impl IntoView {
+    let settings_state = settings::get();
+    let settings = settings_state.get_settings();
+    let event_id = settings_state.event_id();
+    let events = event::get().get_events();
+
+    let active_events = move || {
+        let settings = settings.get()?;
+        let active: Vec<(String, String)> = settings
+            .active_event_ids
+            .iter()
+            .map(|id| {
+                let name = events.with(|events| events.iter().find(|e| &e.id == id).map(|e| e.name.clone()));
+                (id.clone(), name.unwrap_or_else(|| id.clone()))
+            })
+            .collect();
+        (active.len() > 1).then_some(active)
+    };
+
+    move || {
+        active_events().map(|active| {
+            let settings_state = settings_state.clone();
+            view! {
+                <select
+                    class=move || format!(
+                        "px-2 py-1 rounded-md border bg-surface text-sm {}",
+                        if event_id.get().is_some() { "border-border text-text" } else { "border-red-500 text-red-600" }
+                    )
+                    title="Event this device works for"
+                    on:change=move |ev| settings_state.choose_event(event_target_value(&ev))
+                >
+                    <option value="" disabled selected=move || event_id.get().is_none()>"Choose booth"</option>
+                    {active
+                        .into_iter()
+                        .map(|(id, name)| {
+                            let selected_id = id.clone();
+                            view! {
+                                <option value=id selected=move || event_id.get().as_ref() == Some(&selected_id)>
+                                    {name}
+                                </option>
+                            }
+                        })
+                        .collect_view()}
+                </select>
+            }
+        })
+    }
+}
@@ -1,4 +1,6 @@
 pub mod icons;
 pub mod connection_indicator;
 pub mod user_dropdown;
-pub mod logout_button;
\ No newline at end of file
+pub mod logout_button;
+pub mod alert_bell;
+pub mod event_picker;
\ No newline at end of file
@@ -1,3 +1,5 @@
 pub mod products;
 pub mod order;
-pub mod order_info;
\ No newline at end of file
+pub mod order_info;
+pub mod order_history;
+pub mod shortcuts;
\ No newline at end of file
@@ -74,8 +74,10 @@ fn OrderItemComponent(
 pub fn Order() -> impl IntoView {
     let order_state = order::get();
     let order_items = order_state.get_items();
+    let rush = order_state.get_rush();
+    let payment_method = order_state.get_payment_method();
     let settings_state = settings::get();
-    let settings = settings_state.get_settings();
+    let device_event_id = settings_state.event_id();
     let event_state = event::get();
     let events = event_state.get_events();
     let order_info_state = order_info::get();
@@ -132,17 +134,15 @@ pub fn Order() -> impl IntoView {
                         quantity: item.quantity,
                         price: item.price,
                         status: types::OrderStatus::Ordered,
+                        event: None, // Assigned by the server
                     })
                     .collect();
                 
-                // Get active event or show error if none set
-                let active_event_id = settings_state.get_settings().get_untracked()
-                    .and_then(|s| s.active_event_id);
-                
-                let event_id = match active_event_id {
+                // Get the event of this device or show error if there is none
+                let event_id = match settings_state.event_id().get_untracked() {
                     Some(id) => id,
                     None => {
-                        set_order_error.set(Some("No active event set. Please ask an admin to set an active event.".to_string()));
+                        set_order_error.set(Some("No event selected for this device. Choose the booth in the navigation bar or ask an admin to activate an event.".to_string()));
                         set_is_creating_order.set(false);
                         return;
                     }
@@ -151,12 +151,14 @@ pub fn Order() -> impl IntoView {
                 let request = requests::order::Create {
                     event: event_id,
                     items,
+                    rush: order_state.get_rush().get_untracked(),
+                    payment_method: order_state.get_payment_method().get_untracked(),
                 };
                 
                 match create_order(request).await {
                     Ok(created_order) => {
                         // Order created successfully, show the order name
-                        order_info_state.set_order_created(created_order.id);
+                        order_info_state.set_order_created(created_order);
                         // Clear the cart
                         order_state.clear();
                         set_is_creating_order.set(false);
@@ -170,6 +172,21 @@ pub fn Order() -> impl IntoView {
         }
     });
 
+    // Submit shortcut of the keyboard, the first run only registers the counter
+    Effect::new({
+        let submit_requests = order_state.get_submit_requests();
+        move |previous: Option<u32>| {
+            let requests = submit_requests.get();
+            if previous.is_some_and(|previous| previous != requests)
+                && !is_creating_order.get_untracked()
+                && !order_items.get_untracked().is_empty()
+            {
+                create_order_action.dispatch(());
+            }
+            requests
+        }
+    });
+
     // Effect to collapse order info when new items are added to cart
     Effect::new({
         let order_items = order_items.clone();
@@ -245,23 +262,23 @@ pub fn Order() -> impl IntoView {
             <div class="mb-6 p-4 bg-surface-elevated rounded-xl border border-border shadow-sm">
                 <div class="text-sm font-semibold text-text-muted mb-2">"Active Event"</div>
                 <Show
-                    when=move || {
-                        settings.get().and_then(|s| s.active_event_id).is_some()
-                    }
+                    when=move || device_event_id.get().is_some()
                     fallback=|| view! {
-                        <div class="text-sm text-error font-semibold">"No active event set"</div>
-                        <div class="text-xs text-error opacity-80">"Ask an admin to set an active event"</div>
+                        <div class="text-sm text-error font-semibold">"No event selected for this device"</div>
+                        <div class="text-xs text-error opacity-80">"Choose the booth in the navigation bar or ask an admin to activate an event"</div>
                     }
                 >
                     <div class="text-sm text-success font-semibold">
                         {move || {
-                            let active_event_id = settings.get().and_then(|s| s.active_event_id);
-                            if let Some(event_id) = active_event_id {
+                            if let Some(event_id) = device_event_id.get() {
                                 // Find the event name from the events list
                                 events.get()
                                     .iter()
                                     .find(|e| e.id == event_id)
-                                    .map(|e| e.name.clone())
+                                    .map(|e| match e.state {
+                                        types::EventState::Open => e.name.clone(),
+                                        state => format!("{} ({})", e.name, state.label()),
+                                    })
                                     .unwrap_or_else(|| format!("Event: {}", event_id))
                             } else {
                                 "Unknown".to_string()
@@ -312,6 +329,44 @@ pub fn Order() -> impl IntoView {
                         <span class="text-xl sm:text-2xl font-bold text-primary">{move || format!("€{:.2}", total_price())}</span>
                     </div>
 
+                    <button
+                        class=move || format!(
+                            "w-full py-3 px-4 rounded-xl border-2 font-semibold transition-all duration-200 touch-manipulation {}",
+                            if rush.get() {
+                                "bg-error/10 border-error text-error"
+                            } else {
+                                "bg-surface-elevated border-border text-text-muted hover:text-text"
+                            }
+                        )
+                        on:click={
+                            let order_state = order_state.clone();
+                            move |_| order_state.toggle_rush()
+                        }
+                    >
+                        {move || if rush.get() { "Rush! Prepared first" } else { "Mark as rush" }}
+                    </button>
+
+                    <div class="grid grid-cols-2 gap-2">
+                        {types::PaymentMethod::ALL.into_iter().map(|method| {
+                            let order_state = order_state.clone();
+                            view! {
+                                <button
+                                    class=move || format!(
+                                        "py-3 px-4 rounded-xl border-2 font-semibold transition-all duration-200 touch-manipulation {}",
+                                        if payment_method.get() == method {
+                                            "bg-primary/10 border-primary text-primary"
+                                        } else {
+                                            "bg-surface-elevated border-border text-text-muted hover:text-text"
+                                        }
+                                    )
+                                    on:click=move |_| order_state.set_payment_method(method)
+                                >
+                                    {method.label()}
+                                </button>
+                            }
+                        }).collect_view()}
+                    </div>
+
                     <Show when=move || order_error.get().is_some()>
                         <div class="p-4 bg-error/10 border border-error/20 rounded-xl">
                             <p class="text-sm text-error font-medium">{move || order_error.get().unwrap_or_default()}</p>
@@ -0,0 +1,91 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+
+use crate::app::components::station_view::OrderSearch;
+use crate::app::states::{settings, websocket};
+use crate::backend::order::get_orders;
+use crate::common::resource_type::Message;
+use crate::common::types;
+
+/// How many of the latest orders are listed when not searching
+const HISTORY_LENGTH: usize = 10;
+
+/// Latest orders of the active event, so receipts can be printed again
+#[component]
+pub fn OrderHistory() -> impl IntoView {
+    let orders = RwSignal::new(Vec::<types::Order>::new());
+    let (search, set_search) = signal(String::new());
+    let websocket_state = websocket::get();
+    // Nothing is loaded or applied until the device knows its event
+    let event_scope = settings::get().event_scope();
+
+    Effect::new(move |_| {
+        let Some(event_id) = event_scope.get() else {
+            orders.set(Vec::new());
+            return;
+        };
+        spawn_local(async move {
+            if let Ok(loaded) = get_orders(event_id).await {
+                orders.set(loaded);
+            }
+        });
+    });
+
+    Effect::new({
+        let websocket_state = websocket_state.clone();
+        move |_| {
+            if let Some(message) = websocket_state.orders.get() {
+                if event_scope.get_untracked().is_some() {
+                    orders.update(|orders| match message {
+                        Message::Add(order) => orders.push(order),
+                        Message::Update(order) => {
+                            if let Some(existing) = orders.iter_mut().find(|o| o.id == order.id) {
+                                *existing = order;
+                            }
+                        }
+                        Message::Delete(id) => orders.retain(|o| o.id != id),
+                    });
+                }
+                websocket_state.orders.set(None);
+            }
+        }
+    });
+
+    let shown = move || {
+        let search = search.get();
+        let mut shown: Vec<types::Order> = orders.get().into_iter().filter(|o| o.matches(&search)).collect();
+        shown.sort_by_key(|o| std::cmp::Reverse(o.number));
+        shown.truncate(HISTORY_LENGTH);
+        shown
+    };
+
+    view! {
+        <div class="mt-4 bg-surface rounded-xl border border-border p-4 shadow-lg">
+            <div class="flex items-center justify-between mb-3">
+                <h3 class="text-sm font-semibold text-text-muted">"Recent orders"</h3>
+                <OrderSearch search=search set_search=set_search />
+            </div>
+            <ul class="space-y-1">
+                <For
+                    each=shown
+                    key=|order| order.id.clone()
+                    children=move |order| view! {
+                        <li class="flex items-center justify-between py-1 text-sm">
+                            <span class="text-text">
+                                <span class="text-text-muted mr-2">{order.number_label()}</span>
+                                {order.name.clone()}
+                            </span>
+                            <a
+                                href=format!("/receipt/{}", order.id)
+                                target="_blank"
+                                class="text-primary hover:underline"
+                            >
+                                "Receipt"
+                            </a>
+                        </li>
+                    }
+                />
+            </ul>
+        </div>
+    }
+}
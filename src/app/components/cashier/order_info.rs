@@ -1,11 +1,14 @@
 use leptos::prelude::*;
 use crate::app::components::atoms::icons;
-use crate::common::german_names::generate_german_name;
+use crate::backend::tracking::get_order_qr;
+use crate::common::german_names::pickup_name;
+use crate::common::types;
 
 #[derive(Debug, Clone)]
 pub struct OrderInfo {
     pub order_id: String,
     pub name: String,
+    pub number: String, // Short number for the paper fallback, e.g. `#042`
 }
 
 #[derive(Debug, Clone)]
@@ -29,11 +32,11 @@ impl OrderInfoState {
         }
     }
     
-    pub fn set_order_created(&self, order_id: String) {
-        let name = generate_german_name(&order_id);
+    pub fn set_order_created(&self, order: types::Order) {
         let order_info = OrderInfo {
-            order_id: order_id.clone(),
-            name,
+            name: pickup_name(&order.id, Some(&order)),
+            number: order.number_label(),
+            order_id: order.id,
         };
         self.set_info.set(Some(order_info));
         self.set_is_expanded.set(true);
@@ -118,31 +121,62 @@ fn CollapsedOrderInfo() -> impl IntoView {
 #[component]
 fn ExpandedOrderInfo(info: ReadSignal<Option<OrderInfo>>) -> impl IntoView {
     let order_info_state = get();
+    // Customers scan this to follow their order on their phone
+    let qr_code = Resource::new(
+        move || info.get().map(|i| i.order_id),
+        |order_id| async move {
+            match order_id {
+                Some(order_id) => get_order_qr(order_id).await.ok(),
+                None => None,
+            }
+        },
+    );
     
     view! {
-        <button
-            class="w-full p-4 bg-success/5 border-2 border-success/30 rounded-xl shadow-lg text-left transition-all duration-300 hover:bg-success/10 group"
-            on:click=move |_| order_info_state.collapse()
-        >
-            <div class="flex items-center justify-between mb-3">
-                <div class="flex items-center space-x-3">
-                    <div class="w-3 h-3 bg-success rounded-full animate-pulse"></div>
-                    <h3 class="text-lg font-bold text-text">"Bestellungsname"</h3>
+        <div class="space-y-2">
+            <button
+                class="w-full p-4 bg-success/5 border-2 border-success/30 rounded-xl shadow-lg text-left transition-all duration-300 hover:bg-success/10 group"
+                on:click=move |_| order_info_state.collapse()
+            >
+                <div class="flex items-center justify-between mb-3">
+                    <div class="flex items-center space-x-3">
+                        <div class="w-3 h-3 bg-success rounded-full animate-pulse"></div>
+                        <h3 class="text-lg font-bold text-text">"Bestellungsname"</h3>
+                    </div>
+                    <icons::ChevronUp attr:class="w-4 h-4 text-text-muted group-hover:text-text transition-colors"/>
                 </div>
-                <icons::ChevronUp attr:class="w-4 h-4 text-text-muted group-hover:text-text transition-colors"/>
-            </div>
             
-            <div class="space-y-2">
-                <div class="p-3 bg-surface-elevated rounded-lg border border-border">
-                    <div class="text-2xl font-bold text-primary">
-                        {move || info.get().as_ref().map(|i| i.name.clone()).unwrap_or_default()}
+                <div class="space-y-2">
+                    <div class="p-3 bg-surface-elevated rounded-lg border border-border flex items-baseline justify-between">
+                        <div class="text-2xl font-bold text-primary">
+                            {move || info.get().as_ref().map(|i| i.name.clone()).unwrap_or_default()}
+                        </div>
+                        <div class="text-xl font-semibold text-text-muted">
+                            {move || info.get().as_ref().map(|i| i.number.clone()).unwrap_or_default()}
+                        </div>
                     </div>
-                </div>
                 
-                <div class="text-xs text-text-muted text-center mt-2">
-                    "An den anderen Stationen wird dieser Name auch angezeigt!"
+                    <div class="text-xs text-text-muted text-center mt-2">
+                        "An den anderen Stationen wird dieser Name auch angezeigt!"
+                    </div>
+
+                    <Suspense fallback=|| ()>
+                        {move || qr_code.get().flatten().map(|svg| view! {
+                            <div class="flex flex-col items-center mt-3">
+                                <div class="bg-white p-2 rounded-lg" inner_html=svg></div>
+                                <span class="text-xs text-text-muted mt-1">"Scan to follow the status live"</span>
+                            </div>
+                        })}
+                    </Suspense>
                 </div>
-            </div>
-        </button>
+            </button>
+            <a
+                href=move || info.get().map(|i| format!("/receipt/{}", i.order_id)).unwrap_or_default()
+                target="_blank"
+                class="block w-full py-2 px-4 rounded-xl border border-border bg-surface-elevated text-center text-sm font-semibold text-text-muted hover:text-text transition-colors"
+            >
+                "Print receipt"
+            </a>
+        </div>
     }
 }
\ No newline at end of file
@@ -60,9 +60,8 @@ fn CategorySection(
 
 #[component]
 pub fn CashierProducts() -> impl IntoView {
-    let product_state = product::get();
     let category_state = category::get();
-    let products = product_state.get_products();
+    let products = product::menu_products();
     let categories = category_state.get_categories();
 
     let products_by_category = move || {
@@ -0,0 +1,107 @@
+use leptos::prelude::*;
+
+use crate::app::components::shortcuts::{self, use_shortcut_keys, Binding, ShortcutSettings};
+use crate::app::states::{order, product};
+use crate::common::shortcuts::{CashierCommand, CashierKeys, CashierTarget, QuantityEntry};
+
+const STORAGE_KEY: &str = "shortcuts:cashier";
+
+fn target_id(target: &CashierTarget) -> String {
+    match target {
+        CashierTarget::Product(product_id) => format!("product:{}", product_id),
+        CashierTarget::Submit => "submit".to_string(),
+        CashierTarget::Clear => "clear".to_string(),
+    }
+}
+
+/// Product hotkeys, quantities typed before a product key, submit and clear, with their settings
+#[component]
+pub fn CashierShortcuts() -> impl IntoView {
+    let order_state = order::get();
+    let products = product::menu_products();
+    let keys = RwSignal::new(CashierKeys::default());
+    let entry = RwSignal::new(QuantityEntry::default());
+    let capturing = RwSignal::new(None::<String>);
+    Effect::new(move |_| keys.set(shortcuts::load(STORAGE_KEY)));
+
+    let targets = move || {
+        let mut targets = vec![CashierTarget::Submit, CashierTarget::Clear];
+        targets.extend(
+            products
+                .get()
+                .into_iter()
+                .filter(|product| product.active)
+                .map(|product| CashierTarget::Product(product.id)),
+        );
+        targets
+    };
+
+    use_shortcut_keys(move |key| {
+        if let Some(id) = capturing.get_untracked() {
+            if let Some(target) = untrack(targets).into_iter().find(|target| target_id(target) == id) {
+                keys.update(|keys| {
+                    keys.bind(&target, key);
+                });
+                save_keys(&keys.get_untracked());
+            }
+            capturing.set(None);
+            return true;
+        }
+
+        let Some(command) = keys.with_untracked(|keys| entry.try_update(|entry| entry.press(key, keys))).flatten() else {
+            // Digits are handled by the quantity entry
+            return entry.with_untracked(|entry| entry.quantity().is_some());
+        };
+        match command {
+            CashierCommand::Add { product_id, quantity } => {
+                if let Some(product) = products.get_untracked().into_iter().find(|p| p.id == product_id) {
+                    order_state.add_product_quantity(product, quantity);
+                }
+            }
+            CashierCommand::Submit => order_state.request_submit(),
+            CashierCommand::Clear => order_state.clear(),
+        }
+        true
+    });
+
+    let bindings = Signal::derive(move || {
+        let products = products.get();
+        keys.with(|keys| {
+            targets()
+                .into_iter()
+                .map(|target| Binding {
+                    id: target_id(&target),
+                    label: match &target {
+                        CashierTarget::Product(product_id) => products
+                            .iter()
+                            .find(|p| &p.id == product_id)
+                            .map(|p| p.name.clone())
+                            .unwrap_or_default(),
+                        CashierTarget::Submit => "Submit order".to_string(),
+                        CashierTarget::Clear => "Clear order".to_string(),
+                    },
+                    key: keys.key(&target).to_string(),
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    let reset_keys = move |_| {
+        keys.set(CashierKeys::default());
+        save_keys(&CashierKeys::default());
+    };
+
+    view! {
+        <div class="mt-4 space-y-2">
+            <Show when=move || entry.with(|entry| entry.quantity().is_some())>
+                <div class="px-4 py-2 rounded-lg bg-primary/10 border border-primary text-primary font-semibold">
+                    {move || format!("Quantity: {}", entry.with(|entry| entry.quantity().unwrap_or_default()))}
+                </div>
+            </Show>
+            <ShortcutSettings bindings=bindings capturing=capturing on_reset=reset_keys />
+        </div>
+    }
+}
+
+fn save_keys(keys: &CashierKeys) {
+    shortcuts::save(STORAGE_KEY, keys);
+}
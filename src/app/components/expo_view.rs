@@ -0,0 +1,289 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_use::core::ConnectionReadyState;
+#[cfg(feature = "hydrate")]
+use leptos_use::{use_timestamp_with_options, UseTimestampOptions};
+use std::collections::HashMap;
+
+use crate::app::components::atoms::icons;
+use crate::app::components::station_view::{bump_label, Bump, OrderSearch, RecentlyCompleted, UndoToast};
+use crate::app::states::{product, settings, station, websocket};
+use crate::backend::item::{complete_order, get_items};
+use crate::backend::order::get_orders;
+use crate::common::expo::{consolidate, ExpoOrder};
+use crate::common::metrics::format_duration;
+use crate::common::resource_type::Message;
+use crate::common::station::apply_item_message;
+use crate::common::{german_names, types};
+
+/// One order at the expo station, complete orders can be handed out with a single tap
+#[component]
+fn ExpoOrderCard(
+    order: ExpoOrder,
+    name: String,
+    number: Option<String>,
+    products: Signal<HashMap<String, types::Product>>,
+    input_statuses: Vec<types::OrderStatus>,
+    station_id: String,
+    created_at: Option<i64>,
+    rush: bool,
+    now: Signal<i64>,
+    on_bumped: WriteSignal<Option<Bump>>,
+) -> impl IntoView {
+    let ready = order.is_ready();
+    let progress = format!("{}/{} ready", order.ready, order.items.len());
+    let waiting_secs = move || created_at.map(|created| (now.get() - created).max(0));
+    let order_id = order.order_id.clone();
+    let label = bump_label(&name);
+
+    let complete_action = Action::new(move |_: &()| {
+        let order_id = order_id.clone();
+        let station_id = station_id.clone();
+        let label = label.clone();
+        async move {
+            if let Ok(updated) = complete_order(order_id, station_id).await {
+                on_bumped.set(Some(Bump {
+                    label,
+                    item_ids: updated.into_iter().map(|item| item.id).collect(),
+                    at: now.get_untracked(),
+                }));
+            }
+        }
+    });
+
+    let card_class = if ready {
+        "rounded-lg border-2 p-4 bg-green-600/10 border-green-600"
+    } else {
+        "rounded-lg border-2 border-dashed p-4 bg-surface border-yellow-500"
+    };
+
+    view! {
+        <div class=card_class>
+            <div class="flex items-center justify-between mb-4">
+                <div>
+                    <h3 class="text-lg font-semibold text-text" data-order-id=order.order_id.clone()>
+                        {number.map(|number| format!("{} ", number))}{"Bestellung '"}{name}{"'"}
+                    </h3>
+                    <p class="text-sm text-text-muted">
+                        {progress}
+                        {move || waiting_secs().map(|secs| format!(" · waiting {}", format_duration(secs)))}
+                    </p>
+                    {rush.then(|| view! {
+                        <span class="inline-block mt-1 px-2 py-0.5 rounded text-xs font-bold bg-error text-white">"RUSH"</span>
+                    })}
+                </div>
+                {ready.then(|| view! {
+                    <button
+                        class="bg-green-600 text-white hover:bg-green-700 px-4 py-2 rounded text-sm font-semibold transition-colors"
+                        on:click=move |_| {
+                            complete_action.dispatch(());
+                        }
+                        disabled=move || complete_action.pending().get()
+                    >
+                        {move || if complete_action.pending().get() { "..." } else { "Hand out" }}
+                    </button>
+                })}
+            </div>
+
+            <ul class="space-y-1 text-sm">
+                {order.items.into_iter().map(|item| {
+                    let item_ready = input_statuses.contains(&item.status);
+                    let product_id = item.product_id.clone();
+                    let name = move || products.with(|products| {
+                        products.get(&product_id).map(|p| p.name.clone()).unwrap_or_else(|| "Unknown Product".to_string())
+                    });
+                    view! {
+                        <li class="flex items-center justify-between">
+                            <span class=if item_ready { "text-text" } else { "text-text-muted" }>
+                                {format!("{}× ", item.quantity)}{name}
+                            </span>
+                            <span class=if item_ready { "text-green-600" } else { "text-yellow-600" }>
+                                {item.status.to_string()}
+                            </span>
+                        </li>
+                    }
+                }).collect_view()}
+            </ul>
+        </div>
+    }
+}
+
+/// Board of an expo station, showing orders once items of them are ready and handing out complete ones
+#[component]
+pub fn ExpoView(station_id: String) -> impl IntoView {
+    let items = RwSignal::new(Vec::<types::Item>::new());
+    let orders_by_id = RwSignal::new(HashMap::<String, types::Order>::new());
+    let last_bump = RwSignal::new(None::<Bump>);
+    let (search, set_search) = signal(String::new());
+    let (reload_trigger, set_reload_trigger) = signal::<u32>(0);
+    let websocket_state = websocket::get();
+    let products = product::get().get_products();
+    let stations = station::get().get_stations();
+    let card_station_id = StoredValue::new(station_id.clone());
+
+    let station_config = {
+        let station_id = station_id.clone();
+        Memo::new(move |_| stations.get().into_iter().find(|s| s.id == station_id))
+    };
+
+    // Nothing is loaded or applied until the device knows its event
+    let event_scope = settings::get().event_scope();
+
+    // Every item of the event is kept, the expo needs the items other stations still work on as well
+    Effect::new({
+        let websocket_state = websocket_state.clone();
+        move |_| {
+            if let Some(message) = websocket_state.items.get() {
+                if let Some(event_id) = event_scope.get_untracked() {
+                    items.update(|items| apply_item_message(items, message, event_id.as_deref(), |_| true));
+                }
+                websocket_state.items.set(None);
+            }
+        }
+    });
+
+    Effect::new({
+        let websocket_state = websocket_state.clone();
+        move |_| {
+            if let Some(message) = websocket_state.orders.get() {
+                if event_scope.get_untracked().is_some() {
+                    orders_by_id.update(|orders| match message {
+                        Message::Add(order) | Message::Update(order) => {
+                            orders.insert(order.id.clone(), order);
+                        }
+                        Message::Delete(id) => {
+                            orders.remove(&id);
+                        }
+                    });
+                }
+                websocket_state.orders.set(None);
+            }
+        }
+    });
+
+    #[cfg(feature = "hydrate")]
+    let now = {
+        let timestamp = use_timestamp_with_options(UseTimestampOptions::default().interval(1000));
+        Signal::derive(move || (timestamp.get() / 1000.0) as i64)
+    };
+    #[cfg(not(feature = "hydrate"))]
+    let now = Signal::derive(|| 0i64);
+
+    Effect::new({
+        let websocket_state = websocket_state.clone();
+        move |was_open: Option<bool>| {
+            let open = websocket_state.state.get() == ConnectionReadyState::Open;
+            if open && was_open == Some(false) {
+                set_reload_trigger.update(|n| *n += 1);
+            }
+            open
+        }
+    });
+
+    let station_presence = websocket_state.station_presence;
+    station_presence.set(Some(station_id.clone()));
+    on_cleanup(move || station_presence.set(None));
+
+    Effect::new(move |_| {
+        reload_trigger.track();
+        let Some(event_id) = event_scope.get() else {
+            orders_by_id.set(HashMap::new());
+            items.set(Vec::new());
+            return;
+        };
+        spawn_local(async move {
+            if let Ok(loaded) = get_orders(event_id.clone()).await {
+                orders_by_id.set(loaded.into_iter().map(|o| (o.id.clone(), o)).collect());
+            }
+            if let Ok(loaded) = get_items(event_id).await {
+                items.set(loaded);
+            }
+        });
+    });
+
+    let product_map = Signal::derive(move || {
+        products
+            .get()
+            .into_iter()
+            .map(|p| (p.id.clone(), p))
+            .collect::<HashMap<String, types::Product>>()
+    });
+
+    // Complete orders first, then rush orders and the longest waiting
+    let expo_orders = move || {
+        let Some(station) = station_config.get() else {
+            return Vec::new();
+        };
+        let orders = orders_by_id.get();
+        let search = search.get();
+        let mut expo_orders: Vec<(ExpoOrder, Option<types::Order>)> = consolidate(&items.get(), &station)
+            .into_iter()
+            .filter(|expo_order| {
+                search.trim().is_empty() || orders.get(&expo_order.order_id).is_some_and(|o| o.matches(&search))
+            })
+            .map(|expo_order| {
+                let order = orders.get(&expo_order.order_id).cloned();
+                (expo_order, order)
+            })
+            .collect();
+        expo_orders.sort_by_key(|(expo_order, order)| {
+            let (rush, created_at) = order.as_ref().map(|o| (o.rush, o.created_at)).unwrap_or((false, i64::MAX));
+            (!expo_order.is_ready(), !rush, created_at)
+        });
+        expo_orders
+    };
+
+    view! {
+        <div class="space-y-6">
+            <OrderSearch search=search set_search=set_search />
+            <Show
+                when=move || !expo_orders().is_empty()
+                fallback=|| view! {
+                    <div class="text-center py-12">
+                        <icons::Inbox attr:class="mx-auto h-12 w-12 text-text-muted mb-4"/>
+                        <p class="text-text-muted text-lg">"No orders ready for handout"</p>
+                        <p class="text-text-muted text-sm mt-2">"Orders appear here as soon as the first of their items is ready"</p>
+                    </div>
+                }
+            >
+                <div class="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 xl:grid-cols-4 gap-4">
+                    <For
+                        each=expo_orders
+                        key=|(expo_order, order)| {
+                            let item_keys: Vec<(&str, &types::OrderStatus)> = expo_order
+                                .items
+                                .iter()
+                                .map(|item| (item.id.as_str(), &item.status))
+                                .collect();
+                            let flags = order.as_ref().map(|o| (o.rush, o.created_at));
+                            format!("{}:{:?}:{:?}", expo_order.order_id, item_keys, flags)
+                        }
+                        children=move |(expo_order, order)| {
+                            let input_statuses = station_config
+                                .get_untracked()
+                                .map(|s| s.input_statuses)
+                                .unwrap_or_default();
+                            view! {
+                                <ExpoOrderCard
+                                    name=german_names::pickup_name(&expo_order.order_id, order.as_ref())
+                                    number=order.as_ref().map(|o| o.number_label())
+                                    order=expo_order
+                                    products=product_map
+                                    input_statuses=input_statuses
+                                    station_id=card_station_id.get_value()
+                                    created_at=order.as_ref().map(|o| o.created_at)
+                                    rush=order.as_ref().is_some_and(|o| o.rush)
+                                    now=now
+                                    on_bumped=last_bump.write_only()
+                                />
+                            }
+                        }
+                    />
+                </div>
+            </Show>
+
+            <RecentlyCompleted station_id=station_id.clone() items=items />
+            <UndoToast station_id=station_id.clone() last_bump=last_bump now=now />
+        </div>
+    }
+}
@@ -9,4 +9,7 @@ pub mod role_gated;
 pub mod state_provider;
 
 pub mod station_view;
-pub mod stations;
\ No newline at end of file
+pub mod expo_view;
+pub mod station_metrics;
+pub mod stations;
+pub mod shortcuts;
\ No newline at end of file
@@ -1,6 +1,6 @@
 use crate::app::{
     components::{
-        atoms::{connection_indicator::ConnectionIndicator, icons::{Menu, Moon, OrderStream, Sun, SystemTheme, UserProfile}, user_dropdown::UserDropdown, logout_button::LogoutButton},
+        atoms::{alert_bell::AlertBell, connection_indicator::ConnectionIndicator, event_picker::EventPicker, icons::{Menu, Moon, OrderStream, Sun, SystemTheme, UserProfile}, user_dropdown::UserDropdown, logout_button::LogoutButton},
         role_gated::RoleGated,
     },
     states::{user, websocket},
@@ -104,7 +104,7 @@ pub fn Navbar() -> impl IntoView {
 
     view! {
         <Meta name="color-scheme" content=move || theme.get().as_str() />
-        <nav class="bg-surface border-b border-border w-full">
+        <nav class="bg-surface border-b border-border w-full print:hidden">
             <div class="mx-auto px-4 sm:px-6 lg:px-8">
                 <div class="flex justify-between items-center h-16">
                     // Left side - Logo and connection indicator
@@ -161,8 +161,17 @@ pub fn Navbar() -> impl IntoView {
                         </div>
                     </div>
 
-                    // Right side - Theme toggle and user menu
+                    // Right side - Booth, alerts, theme toggle and user menu
                     <div class="flex items-center space-x-2 sm:space-x-4">
+                        <RoleGated
+                            roles=vec![Role::Admin, Role::Cashier, Role::Staff]
+                            children=|| view! { <EventPicker /> }.into_any()
+                        />
+                        <RoleGated
+                            roles=vec![Role::Admin]
+                            children=|| view! { <AlertBell /> }.into_any()
+                        />
+
                         <button
                             on:click=toggle_theme
                             class="p-2 text-text-muted hover:text-text transition-colors rounded-md hover:bg-surface-elevated"
@@ -0,0 +1,125 @@
+use leptos::ev;
+use leptos::prelude::*;
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::common::shortcuts::key_label;
+
+/// Key bindings saved on this device, the defaults if there are none yet
+pub fn load<T: DeserializeOwned + Default>(storage_key: &str) -> T {
+    web_sys::window()
+        .and_then(|window| window.local_storage().ok().flatten())
+        .and_then(|storage| storage.get_item(storage_key).ok().flatten())
+        .and_then(|json| serde_json::from_str(&json).ok())
+        .unwrap_or_default()
+}
+
+pub fn save<T: Serialize>(storage_key: &str, bindings: &T) {
+    if let (Some(Ok(Some(storage))), Ok(json)) = (
+        web_sys::window().map(|window| window.local_storage()),
+        serde_json::to_string(bindings),
+    ) {
+        storage.set_item(storage_key, &json).ok();
+    }
+}
+
+/// Whether the user is typing into a form field, e.g. the order search
+fn is_typing() -> bool {
+    web_sys::window()
+        .and_then(|window| window.document())
+        .and_then(|document| document.active_element())
+        .is_some_and(|element| matches!(element.tag_name().as_str(), "INPUT" | "TEXTAREA" | "SELECT"))
+}
+
+/// Calls the handler with the key of every key press outside form fields until the component is
+/// dropped. Key presses the handler returns `true` for don't reach the browser, e.g. Space scrolling.
+pub fn use_shortcut_keys(handler: impl Fn(&str) -> bool + 'static) {
+    let handle = window_event_listener(ev::keydown, move |event| {
+        // Held keys repeat, which must not bump several orders; modifiers leave browser shortcuts alone
+        if event.repeat() || event.ctrl_key() || event.alt_key() || event.meta_key() || is_typing() {
+            return;
+        }
+        if handler(&event.key()) {
+            event.prevent_default();
+        }
+    });
+    on_cleanup(move || handle.remove());
+}
+
+/// One rebindable action, identified by `id`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Binding {
+    pub id: String,
+    pub label: String,
+    pub key: String,
+}
+
+/// Collapsible list of the key bindings, while `capturing` holds an id the next key press is bound to it
+#[component]
+pub fn ShortcutSettings(
+    bindings: Signal<Vec<Binding>>,
+    capturing: RwSignal<Option<String>>,
+    #[prop(into)] on_reset: Callback<()>,
+) -> impl IntoView {
+    let (open, set_open) = signal(false);
+
+    view! {
+        <div class="bg-surface rounded-lg border border-border">
+            <button
+                class="w-full flex items-center justify-between px-4 py-3 text-sm font-medium text-text"
+                on:click=move |_| {
+                    set_open.update(|open| *open = !*open);
+                    capturing.set(None);
+                }
+            >
+                <span>"⌨ Keyboard shortcuts"</span>
+                <span class="text-text-muted">{move || if open.get() { "▲" } else { "▼" }}</span>
+            </button>
+            <Show when=move || open.get()>
+                <div class="px-4 pb-4 space-y-2">
+                    <p class="text-xs text-text-muted">"Saved on this device. Click a key, then press the new key."</p>
+                    <For
+                        each=move || bindings.get()
+                        key=|binding| (binding.id.clone(), binding.key.clone())
+                        children=move |binding| {
+                            let id = binding.id.clone();
+                            let is_capturing = {
+                                let id = id.clone();
+                                move || capturing.get().as_deref() == Some(id.as_str())
+                            };
+                            view! {
+                                <div class="flex items-center justify-between gap-4">
+                                    <span class="text-sm text-text truncate">{binding.label}</span>
+                                    <button
+                                        class=move || format!(
+                                            "min-w-24 px-3 py-1 rounded border font-mono text-sm {}",
+                                            if is_capturing() { "border-primary text-primary" } else { "border-border text-text bg-background" }
+                                        )
+                                        on:click={
+                                            let id = id.clone();
+                                            move |_| capturing.set(Some(id.clone()))
+                                        }
+                                    >
+                                        {
+                                            let key = key_label(&binding.key);
+                                            let is_capturing = is_capturing.clone();
+                                            move || if is_capturing() { "Press a key...".to_string() } else { key.clone() }
+                                        }
+                                    </button>
+                                </div>
+                            }
+                        }
+                    />
+                    <button
+                        class="text-sm text-text-muted hover:text-text underline"
+                        on:click=move |_| {
+                            capturing.set(None);
+                            on_reset.run(());
+                        }
+                    >
+                        "Reset to defaults"
+                    </button>
+                </div>
+            </Show>
+        </div>
+    }
+}
@@ -15,6 +15,7 @@ where
     states::product::provide();
     states::station::provide();
     states::order::provide();
+    states::alert::provide();
     
     #[cfg(feature = "hydrate")]
     {
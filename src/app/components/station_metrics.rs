@@ -0,0 +1,100 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+#[cfg(feature = "hydrate")]
+use leptos_use::{use_interval, UseIntervalReturn};
+
+use crate::app::states::settings;
+use crate::backend::metrics::{get_recent_station_metrics, get_station_metrics};
+use crate::common::metrics::format_duration;
+use crate::common::types;
+
+/// How often the metrics are reloaded while a view showing them is open
+#[cfg(feature = "hydrate")]
+const REFRESH_MS: u64 = 15_000;
+
+/// Metrics of every station in the event of this device, reloaded periodically and whenever that event changes
+pub fn live_station_metrics() -> ReadSignal<Vec<types::StationMetrics>> {
+    let (metrics, set_metrics) = signal(Vec::new());
+    let event_scope = settings::get().event_scope();
+
+    #[cfg(feature = "hydrate")]
+    let UseIntervalReturn { counter, .. } = use_interval(REFRESH_MS);
+    #[cfg(not(feature = "hydrate"))]
+    let counter = Signal::derive(|| 0u64);
+
+    Effect::new(move |_| {
+        counter.track();
+        let Some(event_id) = event_scope.get() else {
+            set_metrics.set(Vec::new());
+            return;
+        };
+        spawn_local(async move {
+            if let Ok(loaded) = get_station_metrics(event_id).await {
+                set_metrics.set(loaded);
+            }
+        });
+    });
+
+    metrics
+}
+
+/// Recent figures of one station in the event of this device, reloaded periodically and whenever that event changes
+fn live_recent_metrics(station_id: String) -> ReadSignal<Option<types::RecentStationMetrics>> {
+    let (metrics, set_metrics) = signal(None);
+    let event_scope = settings::get().event_scope();
+
+    #[cfg(feature = "hydrate")]
+    let UseIntervalReturn { counter, .. } = use_interval(REFRESH_MS);
+    #[cfg(not(feature = "hydrate"))]
+    let counter = Signal::derive(|| 0u64);
+
+    Effect::new(move |_| {
+        counter.track();
+        let Some(event_id) = event_scope.get() else {
+            set_metrics.set(None);
+            return;
+        };
+        let station_id = station_id.clone();
+        spawn_local(async move {
+            if let Ok(loaded) = get_recent_station_metrics(station_id, event_id).await {
+                set_metrics.set(Some(loaded));
+            }
+        });
+    });
+
+    metrics
+}
+
+/// Formats an optional duration, showing a dash until there is data
+pub fn format_wait(secs: Option<i64>) -> String {
+    secs.map(format_duration).unwrap_or_else(|| "–".to_string())
+}
+
+/// Compact figures shown above a station's items
+#[component]
+pub fn StationMetricsHeader(station_id: String) -> impl IntoView {
+    let metrics = live_recent_metrics(station_id);
+
+    view! {
+        {move || metrics.get().map(|m| view! {
+            <div class="mb-6 grid grid-cols-2 md:grid-cols-4 gap-2 text-sm">
+                <div class="p-2 bg-surface rounded-md border border-border">
+                    <p class="text-text-muted">"Last 15 min"</p>
+                    <p class="text-text font-semibold">{m.processed}" done"</p>
+                </div>
+                <div class="p-2 bg-surface rounded-md border border-border">
+                    <p class="text-text-muted">"Median wait"</p>
+                    <p class="text-text font-semibold">{format_wait(m.median_wait_secs)}</p>
+                </div>
+                <div class="p-2 bg-surface rounded-md border border-border">
+                    <p class="text-text-muted">"P90 wait"</p>
+                    <p class="text-text font-semibold">{format_wait(m.p90_wait_secs)}</p>
+                </div>
+                <div class="p-2 bg-surface rounded-md border border-border">
+                    <p class="text-text-muted">"Backlog"</p>
+                    <p class="text-text font-semibold">{m.backlog}" waiting"</p>
+                </div>
+            </div>
+        })}
+    }
+}
@@ -1,39 +1,115 @@
 use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_use::core::ConnectionReadyState;
+#[cfg(feature = "hydrate")]
+use leptos_use::{use_timestamp_with_options, UseTimestampOptions};
 use std::collections::HashMap;
 
-use crate::app::states::{product, websocket};
-use crate::backend::item::{get_items_by_station, update_item, update_items_by_order};
+use crate::app::components::shortcuts::{self, use_shortcut_keys, Binding, ShortcutSettings};
+use crate::app::states::{product, settings, station, websocket};
+use crate::backend::item::{complete_units, get_items_by_station, recall_items, update_item, update_items_by_order};
+use crate::backend::order::get_orders;
+use crate::backend::status_history::get_recent_bumps;
 use crate::backend::station::get_station;
+use crate::common::resource_type::Message;
 use crate::common::{requests, types, german_names};
+use crate::common::batch::{aggregate, ProductBatch};
+use crate::common::station::{apply_item_message, group_by_order, is_waiting_at};
+use crate::common::metrics::format_duration;
+use crate::common::shortcuts::{StationAction, StationKeys};
 use crate::app::components::atoms::icons;
 
+/// How long the undo toast stays visible after a bump
+const UNDO_SECS: i64 = 8;
+
+/// The latest bump of this station, kept so it can be undone
+#[derive(Debug, Clone)]
+pub struct Bump {
+    pub label: String,
+    pub item_ids: Vec<String>,
+    pub at: i64,
+}
+
+pub fn bump_label(order_name: &str) -> String {
+    format!("Order '{}' passed on", order_name)
+}
+
+/// Moves a single item on to the station's output status
+async fn bump_item(
+    item_id: String,
+    station_id: String,
+    output_status: types::OrderStatus,
+    order_name: String,
+    at: i64,
+) -> Option<Bump> {
+    let update_request = requests::item::Update {
+        product_id: None,
+        quantity: None,
+        status: Some(output_status),
+        station_id: Some(station_id),
+    };
+    update_item(item_id.clone(), update_request).await.ok()?;
+    Some(Bump {
+        label: bump_label(&order_name),
+        item_ids: vec![item_id],
+        at,
+    })
+}
+
+/// Moves all of an order's items at the station on to its output status
+async fn bump_order(
+    order_id: String,
+    station_id: String,
+    output_status: types::OrderStatus,
+    order_name: String,
+    at: i64,
+) -> Option<Bump> {
+    let updated = update_items_by_order(order_id, station_id, output_status).await.ok()?;
+    (!updated.is_empty()).then(|| Bump {
+        label: bump_label(&order_name),
+        item_ids: updated.into_iter().map(|item| item.id).collect(),
+        at,
+    })
+}
+
+/// Position of the selected order on the board, so a re-sorted board keeps the same order selected.
+/// `selected` is the order id and its position when it was selected, an order that left the board
+/// hands the selection to the order that moved into its place.
+fn selected_position(order_ids: &[&str], selected: Option<&(String, usize)>) -> Option<usize> {
+    if order_ids.is_empty() {
+        return None;
+    }
+    let Some((order_id, position)) = selected else {
+        return Some(0);
+    };
+    order_ids
+        .iter()
+        .position(|id| id == order_id)
+        .or(Some((*position).min(order_ids.len() - 1)))
+}
+
 #[component]
 fn ItemCard(
     item: types::Item, 
-    product: types::Product,
+    product: Signal<types::Product>,
+    station_id: String,
     station_output_status: types::OrderStatus,
-    on_update: WriteSignal<u32>,
+    order_name: String,
+    on_bumped: WriteSignal<Option<Bump>>,
+    now: Signal<i64>,
 ) -> impl IntoView {
     let item_id = item.id.clone();
     let item_id_for_update = item_id.clone();
     
     let update_item_action = Action::new(move |_: &()| {
         let item_id = item_id_for_update.clone();
-        let new_status = station_output_status;
+        let new_status = station_output_status.clone();
+        let station_id = station_id.clone();
+        let order_name = order_name.clone();
         async move {
-            let update_request = requests::item::Update {
-                product_id: None,
-                quantity: None,
-                status: Some(new_status),
-            };
-            let _ = update_item(item_id, update_request).await;
-        }
-    });
-
-    // Trigger parent refresh when action completes
-    Effect::new(move |_| {
-        if update_item_action.value().get().is_some() {
-            on_update.update(|n| *n += 1);
+            if let Some(bump) = bump_item(item_id, station_id, new_status, order_name, now.get_untracked()).await {
+                on_bumped.set(Some(bump));
+            }
         }
     });
 
@@ -42,7 +118,7 @@ fn ItemCard(
             <div class="flex items-center justify-between">
                 <div class="flex-1">
                     <div class="flex items-center justify-between">
-                        <h4 class="text-text font-medium">{item.quantity}{" x "}{product.name}</h4>
+                        <h4 class="text-text font-medium">{item.quantity}{" x "}{move || product.get().name}</h4>
                     </div>
                 </div>
                 
@@ -70,36 +146,63 @@ fn ItemCard(
 fn OrderGroup(
     order_id: String,
     items: Vec<types::Item>,
-    products: HashMap<String, types::Product>,
+    products: Signal<HashMap<String, types::Product>>,
     station_id: String,
     station_output_status: types::OrderStatus,
-    on_update: WriteSignal<u32>,
+    order: Option<types::Order>,
+    sla: Signal<types::SlaThresholds>,
+    now: Signal<i64>,
+    on_bumped: WriteSignal<Option<Bump>>,
+    selected: Signal<bool>,
 ) -> impl IntoView {
     let order_id_for_bulk = order_id.clone();
+    let order_name = german_names::pickup_name(&order_id, order.as_ref());
+    let order_number = order.as_ref().map(|o| o.number_label());
+    let order_name_for_bulk = order_name.clone();
+    let rush = order.as_ref().is_some_and(|o| o.rush);
+    let created_at = order.map(|o| o.created_at);
+    let waiting_secs = move || created_at.map(|created| (now.get() - created).max(0));
+
+    let card_class = move || {
+        let level = waiting_secs().map(|secs| sla.with(|sla| sla.level(secs))).unwrap_or(types::SlaLevel::OnTime);
+        format!(
+            "rounded-lg border-2 p-4 transition-colors {} {}",
+            match level {
+                types::SlaLevel::OnTime => "bg-surface border-border",
+                types::SlaLevel::Warning => "bg-yellow-500/10 border-yellow-500",
+                types::SlaLevel::Late => "bg-error/10 border-error",
+            },
+            if selected.get() { "ring-4 ring-primary" } else { "" }
+        )
+    };
     let items_count = items.len();
+    let output_status_for_items = station_output_status.clone();
+    let station_id_for_items = station_id.clone();
     
     let update_order_action = Action::new(move |_: &()| {
         let order_id = order_id_for_bulk.clone();
+        let order_name = order_name_for_bulk.clone();
         let station_id_clone = station_id.clone();
-        let new_status = station_output_status;
+        let new_status = station_output_status.clone();
         async move {
-            let _ = update_items_by_order(order_id, station_id_clone, new_status).await;
-        }
-    });
-
-    // Trigger parent refresh when action completes
-    Effect::new(move |_| {
-        if update_order_action.value().get().is_some() {
-            on_update.update(|n| *n += 1);
+            if let Some(bump) = bump_order(order_id, station_id_clone, new_status, order_name, now.get_untracked()).await {
+                on_bumped.set(Some(bump));
+            }
         }
     });
 
     view! {
-        <div class="bg-surface rounded-lg border border-border p-4">
+        <div class=card_class>
             <div class="flex items-center justify-between mb-4">
                 <div>
-                    <h3 class="text-lg font-semibold text-text" data-order-id=order_id.clone()>{"Bestellung '"}{german_names::generate_german_name(&order_id.clone())}{"'"}</h3>
-                    <p class="text-sm text-text-muted">{format!("{} items", items_count)}</p>
+                    <h3 class="text-lg font-semibold text-text" data-order-id=order_id.clone()>{order_number.map(|number| format!("{} ", number))}{"Bestellung '"}{order_name.clone()}{"'"}</h3>
+                    <p class="text-sm text-text-muted">
+                        {format!("{} items", items_count)}
+                        {move || waiting_secs().map(|secs| format!(" · waiting {}", format_duration(secs)))}
+                    </p>
+                    {rush.then(|| view! {
+                        <span class="inline-block mt-1 px-2 py-0.5 rounded text-xs font-bold bg-error text-white">"RUSH"</span>
+                    })}
                 </div>
                 <button
                     class="bg-green-600 text-white hover:bg-green-700 px-4 py-2 rounded text-sm transition-colors"
@@ -121,22 +224,30 @@ fn OrderGroup(
                     each=move || items.clone()
                     key=|item| item.id.clone()
                     children=move |item| {
-                        let product = products.get(&item.product_id).cloned().unwrap_or_else(|| {
-                            types::Product {
-                                id: item.product_id.clone(),
-                                name: "Unknown Product".to_string(),
-                                category_id: "".to_string(),
-                                price: item.price,
-                                active: false,
-                            }
+                        let product_id = item.product_id.clone();
+                        let price = item.price;
+                        let product = Signal::derive(move || {
+                            products.with(|products| products.get(&product_id).cloned()).unwrap_or_else(|| {
+                                types::Product {
+                                    id: product_id.clone(),
+                                    name: "Unknown Product".to_string(),
+                                    category_id: "".to_string(),
+                                    price,
+                                    active: false,
+                                    stock: None,
+                                }
+                            })
                         });
                         
                         view! {
                             <ItemCard 
                                 item=item
                                 product=product
-                                station_output_status=station_output_status
-                                on_update=on_update
+                                station_id=station_id_for_items.clone()
+                                station_output_status=output_status_for_items.clone()
+                                order_name=order_name.clone()
+                                on_bumped=on_bumped
+                                now=now
                             />
                         }
                     }
@@ -146,36 +257,349 @@ fn OrderGroup(
     }
 }
 
+/// Pending units of one product with controls to mark some of them as done
+#[component]
+fn BatchRow(
+    product_id: String,
+    batch: Signal<ProductBatch>,
+    product_name: Signal<String>,
+    station_id: String,
+    on_bumped: WriteSignal<Option<Bump>>,
+    now: Signal<i64>,
+) -> impl IntoView {
+    let pending = move || batch.with(|batch| batch.quantity);
+    let (units, set_units) = signal(1u32);
+    // Fewer units may be pending once other devices finished some
+    let units = Signal::derive(move || units.get().min(pending()).max(1));
+    let event_id = settings::get().event_id();
+
+    let complete_action = Action::new(move |units: &u32| {
+        let units = *units;
+        let station_id = station_id.clone();
+        let product_id = product_id.clone();
+        let event_id = event_id.get_untracked();
+        async move {
+            if let Ok(advanced) = complete_units(station_id, product_id, units, event_id).await {
+                if !advanced.is_empty() {
+                    on_bumped.set(Some(Bump {
+                        label: format!("{} x {} done", units, product_name.get_untracked()),
+                        item_ids: advanced.into_iter().map(|item| item.id).collect(),
+                        at: now.get_untracked(),
+                    }));
+                }
+            }
+        }
+    });
+
+    let stepper_class = "w-10 h-10 flex items-center justify-center rounded bg-border/80 text-text hover:bg-border";
+
+    view! {
+        <div class="flex items-center justify-between p-4 bg-surface rounded-lg border border-border">
+            <div>
+                <h3 class="text-2xl font-bold text-text">{pending}" x "{move || product_name.get()}</h3>
+                <p class="text-sm text-text-muted">{move || format!("pending in {} orders", batch.with(|batch| batch.orders))}</p>
+            </div>
+            <div class="flex items-center gap-2">
+                <button class=stepper_class on:click=move |_| set_units.set(units.get_untracked().saturating_sub(1).max(1))>
+                    <icons::Minus attr:class="w-5 h-5"/>
+                </button>
+                <span class="min-w-8 text-center text-lg font-semibold text-text">{move || units.get()}</span>
+                <button class=stepper_class on:click=move |_| set_units.set((units.get_untracked() + 1).min(pending()))>
+                    <icons::Plus attr:class="w-5 h-5"/>
+                </button>
+                <button
+                    class="bg-primary text-white hover:bg-primary/90 px-3 py-2 rounded text-sm transition-colors"
+                    disabled=move || complete_action.pending().get()
+                    on:click=move |_| {
+                        complete_action.dispatch(units.get_untracked());
+                    }
+                >
+                    {move || format!("{} done", units.get())}
+                </button>
+                <button
+                    class="bg-green-600 text-white hover:bg-green-700 px-3 py-2 rounded text-sm transition-colors"
+                    disabled=move || complete_action.pending().get()
+                    on:click=move |_| {
+                        complete_action.dispatch(batch.with_untracked(|batch| batch.quantity));
+                    }
+                >
+                    "All"
+                </button>
+            </div>
+        </div>
+    }
+}
+
+/// Pending units summed per product, for stations that prepare in batches rather than per order
+#[component]
+fn BatchView(
+    items: RwSignal<Vec<types::Item>>,
+    products: Signal<HashMap<String, types::Product>>,
+    station_id: String,
+    on_bumped: WriteSignal<Option<Bump>>,
+    now: Signal<i64>,
+) -> impl IntoView {
+    view! {
+        <div class="space-y-3">
+            <For
+                each=move || items.with(|items| aggregate(items))
+                key=|batch| batch.product_id.clone()
+                children=move |batch| {
+                    // The row stays while its product is pending, only the counts change in place
+                    let product_id = batch.product_id.clone();
+                    let batch = Signal::derive({
+                        let product_id = product_id.clone();
+                        move || {
+                            items.with(|items| aggregate(items))
+                                .into_iter()
+                                .find(|batch| batch.product_id == product_id)
+                                .unwrap_or_else(|| batch.clone())
+                        }
+                    });
+                    let product_name = Signal::derive({
+                        let product_id = product_id.clone();
+                        move || {
+                            products.with(|products| products.get(&product_id).map(|p| p.name.clone()))
+                                .unwrap_or_else(|| "Unknown Product".to_string())
+                        }
+                    });
+                    view! {
+                        <BatchRow
+                            product_id=product_id
+                            batch=batch
+                            product_name=product_name
+                            station_id=station_id.clone()
+                            on_bumped=on_bumped
+                            now=now
+                        />
+                    }
+                }
+            />
+        </div>
+    }
+}
+
+/// Offers to undo the latest bump for a few seconds
+#[component]
+pub fn UndoToast(
+    station_id: String,
+    last_bump: RwSignal<Option<Bump>>,
+    now: Signal<i64>,
+) -> impl IntoView {
+    let undo_action = Action::new(move |bump: &Bump| {
+        let item_ids = bump.item_ids.clone();
+        let station_id = station_id.clone();
+        async move {
+            let _ = recall_items(item_ids, station_id).await;
+        }
+    });
+    let visible = move || last_bump.with(|bump| bump.as_ref().is_some_and(|b| now.get() - b.at < UNDO_SECS));
+
+    view! {
+        <Show when=visible>
+            <div class="fixed bottom-6 left-1/2 -translate-x-1/2 z-50 flex items-center gap-4 px-4 py-3 rounded-lg shadow-lg bg-surface-elevated border border-border">
+                <span class="text-text text-sm">
+                    {move || last_bump.get().map(|b| b.label)}
+                </span>
+                <button
+                    class="px-3 py-1 rounded text-sm font-semibold bg-primary text-white hover:bg-primary/90"
+                    on:click=move |_| {
+                        if let Some(bump) = last_bump.get_untracked() {
+                            undo_action.dispatch(bump);
+                        }
+                        last_bump.set(None);
+                    }
+                >
+                    "Undo"
+                </button>
+            </div>
+        </Show>
+    }
+}
+
+/// Orders this station recently moved on, each of them can be recalled to the board
+#[component]
+pub fn RecentlyCompleted(station_id: String, items: RwSignal<Vec<types::Item>>) -> impl IntoView {
+    let (open, set_open) = signal(false);
+    let recent = RwSignal::new(Vec::<types::BumpedOrder>::new());
+
+    // Reload whenever the board changes while the drawer is open
+    Effect::new({
+        let station_id = station_id.clone();
+        move |_| {
+            items.track();
+            if !open.get() {
+                return;
+            }
+            let station_id = station_id.clone();
+            spawn_local(async move {
+                if let Ok(loaded) = get_recent_bumps(station_id).await {
+                    recent.set(loaded);
+                }
+            });
+        }
+    });
+
+    let recall_action = Action::new(move |item_ids: &Vec<String>| {
+        let item_ids = item_ids.clone();
+        let station_id = station_id.clone();
+        async move {
+            let _ = recall_items(item_ids, station_id).await;
+        }
+    });
+
+    view! {
+        <div class="bg-surface rounded-lg border border-border">
+            <button
+                class="w-full flex items-center justify-between p-4 text-text font-medium"
+                on:click=move |_| set_open.update(|open| *open = !*open)
+            >
+                "Recently completed"
+                {move || if open.get() {
+                    view! { <icons::ChevronUp attr:class="w-4 h-4"/> }.into_any()
+                } else {
+                    view! { <icons::ChevronDown attr:class="w-4 h-4"/> }.into_any()
+                }}
+            </button>
+            <Show when=move || open.get()>
+                <div class="px-4 pb-4 space-y-2">
+                    <Show
+                        when=move || !recent.with(|recent| recent.is_empty())
+                        fallback=|| view! { <p class="text-sm text-text-muted">"Nothing completed recently"</p> }
+                    >
+                        {move || recent.get().into_iter().map(|bumped| {
+                            let label = format!(
+                                "'{}' · {} items",
+                                bumped.name,
+                                bumped.quantity
+                            );
+                            let item_ids = bumped.item_ids;
+                            view! {
+                                <div class="flex items-center justify-between p-2 bg-surface-elevated rounded border border-border">
+                                    <span class="text-sm text-text">{label}</span>
+                                    <button
+                                        class="px-3 py-1 rounded text-sm bg-border/80 text-text hover:bg-border"
+                                        disabled=move || recall_action.pending().get()
+                                        on:click=move |_| {
+                                            recall_action.dispatch(item_ids.clone());
+                                        }
+                                    >
+                                        "Recall"
+                                    </button>
+                                </div>
+                            }
+                        }).collect_view()}
+                    </Show>
+                </div>
+            </Show>
+        </div>
+    }
+}
+
+/// Narrows the board down to one order by its number or pickup name
+#[component]
+pub fn OrderSearch(search: ReadSignal<String>, set_search: WriteSignal<String>) -> impl IntoView {
+    view! {
+        <input
+            type="search"
+            placeholder="#042 or name"
+            prop:value=move || search.get()
+            on:input=move |ev| set_search.set(event_target_value(&ev))
+            class="px-3 py-2 w-48 border border-border bg-surface placeholder-text-muted text-text rounded-md focus:outline-none focus:ring-primary focus:border-primary text-sm"
+        />
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum DisplayMode {
+    Orders,
+    Batch,
+}
+
 #[component]
 pub fn StationView(station_id: String) -> impl IntoView {
-    let (refresh_trigger, set_refresh_trigger) = signal::<u32>(0);
-    
-    // Listen to WebSocket updates for items and orders to trigger refresh
+    let items = RwSignal::new(Vec::<types::Item>::new());
+    let orders_by_id = RwSignal::new(HashMap::<String, types::Order>::new());
+    let last_bump = RwSignal::new(None::<Bump>);
+    let (mode, set_mode) = signal(DisplayMode::Orders);
+    let (search, set_search) = signal(String::new());
+    let batch_station_id = StoredValue::new(station_id.clone());
+    let (reload_trigger, set_reload_trigger) = signal::<u32>(0);
     let websocket_state = websocket::get();
+    let products = product::get().get_products();
+    let stations = station::get().get_stations();
+
+    // Live station configuration, so edits in the admin panel apply without a page reload
+    let station_config = {
+        let station_id = station_id.clone();
+        Memo::new(move |_| stations.get().into_iter().find(|s| s.id == station_id))
+    };
+
+    // Items are scoped to the event of this device, so switching events reloads them.
+    // Nothing is loaded or applied until the device knows its event
+    let event_scope = settings::get().event_scope();
+
+    // Apply item changes locally instead of refetching, so a bump shows up on every screen at once
     Effect::new({
-        let set_refresh_trigger = set_refresh_trigger;
         let websocket_state = websocket_state.clone();
         move |_| {
-            if let Some(_message) = websocket_state.items.get() {
-                // Any item change should refresh all station views
-                set_refresh_trigger.update(|n| *n += 1);
+            if let Some(message) = websocket_state.items.get() {
+                if let (Some(station), Some(event_id)) = (station_config.get_untracked(), event_scope.get_untracked()) {
+                    let products = products.get_untracked();
+                    items.update(|items| {
+                        apply_item_message(items, message, event_id.as_deref(), |item| is_waiting_at(&station, &products, item))
+                    });
+                }
                 websocket_state.items.set(None);
             }
         }
     });
-    
-    // Also listen for order changes (new orders create items)
+
+    // Rush flags and new orders, needed to sort the board
     Effect::new({
-        let set_refresh_trigger = set_refresh_trigger;
         let websocket_state = websocket_state.clone();
         move |_| {
-            if let Some(_message) = websocket_state.orders.get() {
-                // New orders might have items for this station
-                set_refresh_trigger.update(|n| *n += 1);
+            if let Some(message) = websocket_state.orders.get() {
+                if event_scope.get_untracked().is_some() {
+                    orders_by_id.update(|orders| match message {
+                        Message::Add(order) | Message::Update(order) => {
+                            orders.insert(order.id.clone(), order);
+                        }
+                        Message::Delete(id) => {
+                            orders.remove(&id);
+                        }
+                    });
+                }
                 websocket_state.orders.set(None);
             }
         }
     });
+
+    // Current time for the waiting counters, ticking every second in the browser
+    #[cfg(feature = "hydrate")]
+    let now = {
+        let timestamp = use_timestamp_with_options(UseTimestampOptions::default().interval(1000));
+        Signal::derive(move || (timestamp.get() / 1000.0) as i64)
+    };
+    #[cfg(not(feature = "hydrate"))]
+    let now = Signal::derive(|| 0i64);
+
+    // Messages may have been missed while disconnected, so reload everything after a reconnect
+    Effect::new({
+        let websocket_state = websocket_state.clone();
+        move |was_open: Option<bool>| {
+            let open = websocket_state.state.get() == ConnectionReadyState::Open;
+            if open && was_open == Some(false) {
+                set_reload_trigger.update(|n| *n += 1);
+            }
+            open
+        }
+    });
+    
+    // Announce this station to the server so a lost connection raises an alert
+    let station_presence = websocket_state.station_presence;
+    station_presence.set(Some(station_id.clone()));
+    on_cleanup(move || station_presence.set(None));
     
     let station_id_mv = station_id.clone();
     // Resource to fetch station details
@@ -187,75 +611,250 @@ pub fn StationView(station_id: String) -> impl IntoView {
     );
     let station_id_mv = station_id.clone();
 
-    // Resource to fetch items for this station
-    let items_resource = Resource::new(
-        move || (station_id_mv.clone(), refresh_trigger.get()),
-        |(id, _)| async move {
-            get_items_by_station(id).await.unwrap_or_default()
-        },
-    );
+    // Full load of the station's items, only needed initially, after a reconnect
+    // and when the event or the station configuration changes
+    Effect::new(move |_| {
+        let id = station_id_mv.clone();
+        station_config.track();
+        reload_trigger.track();
+        let Some(event_id) = event_scope.get() else {
+            orders_by_id.set(HashMap::new());
+            items.set(Vec::new());
+            return;
+        };
+        spawn_local(async move {
+            if let Ok(loaded) = get_orders(event_id.clone()).await {
+                orders_by_id.set(loaded.into_iter().map(|o| (o.id.clone(), o)).collect());
+            }
+            if let Ok(loaded) = get_items_by_station(id, event_id).await {
+                items.set(loaded);
+            }
+        });
+    });
     
-    // Resource to fetch all products (to display item details)
-    let product_state = product::get();
-    let products = product_state.get_products();
+    // Create a product lookup map
+    let product_map = Signal::derive(move || {
+        products
+            .get()
+            .into_iter()
+            .map(|p| (p.id.clone(), p))
+            .collect::<HashMap<String, types::Product>>()
+    });
+
+    // Thresholds follow live edits of the station
+    let sla = Signal::derive(move || station_config.get().map(|s| s.sla).unwrap_or_default());
+
+    let orders = move || {
+        let orders = orders_by_id.get();
+        let search = search.get();
+        group_by_order(items.get(), &orders)
+            .into_iter()
+            .filter(|(order_id, _)| search.trim().is_empty() || orders.get(order_id).is_some_and(|o| o.matches(&search)))
+            .map(|(order_id, order_items)| {
+                let order = orders.get(&order_id).cloned();
+                (order_id, order_items, order)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    // Keyboard and bump bar operation, the bindings are saved per station on this device
+    let shortcut_storage_key = StoredValue::new(format!("shortcuts:station:{}", station_id));
+    let keys = RwSignal::new(StationKeys::default());
+    let capturing = RwSignal::new(None::<String>);
+    let selected = RwSignal::new(None::<(String, usize)>); // Order id and its position when selected
+    // The selection is only highlighted once the keyboard is used, touch screens don't need it
+    let keyboard_used = RwSignal::new(false);
+    Effect::new(move |_| keys.set(shortcut_storage_key.with_value(|key| shortcuts::load(key))));
+
+    let selected_order_id = Memo::new(move |_| {
+        let board = orders();
+        let order_ids: Vec<&str> = board.iter().map(|(order_id, _, _)| order_id.as_str()).collect();
+        selected.with(|selected| selected_position(&order_ids, selected.as_ref())).map(|index| board[index].0.clone())
+    });
+
+    use_shortcut_keys({
+        let station_id = station_id.clone();
+        move |key| {
+            if let Some(id) = capturing.get_untracked() {
+                if let Some(action) = StationAction::ALL.into_iter().find(|action| action.label() == id) {
+                    keys.update(|keys| keys.bind(action, key));
+                    shortcut_storage_key.with_value(|storage_key| shortcuts::save(storage_key, &keys.get_untracked()));
+                }
+                capturing.set(None);
+                return true;
+            }
+            let Some(action) = keys.with_untracked(|keys| keys.action(key)) else {
+                return false;
+            };
+
+            if action == StationAction::Undo {
+                let Some(bump) = last_bump.get_untracked().filter(|bump| now.get_untracked() - bump.at < UNDO_SECS) else {
+                    return false;
+                };
+                spawn_local({
+                    let station_id = station_id.clone();
+                    async move {
+                        let _ = recall_items(bump.item_ids, station_id).await;
+                    }
+                });
+                last_bump.set(None);
+                return true;
+            }
+
+            let Some(station) = station_config.get_untracked() else {
+                return false;
+            };
+            let board = untrack(orders);
+            if mode.get_untracked() != DisplayMode::Orders || board.is_empty() {
+                return false;
+            }
+            keyboard_used.set(true);
+            let order_ids: Vec<&str> = board.iter().map(|(order_id, _, _)| order_id.as_str()).collect();
+            let index = selected.with_untracked(|selected| selected_position(&order_ids, selected.as_ref())).unwrap_or(0);
+            let select = |index: usize| selected.set(Some((board[index].0.clone(), index)));
+            let (order_id, order_items, order) = board[index].clone();
+            let order_name = german_names::pickup_name(&order_id, order.as_ref());
+            let at = now.get_untracked();
+
+            match action {
+                StationAction::NextOrder => select((index + 1).min(board.len() - 1)),
+                StationAction::PreviousOrder => select(index.saturating_sub(1)),
+                StationAction::BumpItem => {
+                    if let Some(item) = order_items.into_iter().next() {
+                        spawn_local(async move {
+                            if let Some(bump) = bump_item(item.id, station.id, station.output_status, order_name, at).await {
+                                last_bump.set(Some(bump));
+                            }
+                        });
+                    }
+                }
+                StationAction::BumpOrder => spawn_local(async move {
+                    if let Some(bump) = bump_order(order_id, station.id, station.output_status, order_name, at).await {
+                        last_bump.set(Some(bump));
+                    }
+                }),
+                StationAction::Undo => {}
+            }
+            true
+        }
+    });
+
+    let bindings = Signal::derive(move || {
+        keys.with(|keys| {
+            StationAction::ALL
+                .into_iter()
+                .map(|action| Binding {
+                    id: action.label().to_string(),
+                    label: action.label().to_string(),
+                    key: keys.key(action).to_string(),
+                })
+                .collect::<Vec<_>>()
+        })
+    });
+    let reset_keys = move |_| {
+        keys.set(StationKeys::default());
+        shortcut_storage_key.with_value(|storage_key| shortcuts::save(storage_key, &StationKeys::default()));
+    };
     
+    let mode_button = move |target: DisplayMode, label: &'static str| {
+        view! {
+            <button
+                class=move || format!(
+                    "px-4 py-2 text-sm font-medium rounded-md transition-colors {}",
+                    if mode.get() == target { "bg-primary text-white" } else { "text-text hover:bg-background" }
+                )
+                on:click=move |_| set_mode.set(target)
+            >
+                {label}
+            </button>
+        }
+    };
+
     view! {
         <div class="space-y-6">
+            <div class="flex flex-wrap items-center gap-4">
+                <div class="inline-flex space-x-1 bg-surface rounded-lg p-1 border border-border">
+                    {mode_button(DisplayMode::Orders, "Orders")}
+                    {mode_button(DisplayMode::Batch, "Batch")}
+                </div>
+                <OrderSearch search=search set_search=set_search />
+            </div>
+
             <Suspense fallback=move || view! {
                 <div class="flex items-center justify-center py-12">
                     <div class="animate-spin rounded-full h-8 w-8 border-b-2 border-primary"></div>
                 </div>
             }>
                 {move || {
-                    let station_opt = station_resource.get();
-                    let items = items_resource.get().unwrap_or_default();
-                    let products_list = products.get();
-                    
-                    if let Some(Some(station)) = station_opt {
-                        // Create a product lookup map
-                        let product_map: HashMap<String, types::Product> = products_list
-                            .into_iter()
-                            .map(|p| (p.id.clone(), p))
-                            .collect();
-                        
-                        // Group items by order_id
-                        let mut orders: HashMap<String, Vec<types::Item>> = HashMap::new();
-                        for item in items {
-                            if let Some(order_id) = &item.order_id {
-                                orders.entry(order_id.clone()).or_insert_with(Vec::new).push(item);
-                            }
-                        }
-                        
-                        if orders.is_empty() {
-                            view! {
-                                <div class="text-center py-12">
-                                    <icons::Inbox attr:class="mx-auto h-12 w-12 text-text-muted mb-4"/>
-                                    <p class="text-text-muted text-lg">"No items for this station"</p>
-                                    <p class="text-text-muted text-sm mt-2">"Items will appear here when they match this station's criteria"</p>
-                                </div>
-                            }.into_any()
-                        } else {
-                            view! {
-                                <div class="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 xl:grid-cols-4 gap-4">    
-                                    <For
-                                        each=move || orders.clone()
-                                        key=|(order_id, _)| order_id.clone()
-                                        children=move |(order_id, order_items)| {
-                                            view! {
-                                                <OrderGroup 
-                                                    order_id=order_id
-                                                    items=order_items
-                                                    products=product_map.clone()
-                                                    station_id=station.id.clone()
-                                                    station_output_status=station.output_status
-                                                    on_update=set_refresh_trigger
+                    if let Some(Some(station)) = station_resource.get() {
+                        view! {
+                            <Show
+                                when=move || !items.with(|items| items.is_empty())
+                                fallback=|| view! {
+                                    <div class="text-center py-12">
+                                        <icons::Inbox attr:class="mx-auto h-12 w-12 text-text-muted mb-4"/>
+                                        <p class="text-text-muted text-lg">"No items for this station"</p>
+                                        <p class="text-text-muted text-sm mt-2">"Items will appear here when they match this station's criteria"</p>
+                                    </div>
+                                }
+                            >
+                                <Show
+                                    when=move || mode.get() == DisplayMode::Batch
+                                    fallback={
+                                        let station = station.clone();
+                                        move || view! {
+                                            <div class="grid grid-cols-1 md:grid-cols-2 lg:grid-cols-3 xl:grid-cols-4 gap-4">
+                                                <For
+                                                    each=orders
+                                                    // Only orders whose items changed are rendered again
+                                                    key=|(order_id, order_items, order)| {
+                                                        let item_keys: Vec<(&str, &types::OrderStatus)> = order_items
+                                                            .iter()
+                                                            .map(|item| (item.id.as_str(), &item.status))
+                                                            .collect();
+                                                        let flags = order.as_ref().map(|o| (o.rush, o.created_at));
+                                                        format!("{}:{:?}:{:?}", order_id, item_keys, flags)
+                                                    }
+                                                    children={
+                                                        let station = station.clone();
+                                                        move |(order_id, order_items, order)| {
+                                                            let selected = {
+                                                                let order_id = order_id.clone();
+                                                                Signal::derive(move || {
+                                                                    keyboard_used.get() && selected_order_id.get().as_deref() == Some(order_id.as_str())
+                                                                })
+                                                            };
+                                                            view! {
+                                                                <OrderGroup 
+                                                                    order_id=order_id
+                                                                    items=order_items
+                                                                    products=product_map
+                                                                    station_id=station.id.clone()
+                                                                    station_output_status=station.output_status.clone()
+                                                                    order=order
+                                                                    sla=sla
+                                                                    now=now
+                                                                    on_bumped=last_bump.write_only()
+                                                                    selected=selected
+                                                                />
+                                                            }
+                                                        }
+                                                    }
                                                 />
-                                            }
+                                            </div>
                                         }
+                                    }
+                                >
+                                    <BatchView
+                                        items=items
+                                        products=product_map
+                                        station_id=batch_station_id.get_value()
+                                        on_bumped=last_bump.write_only()
+                                        now=now
                                     />
-                                </div>
-                            }.into_any()
-                        }
+                                </Show>
+                            </Show>
+                        }.into_any()
                     } else {
                         view! {
                             <div class="text-center py-12">
@@ -265,6 +864,26 @@ pub fn StationView(station_id: String) -> impl IntoView {
                     }
                 }}
             </Suspense>
+
+            <ShortcutSettings bindings=bindings capturing=capturing on_reset=reset_keys />
+            <RecentlyCompleted station_id=station_id.clone() items=items />
+            <UndoToast station_id=station_id.clone() last_bump=last_bump now=now />
         </div>
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_selection_follows_the_order() {
+        let selected = ("b".to_string(), 1);
+
+        assert_eq!(selected_position(&["rush", "a", "b"], Some(&selected)), Some(2), "Re-sorted board");
+        assert_eq!(selected_position(&["a", "c"], Some(&selected)), Some(1), "Bumped order");
+        assert_eq!(selected_position(&["a"], Some(&selected)), Some(0));
+        assert_eq!(selected_position(&["a", "b"], None), Some(0));
+        assert_eq!(selected_position(&[], Some(&selected)), None);
+    }
+}
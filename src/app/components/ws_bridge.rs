@@ -1,6 +1,7 @@
-use crate::app::states::websocket;
-use crate::common::resource_type::GenericWebSocketMessage;
+use crate::app::states::{settings, websocket};
+use crate::common::resource_type::{GenericWebSocketMessage, EVENT_SUBSCRIPTION, ORDER_SUBSCRIPTION, STATION_PRESENCE};
 use codee::string::JsonSerdeCodec;
+use leptos_use::core::ConnectionReadyState;
 use leptos::logging::log;
 use leptos::prelude::*;
 use leptos_use::{use_websocket, UseWebSocketReturn};
@@ -31,7 +32,8 @@ pub fn WsBridge() -> impl IntoView {
 
     let UseWebSocketReturn {
         message,
-        ready_state,..
+        ready_state,
+        send,..
     } = use_websocket::<GenericWebSocketMessage, GenericWebSocketMessage, JsonSerdeCodec>(
         &ws_url,
     );
@@ -53,6 +55,44 @@ pub fn WsBridge() -> impl IntoView {
         ws_state.set_state(ready_state.get())
     });
 
+    // Follow only the event of this device, again after every reconnect
+    let event_id = settings::get().event_id();
+    let send_subscription = send.clone();
+    Effect::new(move |_| {
+        let event_id = event_id.get();
+        if ready_state.get() == ConnectionReadyState::Open {
+            send_subscription(&GenericWebSocketMessage {
+                resource_type: EVENT_SUBSCRIPTION.to_string(),
+                message: event_id.map(serde_json::Value::String).unwrap_or(serde_json::Value::Null),
+            });
+        }
+    });
+
+    // Limit the connection to one order on its tracking page, again after every reconnect
+    let ws_state = websocket::get();
+    let send_order_subscription = send.clone();
+    Effect::new(move |_| {
+        let order_id = ws_state.order_subscription.get();
+        if ready_state.get() == ConnectionReadyState::Open {
+            send_order_subscription(&GenericWebSocketMessage {
+                resource_type: ORDER_SUBSCRIPTION.to_string(),
+                message: order_id.map(serde_json::Value::String).unwrap_or(serde_json::Value::Null),
+            });
+        }
+    });
+
+    // Announce the served station, again after every reconnect
+    let ws_state = websocket::get();
+    Effect::new(move |_| {
+        let station_id = ws_state.station_presence.get();
+        if ready_state.get() == ConnectionReadyState::Open {
+            send(&GenericWebSocketMessage {
+                resource_type: STATION_PRESENCE.to_string(),
+                message: station_id.map(serde_json::Value::String).unwrap_or(serde_json::Value::Null),
+            });
+        }
+    });
+
     view! {
         <></>
     }
@@ -15,7 +15,8 @@ use components::{
 };
 use pages::{
     admin::Admin, cashier::Cashier, signin::SignIn, signup::SignUp, station::StationPage,
-    stations::StationsPage, home::Home,
+    stations::StationsPage, home::Home, pickup::PickupBoard,
+    order_tracking::OrderTrackingPage, receipt::ReceiptPage,
 };
 
 pub fn shell(options: LeptosOptions) -> impl IntoView {
@@ -51,6 +52,8 @@ pub fn App() -> impl IntoView {
                     <Route path=StaticSegment("") view=Home/>
                     <Route path=StaticSegment("signin") view=SignIn/>
                     <Route path=StaticSegment("signup") view=SignUp/>
+                    <Route path=StaticSegment("pickup") view=PickupBoard/>
+                    <Route path=(StaticSegment("order"), ParamSegment("id")) view=OrderTrackingPage/>
                     <Route path=StaticSegment("admin") view=move || view! {
                         <RouteGuard roles=vec![crate::common::types::Role::Admin] children=move || view! {
                             <Admin/>
@@ -61,6 +64,11 @@ pub fn App() -> impl IntoView {
                             <Cashier/>
                         }.into_any() />
                     }/>
+                    <Route path=(StaticSegment("receipt"), ParamSegment("id")) view=move || view! {
+                        <RouteGuard roles=vec![crate::common::types::Role::Admin, crate::common::types::Role::Cashier] children=move || view! {
+                            <ReceiptPage/>
+                        }.into_any() />
+                    }/>
                     <Route path=StaticSegment("station") view=move || view! {
                         <RouteGuard roles=vec![crate::common::types::Role::Admin, crate::common::types::Role::Cashier, crate::common::types::Role::Staff] children=move || view! {
                             <StationsPage/>
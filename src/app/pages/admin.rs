@@ -1,9 +1,9 @@
 use leptos::prelude::*;
 
 use crate::app::components::admin::{
-    categories::Categories, create_category::CreateCategory, create_event::CreateEvent,
+    alert_rules::AlertRulesForm, categories::Categories, create_category::CreateCategory, create_event::CreateEvent,
     create_product::CreateProduct, create_station::CreateStation, events::Events,
-    products::Products, stations::Stations, users::Users,
+    metrics::Metrics, pipeline::Pipeline, products::Products, stations::Stations, users::Users,
 };
 use crate::app::components::atoms::icons;
 
@@ -14,6 +14,8 @@ enum AdminTab {
     Events,
     Stations,
     Users,
+    Alerts,
+    Metrics,
 }
 
 #[component]
@@ -102,6 +104,34 @@ pub fn Admin() -> impl IntoView {
                             <icons::Users attr:class="w-4 h-4 mr-2"/>
                             "Users"
                         </button>
+                        <button
+                            class=move || format!(
+                                "flex-1 px-4 py-2 text-sm font-medium rounded-md transition-colors duration-200 flex items-center justify-center {}",
+                                if active_tab.get() == AdminTab::Alerts {
+                                    "bg-primary text-white shadow-sm"
+                                } else {
+                                    "text-text hover:bg-background hover:text-text"
+                                }
+                            )
+                            on:click=move |_| set_active_tab.set(AdminTab::Alerts)
+                        >
+                            <icons::Bell attr:class="w-4 h-4 mr-2"/>
+                            "Alerts"
+                        </button>
+                        <button
+                            class=move || format!(
+                                "flex-1 px-4 py-2 text-sm font-medium rounded-md transition-colors duration-200 flex items-center justify-center {}",
+                                if active_tab.get() == AdminTab::Metrics {
+                                    "bg-primary text-white shadow-sm"
+                                } else {
+                                    "text-text hover:bg-background hover:text-text"
+                                }
+                            )
+                            on:click=move |_| set_active_tab.set(AdminTab::Metrics)
+                        >
+                            <icons::Chart attr:class="w-4 h-4 mr-2"/>
+                            "Metrics"
+                        </button>
                     </nav>
                 </div>
 
@@ -154,6 +184,7 @@ pub fn Admin() -> impl IntoView {
                                     <CreateStation />
                                 </div>
                                 <Stations />
+                                <Pipeline />
                             </div>
                         }.into_any(),
                         AdminTab::Users => view! {
@@ -167,6 +198,22 @@ pub fn Admin() -> impl IntoView {
                                 <Users />
                             </div>
                         }.into_any(),
+                        AdminTab::Alerts => view! {
+                            <div class="space-y-6">
+                                <div class="bg-surface rounded-lg border border-border p-6 shadow-sm">
+                                    <h2 class="text-xl font-semibold text-text mb-4 flex items-center">
+                                        <icons::Bell attr:class="w-5 h-5 mr-2 text-primary"/>
+                                        "Alert Rules"
+                                    </h2>
+                                    <AlertRulesForm />
+                                </div>
+                            </div>
+                        }.into_any(),
+                        AdminTab::Metrics => view! {
+                            <div class="space-y-6">
+                                <Metrics />
+                            </div>
+                        }.into_any(),
                     }}
                 </div>
             </div>
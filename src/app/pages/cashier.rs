@@ -1,6 +1,6 @@
 use leptos::prelude::*;
 
-use crate::app::components::cashier::{products::CashierProducts, order::Order, order_info::{OrderInfoComponent, provide as provide_order_info}};
+use crate::app::components::cashier::{products::CashierProducts, order::Order, order_info::{OrderInfoComponent, provide as provide_order_info}, order_history::OrderHistory, shortcuts::CashierShortcuts};
 
 #[component]
 pub fn Cashier() -> impl IntoView {
@@ -27,6 +27,8 @@ pub fn Cashier() -> impl IntoView {
                     <div class="sm:col-span-1 w-full sm:max-w-sm">
                         <OrderInfoComponent />
                         <Order />
+                        <OrderHistory />
+                        <CashierShortcuts />
                     </div>
                 </div>
             </div>
@@ -5,4 +5,7 @@ pub mod admin;
 pub mod cashier;
 pub mod station;
 pub mod stations;
-pub mod home;
\ No newline at end of file
+pub mod home;
+pub mod pickup;
+pub mod order_tracking;
+pub mod receipt;
\ No newline at end of file
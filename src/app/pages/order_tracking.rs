@@ -0,0 +1,129 @@
+use leptos::prelude::*;
+use leptos_router::{hooks::use_params, params::Params};
+
+use crate::app::states::websocket;
+use crate::backend::tracking::get_order_tracking;
+use crate::common::pickup::{stage, PickupStage};
+use crate::common::resource_type::Message;
+use crate::common::types;
+
+#[derive(Params, PartialEq, Clone)]
+struct OrderParams {
+    id: String,
+}
+
+/// Headline for the customer, from the statuses of the order's items
+fn stage_label(items: &[types::TrackedItem]) -> (&'static str, &'static str) {
+    match stage(items.iter().map(|item| &item.status)) {
+        Some(PickupStage::Preparing) => ("Being prepared", "text-yellow-600"),
+        Some(PickupStage::Ready) => ("Ready for pickup!", "text-green-600"),
+        None => ("Picked up", "text-text-muted"),
+    }
+}
+
+/// Public page customers open by scanning the QR code of their order
+#[component]
+pub fn OrderTrackingPage() -> impl IntoView {
+    let params = use_params::<OrderParams>();
+    let tracking = RwSignal::new(None::<types::OrderTracking>);
+    let error = RwSignal::new(None::<String>);
+    let websocket_state = websocket::get();
+
+    let resource = Resource::new(
+        move || params.with(|params| params.as_ref().map(|p| p.id.clone()).ok()),
+        |id| async move {
+            match id {
+                Some(id) => get_order_tracking(id).await.map_err(|e| e.to_string()),
+                None => Err("Order not found".to_string()),
+            }
+        },
+    );
+
+    Effect::new(move |_| {
+        match resource.get() {
+            Some(Ok(loaded)) => tracking.set(Some(loaded)),
+            Some(Err(e)) => error.set(Some(e)),
+            None => {}
+        }
+    });
+
+    // Status changes of the order's items arrive over the WebSocket, so the page never needs to poll.
+    // The connection only receives this order's changes while the page is open
+    let order_subscription = websocket_state.order_subscription;
+    Effect::new(move |_| {
+        order_subscription.set(params.with(|params| params.as_ref().map(|p| p.id.clone()).ok()));
+    });
+    on_cleanup(move || order_subscription.set(None));
+
+    Effect::new({
+        let websocket_state = websocket_state.clone();
+        move |_| {
+            if let Some(message) = websocket_state.tracked_items.get() {
+                tracking.update(|tracking| {
+                    let Some(tracking) = tracking.as_mut() else {
+                        return;
+                    };
+                    match message {
+                        Message::Add(item) | Message::Update(item) => {
+                            match tracking.items.iter_mut().find(|tracked| tracked.id == item.id) {
+                                Some(tracked) => *tracked = item,
+                                None => tracking.items.push(item),
+                            }
+                        }
+                        Message::Delete(id) => tracking.items.retain(|tracked| tracked.id != id),
+                    }
+                });
+                websocket_state.tracked_items.set(None);
+            }
+        }
+    });
+
+    // A deleted order is gone for the customer as well
+    Effect::new({
+        let websocket_state = websocket_state.clone();
+        move |_| {
+            if let Some(Message::Delete(id)) = websocket_state.orders.get() {
+                if tracking.with_untracked(|t| t.as_ref().is_some_and(|t| t.order_id == id)) {
+                    tracking.set(None);
+                    error.set(Some("Order not found".to_string()));
+                }
+                websocket_state.orders.set(None);
+            }
+        }
+    });
+
+    view! {
+        <div class="min-h-screen bg-background p-6">
+            <div class="max-w-md mx-auto">
+                <Show
+                    when=move || tracking.with(|t| t.is_some())
+                    fallback=move || view! {
+                        <div class="text-center py-12 text-text-muted">
+                            {move || error.get().unwrap_or_else(|| "Loading order...".to_string())}
+                        </div>
+                    }
+                >
+                    {move || tracking.get().map(|tracking| {
+                        let (label, class) = stage_label(&tracking.items);
+                        view! {
+                            <div class="bg-surface rounded-2xl border border-border p-6 text-center">
+                                <p class="text-sm text-text-muted">"Your order"</p>
+                                <h1 class="text-3xl font-bold text-primary mt-1">{tracking.name.clone()}</h1>
+                                <p class="text-lg text-text-muted">{tracking.number.clone()}</p>
+                                <p class=format!("text-2xl font-semibold mt-4 {}", class)>{label}</p>
+                            </div>
+                            <ul class="mt-4 space-y-2">
+                                {tracking.items.into_iter().filter(|item| item.status != types::OrderStatus::Cancelled).map(|item| view! {
+                                    <li class="flex items-center justify-between p-3 bg-surface rounded-lg border border-border">
+                                        <span class="text-text">{format!("{}× {}", item.quantity, item.product_name)}</span>
+                                        <span class="text-sm text-text-muted">{item.status.to_string()}</span>
+                                    </li>
+                                }).collect_view()}
+                            </ul>
+                        }
+                    })}
+                </Show>
+            </div>
+        </div>
+    }
+}
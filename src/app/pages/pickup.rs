@@ -0,0 +1,240 @@
+use leptos::html::Audio;
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use leptos_use::core::ConnectionReadyState;
+#[cfg(feature = "hydrate")]
+use leptos_use::{use_timestamp_with_options, UseTimestampOptions};
+use std::collections::HashMap;
+
+use crate::app::states::{settings, websocket};
+use crate::backend::item::get_items;
+use crate::backend::order::get_orders;
+use crate::backend::status_history::get_ready_since;
+use crate::common::pickup::{board, PickupEntry, PickupStage};
+use crate::common::resource_type::Message;
+use crate::common::station::apply_item_message;
+
+/// Ready orders that were not picked up leave the board after this long
+const READY_TIMEOUT_SECS: i64 = 10 * 60;
+/// How long a freshly ready order is highlighted
+const HIGHLIGHT_SECS: i64 = 10;
+
+#[component]
+fn PickupColumn(
+    title: &'static str,
+    entries: Signal<Vec<PickupEntry>>,
+    highlighted: Signal<Vec<String>>,
+    ready: bool,
+) -> impl IntoView {
+    view! {
+        <section class="flex-1 flex flex-col bg-surface rounded-2xl border border-border p-6 overflow-hidden">
+            <h2 class=if ready { "text-4xl font-bold text-green-600 mb-6" } else { "text-4xl font-bold text-text-muted mb-6" }>
+                {title}
+            </h2>
+            <ul class="grid grid-cols-1 xl:grid-cols-2 gap-4 content-start">
+                <For
+                    each=move || entries.get()
+                    key=|entry| entry.order_id.clone()
+                    children=move |entry| {
+                        let order_id = entry.order_id.clone();
+                        let class = move || {
+                            let base = "px-4 py-3 rounded-xl text-3xl font-semibold transition-colors";
+                            if highlighted.with(|ids| ids.contains(&order_id)) {
+                                format!("{} bg-green-600 text-white animate-pulse", base)
+                            } else if ready {
+                                format!("{} bg-green-600/10 text-text", base)
+                            } else {
+                                format!("{} bg-surface-elevated text-text-muted", base)
+                            }
+                        };
+                        view! {
+                            <li class=class>
+                                {entry.number.map(|number| view! { <span class="opacity-60 mr-3">{number}</span> })}
+                                {entry.name}
+                            </li>
+                        }
+                    }
+                />
+            </ul>
+        </section>
+    }
+}
+
+/// Read-only board for a screen at the counter, showing which orders are being prepared and which can be picked up
+#[component]
+pub fn PickupBoard() -> impl IntoView {
+    let items = RwSignal::new(Vec::new());
+    let orders_by_id = RwSignal::new(HashMap::new());
+    // When each order became ready, used for the highlight and the timeout
+    let ready_since = RwSignal::new(HashMap::<String, i64>::new());
+    // Orders already ready when the board loads are taken over silently, with the time they became ready
+    let seeded = RwSignal::new(false);
+    let (sound, set_sound) = signal(false);
+    let chime = NodeRef::<Audio>::new();
+    let (reload_trigger, set_reload_trigger) = signal::<u32>(0);
+    let websocket_state = websocket::get();
+    // Nothing is loaded or applied until the device knows its event
+    let event_scope = settings::get().event_scope();
+
+    #[cfg(feature = "hydrate")]
+    let now = {
+        let timestamp = use_timestamp_with_options(UseTimestampOptions::default().interval(1000));
+        Signal::derive(move || (timestamp.get() / 1000.0) as i64)
+    };
+    #[cfg(not(feature = "hydrate"))]
+    let now = Signal::derive(|| 0i64);
+
+    Effect::new({
+        let websocket_state = websocket_state.clone();
+        move |_| {
+            if let Some(message) = websocket_state.items.get() {
+                if let Some(event_id) = event_scope.get_untracked() {
+                    items.update(|items| apply_item_message(items, message, event_id.as_deref(), |_| true));
+                }
+                websocket_state.items.set(None);
+            }
+        }
+    });
+
+    // Pickup names come with the orders
+    Effect::new({
+        let websocket_state = websocket_state.clone();
+        move |_| {
+            if let Some(message) = websocket_state.orders.get() {
+                if event_scope.get_untracked().is_some() {
+                    orders_by_id.update(|orders| match message {
+                        Message::Add(order) | Message::Update(order) => {
+                            orders.insert(order.id.clone(), order);
+                        }
+                        Message::Delete(id) => {
+                            orders.remove(&id);
+                        }
+                    });
+                }
+                websocket_state.orders.set(None);
+            }
+        }
+    });
+
+    Effect::new({
+        let websocket_state = websocket_state.clone();
+        move |was_open: Option<bool>| {
+            let open = websocket_state.state.get() == ConnectionReadyState::Open;
+            if open && was_open == Some(false) {
+                set_reload_trigger.update(|n| *n += 1);
+            }
+            open
+        }
+    });
+
+    Effect::new(move |_| {
+        reload_trigger.track();
+        seeded.set(false);
+        let Some(event_id) = event_scope.get() else {
+            orders_by_id.set(HashMap::new());
+            items.set(Vec::new());
+            return;
+        };
+        spawn_local(async move {
+            if let Ok(loaded) = get_orders(event_id.clone()).await {
+                orders_by_id.set(loaded.into_iter().map(|o| (o.id.clone(), o)).collect());
+            }
+            if let Ok(loaded) = get_items(event_id).await {
+                items.set(loaded);
+            }
+
+            let ready_ids: Vec<String> = orders_by_id
+                .with_untracked(|orders| items.with_untracked(|items| board(items, orders)))
+                .into_iter()
+                .filter(|entry| entry.stage == PickupStage::Ready)
+                .map(|entry| entry.order_id)
+                .collect();
+            let became_ready = get_ready_since(ready_ids.clone()).await.unwrap_or_default();
+            let now = now.get_untracked();
+            ready_since.set(
+                ready_ids
+                    .into_iter()
+                    .map(|order_id| {
+                        let since = became_ready.get(&order_id).copied().unwrap_or(now);
+                        (order_id, since)
+                    })
+                    .collect(),
+            );
+            seeded.set(true);
+        });
+    });
+
+    let entries = Memo::new(move |_| orders_by_id.with(|orders| items.with(|items| board(items, orders))));
+
+    // Remember when orders turned ready and chime for the ones that just did
+    Effect::new(move |_| {
+        let entries = entries.get();
+        if !seeded.get_untracked() {
+            return;
+        }
+        let now = now.get_untracked();
+        let mut newly_ready = false;
+        ready_since.update(|ready_since| {
+            ready_since.retain(|order_id, _| {
+                entries.iter().any(|e| &e.order_id == order_id && e.stage == PickupStage::Ready)
+            });
+            for entry in entries.iter().filter(|e| e.stage == PickupStage::Ready) {
+                if !ready_since.contains_key(&entry.order_id) {
+                    ready_since.insert(entry.order_id.clone(), now);
+                    newly_ready = true;
+                }
+            }
+        });
+        if newly_ready && sound.get_untracked() {
+            if let Some(chime) = chime.get_untracked() {
+                let _ = chime.play();
+            }
+        }
+    });
+
+    let preparing = Signal::derive(move || {
+        entries.get().into_iter().filter(|e| e.stage == PickupStage::Preparing).collect::<Vec<_>>()
+    });
+    // Newest ready orders first, orders waiting too long are dropped
+    let ready = Signal::derive(move || {
+        let now = now.get();
+        let ready_since = ready_since.get();
+        let mut ready: Vec<(i64, PickupEntry)> = entries
+            .get()
+            .into_iter()
+            .filter_map(|entry| ready_since.get(&entry.order_id).map(|since| (*since, entry)))
+            .filter(|(since, _)| now - since < READY_TIMEOUT_SECS)
+            .collect();
+        ready.sort_by_key(|(since, _)| std::cmp::Reverse(*since));
+        ready.into_iter().map(|(_, entry)| entry).collect::<Vec<_>>()
+    });
+    let highlighted = Signal::derive(move || {
+        let now = now.get();
+        ready_since.with(|ready_since| {
+            ready_since
+                .iter()
+                .filter(|(_, since)| now - **since < HIGHLIGHT_SECS)
+                .map(|(order_id, _)| order_id.clone())
+                .collect::<Vec<_>>()
+        })
+    });
+
+    view! {
+        <div class="fixed inset-0 z-50 bg-background flex flex-col p-8 gap-6">
+            <div class="flex items-center justify-between">
+                <h1 class="text-5xl font-bold text-text">"Pickup"</h1>
+                <button
+                    class="px-4 py-2 rounded-lg text-sm bg-surface border border-border text-text-muted hover:text-text"
+                    on:click=move |_| set_sound.update(|sound| *sound = !*sound)
+                >
+                    {move || if sound.get() { "Sound on" } else { "Sound off" }}
+                </button>
+            </div>
+            <div class="flex-1 flex gap-6 min-h-0">
+                <PickupColumn title="Preparing" entries=preparing highlighted=highlighted ready=false />
+                <PickupColumn title="Ready for pickup" entries=ready highlighted=highlighted ready=true />
+            </div>
+            <audio node_ref=chime src="/chime.wav" preload="auto"></audio>
+        </div>
+    }
+}
@@ -0,0 +1,120 @@
+use leptos::prelude::*;
+use leptos_router::{hooks::use_params, params::Params};
+
+use crate::backend::receipt::{get_receipt, PrintReceipt};
+use crate::backend::tracking::get_order_qr;
+use crate::common::receipt::Receipt;
+
+#[derive(Params, PartialEq, Clone)]
+struct ReceiptParams {
+    id: String,
+}
+
+#[component]
+fn ReceiptView(receipt: Receipt, qr_code: Option<String>) -> impl IntoView {
+    let total = receipt.total();
+    let breakdown = receipt.tax_breakdown();
+
+    view! {
+        <div class="bg-white text-black font-mono text-sm p-4 space-y-3">
+            <div class="text-center">
+                <p class="font-bold">{receipt.event_name.clone()}</p>
+                <p class="text-2xl font-bold">{format!("{} {}", receipt.number, receipt.order_name)}</p>
+                <p>{receipt.date.clone()}</p>
+            </div>
+            <table class="w-full border-t border-b border-dashed border-black">
+                {receipt.lines.into_iter().map(|line| view! {
+                    <tr>
+                        <td>{format!("{}× {}", line.quantity, line.name)}</td>
+                        <td class="text-right whitespace-nowrap">{format!("{:.2} €", line.total())}</td>
+                    </tr>
+                }).collect_view()}
+            </table>
+            <div class="flex justify-between font-bold">
+                <span>"Total"</span>
+                <span>{format!("{:.2} €", total)}</span>
+            </div>
+            <div class="flex justify-between">
+                <span>"Paid"</span>
+                <span>{receipt.payment_method.label()}</span>
+            </div>
+            <table class="w-full text-xs border-t border-dashed border-black">
+                <tr>
+                    <th class="text-left font-normal">"VAT"</th>
+                    <th class="text-right font-normal">"Net"</th>
+                    <th class="text-right font-normal">"Tax"</th>
+                    <th class="text-right font-normal">"Gross"</th>
+                </tr>
+                {breakdown.into_iter().map(|tax| view! {
+                    <tr>
+                        <td>{format!("{}%", tax.rate)}</td>
+                        <td class="text-right">{format!("{:.2}", tax.net)}</td>
+                        <td class="text-right">{format!("{:.2}", tax.tax)}</td>
+                        <td class="text-right">{format!("{:.2}", tax.gross)}</td>
+                    </tr>
+                }).collect_view()}
+            </table>
+            {qr_code.map(|svg| view! {
+                <div class="flex flex-col items-center">
+                    <div inner_html=svg></div>
+                    <span class="text-xs">"Follow your order"</span>
+                </div>
+            })}
+            <p class="text-center">"Thank you!"</p>
+        </div>
+    }
+}
+
+/// Print view of an order's receipt, opened from the cashier after an order and from the order history
+#[component]
+pub fn ReceiptPage() -> impl IntoView {
+    let params = use_params::<ReceiptParams>();
+    let print_action = ServerAction::<PrintReceipt>::new();
+    let order_id = move || params.with(|params| params.as_ref().map(|p| p.id.clone()).ok());
+
+    let receipt = Resource::new(order_id, |id| async move {
+        let Some(id) = id else {
+            return Err("Order not found".to_string());
+        };
+        let receipt = get_receipt(id.clone()).await.map_err(|e| e.to_string())?;
+        let qr_code = get_order_qr(id).await.ok();
+        Ok((receipt, qr_code))
+    });
+
+    view! {
+        <div class="min-h-screen bg-background p-6 print:p-0 print:bg-white">
+            <div class="max-w-xs mx-auto">
+                <div class="flex gap-2 mb-4 print:hidden">
+                    <button
+                        class="flex-1 py-2 px-4 rounded-lg bg-primary text-white font-semibold hover:bg-primary/90"
+                        on:click=move |_| {
+                            let _ = window().print();
+                        }
+                    >
+                        "Print"
+                    </button>
+                    <button
+                        class="flex-1 py-2 px-4 rounded-lg border border-border bg-surface text-text font-semibold hover:bg-surface-elevated disabled:opacity-50"
+                        disabled=move || print_action.pending().get()
+                        on:click=move |_| {
+                            if let Some(order_id) = order_id() {
+                                print_action.dispatch(PrintReceipt { order_id });
+                            }
+                        }
+                    >
+                        "Receipt printer"
+                    </button>
+                </div>
+                {move || print_action.value().get().and_then(|result| result.err()).map(|e| view! {
+                    <p class="mb-4 text-sm text-error print:hidden">{e.to_string()}</p>
+                })}
+                <Suspense fallback=|| view! { <p class="text-center text-text-muted">"Loading receipt..."</p> }>
+                    {move || receipt.get().map(|result| match result {
+                        Ok((receipt, qr_code)) => view! { <ReceiptView receipt=receipt qr_code=qr_code /> }.into_any(),
+                        Err(e) => view! { <p class="text-center text-error">{e}</p> }.into_any(),
+                    })}
+                </Suspense>
+            </div>
+        </div>
+    }
+}
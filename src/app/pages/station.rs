@@ -1,5 +1,8 @@
+use crate::app::components::expo_view::ExpoView;
+use crate::app::components::station_metrics::StationMetricsHeader;
 use crate::app::components::station_view::StationView;
 use crate::app::components::stations::Stations;
+use crate::app::states::{event, settings};
 use crate::backend::station::get_station;
 use leptos::prelude::*;
 use leptos_router::{hooks::use_params, params::Params};
@@ -12,6 +15,29 @@ struct StationParams {
 #[component]
 pub fn StationPage() -> impl IntoView {
     let params = use_params::<StationParams>();
+    let events = event::get().get_events();
+    let settings_state = settings::get();
+    let settings = settings_state.get_settings();
+    let event_id = settings_state.event_id();
+
+    // Name of the event this station is serving, stations show every event's items while none is active
+    let serving = move || {
+        match event_id.get() {
+            Some(id) => {
+                let name = events
+                    .get()
+                    .into_iter()
+                    .find(|e| e.id == id)
+                    .map(|e| e.name)
+                    .unwrap_or(id);
+                format!("Serving {}", name)
+            }
+            None if settings.get().is_some_and(|s| s.active_event_ids.len() > 1) => {
+                "Several events are active, choose the booth of this device".to_string()
+            }
+            None => "No active event, showing items of all events".to_string(),
+        }
+    };
 
     // Create a resource to fetch the station
     let station_resource = Resource::new(
@@ -39,9 +65,14 @@ pub fn StationPage() -> impl IntoView {
                                     <div>
                                         <div class="mb-8">
                                             <h1 class="text-3xl font-bold text-text mb-2">{station.name.clone()}</h1>
-                                            <p class="text-text-muted">"Manage items for this station"</p>
+                                            <p class="text-text-muted">{serving}</p>
                                         </div>
-                                        <StationView station_id=station.id.clone() />
+                                        <StationMetricsHeader station_id=station.id.clone() />
+                                        {if station.expo {
+                                            view! { <ExpoView station_id=station.id.clone() /> }.into_any()
+                                        } else {
+                                            view! { <StationView station_id=station.id.clone() /> }.into_any()
+                                        }}
                                     </div>
                                 }.into_any(),
                                 None => view! {
@@ -0,0 +1,84 @@
+use leptos::prelude::*;
+use leptos::task::spawn_local;
+use crate::common::types::Alert;
+use crate::backend::alert::get_alerts;
+use crate::common::resource_type::Message;
+use crate::app::states::websocket;
+
+#[derive(Debug, Clone)]
+pub struct AlertState {
+    alerts: ReadSignal<Vec<Alert>>,
+    set_alerts: WriteSignal<Vec<Alert>>,
+}
+
+impl AlertState {
+    fn new() -> Self {
+        let (alerts, set_alerts) = signal(Vec::new());
+
+        // Load the currently active alerts once on initialization
+        Effect::new(move |_| {
+            spawn_local(async move {
+                if let Ok(active) = get_alerts().await {
+                    set_alerts.set(active);
+                }
+            });
+        });
+
+        let alert_state = Self {
+            alerts,
+            set_alerts,
+        };
+
+        // Connect to websocket updates
+        let websocket_state = websocket::get();
+        Effect::new({
+            let alert_state = alert_state.clone();
+            let websocket_state = websocket_state.clone();
+            move |_| {
+                if let Some(message) = websocket_state.alerts.get() {
+                    match message {
+                        Message::Add(alert) | Message::Update(alert) => {
+                            alert_state.upsert_alert(alert);
+                        }
+                        Message::Delete(id) => {
+                            alert_state.remove_alert(&id);
+                        }
+                    }
+                    // Clear the signal after processing to allow new messages to trigger
+                    websocket_state.alerts.set(None);
+                }
+            }
+        });
+
+        alert_state
+    }
+
+    pub fn get_alerts(&self) -> ReadSignal<Vec<Alert>> {
+        self.alerts
+    }
+
+    /// Add an alert or replace the one with the same id (alert ids identify the condition)
+    pub fn upsert_alert(&self, alert: Alert) {
+        self.set_alerts.update(|alerts| {
+            if let Some(existing) = alerts.iter_mut().find(|a| a.id == alert.id) {
+                *existing = alert;
+            } else {
+                alerts.push(alert);
+            }
+        });
+    }
+
+    pub fn remove_alert(&self, id: &str) {
+        self.set_alerts.update(|alerts| alerts.retain(|a| a.id != id));
+    }
+}
+
+pub fn provide() -> AlertState {
+    let alert_state = AlertState::new();
+    provide_context(alert_state.clone());
+    alert_state
+}
+
+pub fn get() -> AlertState {
+    expect_context::<AlertState>()
+}
@@ -5,4 +5,5 @@ pub mod station;
 pub mod event;
 pub mod settings;
 pub mod websocket;
-pub mod order;
\ No newline at end of file
+pub mod order;
+pub mod alert;
\ No newline at end of file
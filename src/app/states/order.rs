@@ -1,5 +1,5 @@
 use leptos::prelude::*;
-use crate::common::types::Product;
+use crate::common::types::{PaymentMethod, Product};
 
 #[derive(Debug, Clone)]
 pub struct OrderItem {
@@ -28,15 +28,30 @@ impl OrderItem {
 pub struct OrderState {
     items: ReadSignal<Vec<OrderItem>>,
     set_items: WriteSignal<Vec<OrderItem>>,
+    rush: ReadSignal<bool>,
+    set_rush: WriteSignal<bool>,
+    payment_method: ReadSignal<PaymentMethod>,
+    set_payment_method: WriteSignal<PaymentMethod>,
+    submit_requests: ReadSignal<u32>,
+    set_submit_requests: WriteSignal<u32>,
 }
 
 impl OrderState {
     pub fn new() -> Self {
         let (items, set_items) = signal(Vec::new());
+        let (rush, set_rush) = signal(false);
+        let (payment_method, set_payment_method) = signal(PaymentMethod::default());
+        let (submit_requests, set_submit_requests) = signal(0u32);
         
         Self {
             items,
             set_items,
+            rush,
+            set_rush,
+            payment_method,
+            set_payment_method,
+            submit_requests,
+            set_submit_requests,
         }
     }
     
@@ -47,15 +62,20 @@ impl OrderState {
     
     /// Add a product to the order (or increase quantity if already present)
     pub fn add_product(&self, product: Product) {
-        if !product.active {
+        self.add_product_quantity(product, 1);
+    }
+    
+    /// Add several units of a product at once, e.g. a quantity typed on the keyboard
+    pub fn add_product_quantity(&self, product: Product, quantity: u32) {
+        if !product.active || quantity == 0 {
             return; // Don't add inactive products
         }
         
         self.set_items.update(|items| {
             if let Some(existing_item) = items.iter_mut().find(|item| item.product_id == product.id) {
-                existing_item.quantity += 1;
+                existing_item.quantity += quantity;
             } else {
-                items.push(OrderItem::from_product(product));
+                items.push(OrderItem { quantity, ..OrderItem::from_product(product) });
             }
         });
     }
@@ -89,9 +109,38 @@ impl OrderState {
         });
     }
     
+    /// Whether the order is flagged as rush, which pins it to the top of the station boards
+    pub fn get_rush(&self) -> ReadSignal<bool> {
+        self.rush
+    }
+    
+    pub fn toggle_rush(&self) {
+        self.set_rush.update(|rush| *rush = !*rush);
+    }
+    
+    /// How the customer pays, printed on the receipt
+    pub fn get_payment_method(&self) -> ReadSignal<PaymentMethod> {
+        self.payment_method
+    }
+    
+    pub fn set_payment_method(&self, method: PaymentMethod) {
+        self.set_payment_method.set(method);
+    }
+    
+    /// Counts submit requests from outside the order panel, e.g. the submit shortcut
+    pub fn get_submit_requests(&self) -> ReadSignal<u32> {
+        self.submit_requests
+    }
+    
+    pub fn request_submit(&self) {
+        self.set_submit_requests.update(|requests| *requests += 1);
+    }
+    
     /// Clear all items from the order
     pub fn clear(&self) {
         self.set_items.set(Vec::new());
+        self.set_rush.set(false);
+        self.set_payment_method.set(PaymentMethod::default());
     }
     
     /// Get the total price of all items
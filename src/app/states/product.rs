@@ -3,7 +3,7 @@ use leptos::task::spawn_local;
 use crate::common::types::Product;
 use crate::backend::product::get_products;
 use crate::common::resource_type::Message;
-use crate::app::states::websocket;
+use crate::app::states::{event, settings, websocket};
 
 #[derive(Debug, Clone)]
 pub struct ProductState {
@@ -106,4 +106,18 @@ pub fn provide() -> ProductState {
 
 pub fn get() -> ProductState {
     expect_context::<ProductState>()
+}
+
+/// Products on the menu of this device's event at the event's prices, as the cashier sells them
+pub fn menu_products() -> Signal<Vec<Product>> {
+    let products = get().get_products();
+    let events = event::get().get_events();
+    let event_id = settings::get().event_id();
+    Signal::derive(move || {
+        let event_id = event_id.get();
+        let menu = events
+            .with(|events| events.iter().find(|e| Some(&e.id) == event_id.as_ref()).map(|e| e.menu.clone()))
+            .unwrap_or_default();
+        products.with(|products| crate::common::menu::menu_products(products, &menu))
+    })
 }
\ No newline at end of file
@@ -5,12 +5,19 @@ use crate::backend::settings::get_settings;
 use crate::common::resource_type::Message;
 use crate::app::states::websocket;
 
+/// Local storage key of the event this device works for
+const DEVICE_EVENT_KEY: &str = "device:event";
+
+fn local_storage() -> Option<web_sys::Storage> {
+    web_sys::window().and_then(|window| window.local_storage().ok().flatten())
+}
 
 
 #[derive(Debug, Clone)]
 pub struct SettingsState {
     settings: ReadSignal<Option<Settings>>,
     set_settings: WriteSignal<Option<Settings>>,
+    device_event: RwSignal<Option<String>>, // Chosen on this device, may no longer be active
 }
 
 impl SettingsState {
@@ -30,9 +37,15 @@ impl SettingsState {
             }
         });
 
+        let device_event = RwSignal::new(None);
+        Effect::new(move |_| {
+            device_event.set(local_storage().and_then(|storage| storage.get_item(DEVICE_EVENT_KEY).ok().flatten()));
+        });
+
         let settings_state = Self {
             settings,
             set_settings,
+            device_event,
         };
 
         // Connect to websocket updates
@@ -72,9 +85,36 @@ impl SettingsState {
         self.set_settings.set(Some(settings));
     }
     
-    /// Get the current active event ID if available
-    pub fn get_active_event_id(&self) -> Option<String> {
-        self.settings.get().and_then(|s| s.active_event_id)
+    /// Event this device works for, `None` while several events are active and none was chosen
+    pub fn event_id(&self) -> Signal<Option<String>> {
+        let settings = self.settings;
+        let device_event = self.device_event;
+        Signal::derive(move || {
+            let chosen = device_event.get();
+            settings.with(|s| s.as_ref().and_then(|s| s.event_for_device(chosen.as_deref())))
+        })
+    }
+
+    /// Event boards load and apply changes for: `Some(None)` for every event while none is active,
+    /// `None` while the settings are loading or several events are active and none was chosen
+    pub fn event_scope(&self) -> Signal<Option<Option<String>>> {
+        let settings = self.settings;
+        let event_id = self.event_id();
+        Signal::derive(move || {
+            let several_active = settings.with(|s| s.as_ref().map(|s| s.active_event_ids.len() > 1))?;
+            match event_id.get() {
+                None if several_active => None,
+                event_id => Some(event_id),
+            }
+        })
+    }
+
+    /// Binds this device to one of the active events
+    pub fn choose_event(&self, event_id: String) {
+        if let Some(storage) = local_storage() {
+            storage.set_item(DEVICE_EVENT_KEY, &event_id).ok();
+        }
+        self.device_event.set(Some(event_id));
     }
 }
 
@@ -14,6 +14,13 @@ pub struct WebSocketState {
     pub stations: RwSignal<Option<Message<Station>>>,
     pub events: RwSignal<Option<Message<Event>>>,
     pub settings: RwSignal<Option<Message<Settings>>>,
+    pub alerts: RwSignal<Option<Message<Alert>>>,
+    /// Item changes of the subscribed order, as the public view sent to tracking pages
+    pub tracked_items: RwSignal<Option<Message<TrackedItem>>>,
+    /// Station this client is currently serving, announced to the server while connected
+    pub station_presence: RwSignal<Option<String>>,
+    /// Order whose changes alone this client receives, for the public order tracking page
+    pub order_subscription: RwSignal<Option<String>>,
 }
 
 impl WebSocketState {
@@ -28,6 +35,10 @@ impl WebSocketState {
             stations: RwSignal::new(None),
             events: RwSignal::new(None),
             settings: RwSignal::new(None),
+            alerts: RwSignal::new(None),
+            tracked_items: RwSignal::new(None),
+            station_presence: RwSignal::new(None),
+            order_subscription: RwSignal::new(None),
         }
     }
 
@@ -77,6 +88,16 @@ impl WebSocketState {
                     self.settings.set(Some(ws_msg.message));
                 }
             }
+            "alert" => {
+                if let Ok(ws_msg) = serde_json::from_str::<WebSocketMessage<Alert>>(json_str) {
+                    self.alerts.set(Some(ws_msg.message));
+                }
+            }
+            "tracked_item" => {
+                if let Ok(ws_msg) = serde_json::from_str::<WebSocketMessage<TrackedItem>>(json_str) {
+                    self.tracked_items.set(Some(ws_msg.message));
+                }
+            }
             _ => {} // Unknown resource type
         }
     }
@@ -0,0 +1,446 @@
+use leptos::prelude::*;
+
+use crate::common::types;
+
+/// Pending units of one item at a station and how long its order has been waiting
+#[derive(Debug, Clone)]
+pub struct WaitingItem {
+    pub quantity: u32,
+    pub waiting_secs: i64,
+}
+
+/// Everything that is currently waiting at a single station
+#[derive(Debug, Clone)]
+pub struct StationLoad {
+    pub station: types::Station,
+    pub items: Vec<WaitingItem>,
+}
+
+/// Applies the alert rules to a snapshot of the system and returns every alert that should be active
+pub fn evaluate_rules(
+    rules: &types::AlertRules,
+    products: &[types::Product],
+    loads: &[StationLoad],
+    disconnected: &[types::Station],
+    printer_errors: &[types::Station],
+) -> Vec<types::Alert> {
+    let mut alerts = Vec::new();
+
+    if let Some(threshold) = rules.low_stock_threshold {
+        for product in products.iter().filter(|p| p.active) {
+            if let Some(stock) = product.stock.filter(|stock| *stock < threshold) {
+                alerts.push(types::Alert {
+                    id: format!("low_stock:{}", product.id),
+                    kind: types::AlertKind::LowStock,
+                    message: format!("{} is running low ({} left)", product.name, stock),
+                    station_id: None,
+                    product_id: Some(product.id.clone()),
+                });
+            }
+        }
+    }
+
+    for load in loads {
+        let station = &load.station;
+
+        if let Some(threshold) = rules.backlog_threshold {
+            let backlog: u32 = load.items.iter().map(|item| item.quantity).sum();
+            if backlog > threshold {
+                alerts.push(types::Alert {
+                    id: format!("backlog:{}", station.id),
+                    kind: types::AlertKind::StationBacklog,
+                    message: format!("{} has {} items pending", station.name, backlog),
+                    station_id: Some(station.id.clone()),
+                    product_id: None,
+                });
+            }
+        }
+
+        if let Some(max_minutes) = rules.max_wait_minutes {
+            let limit_secs = i64::from(max_minutes) * 60;
+            let overdue: Vec<i64> = load
+                .items
+                .iter()
+                .map(|item| item.waiting_secs)
+                .filter(|secs| *secs > limit_secs)
+                .collect();
+            if let Some(longest) = overdue.iter().max() {
+                alerts.push(types::Alert {
+                    id: format!("item_waiting:{}", station.id),
+                    kind: types::AlertKind::ItemWaiting,
+                    message: format!(
+                        "{} items at {} waiting longer than {} min (longest {} min)",
+                        overdue.len(),
+                        station.name,
+                        max_minutes,
+                        longest / 60
+                    ),
+                    station_id: Some(station.id.clone()),
+                    product_id: None,
+                });
+            }
+        }
+    }
+
+    for station in disconnected {
+        alerts.push(types::Alert {
+            id: format!("disconnected:{}", station.id),
+            kind: types::AlertKind::StationDisconnected,
+            message: format!("{} lost its connection", station.name),
+            station_id: Some(station.id.clone()),
+            product_id: None,
+        });
+    }
+
+    for station in printer_errors {
+        alerts.push(types::Alert {
+            id: format!("printer:{}", station.id),
+            kind: types::AlertKind::PrinterError,
+            message: format!("Printer of {} is not reachable", station.name),
+            station_id: Some(station.id.clone()),
+            product_id: None,
+        });
+    }
+
+    alerts
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    pub use crate::backend::db::DB;
+    pub use crate::backend::websocket::{broadcast_add, broadcast_delete, broadcast_update};
+    pub use crate::common::types;
+    pub use leptos::logging::log;
+    pub use surrealdb::sql::Datetime;
+    use std::collections::{HashMap, HashSet};
+    use std::sync::{LazyLock, Mutex};
+
+    use super::{evaluate_rules, StationLoad, WaitingItem};
+
+    /// How often time based rules are re-evaluated without any triggering change
+    pub const MONITOR_INTERVAL_SECS: u64 = 30;
+    /// How long the monitor waits after a change for further ones, so a burst is evaluated once
+    pub const EVALUATION_DELAY_MILLIS: u64 = 250;
+
+    static ACTIVE_ALERTS: LazyLock<Mutex<HashMap<String, types::Alert>>> =
+        LazyLock::new(|| Mutex::new(HashMap::new()));
+    // Number of open WebSocket connections that announced themselves as a station
+    static STATION_CONNECTIONS: LazyLock<Mutex<HashMap<String, usize>>> =
+        LazyLock::new(|| Mutex::new(HashMap::new()));
+    static DISCONNECTED_STATIONS: LazyLock<Mutex<HashSet<String>>> =
+        LazyLock::new(|| Mutex::new(HashSet::new()));
+    // Stations whose printer failed the last ticket even after retrying
+    static FAILED_PRINTERS: LazyLock<Mutex<HashSet<String>>> =
+        LazyLock::new(|| Mutex::new(HashSet::new()));
+    // Wakes the monitor after a change, requests made while it is busy collapse into a single one
+    static EVALUATION_REQUESTED: tokio::sync::Notify = tokio::sync::Notify::const_new();
+
+    pub fn active_alerts() -> Vec<types::Alert> {
+        ACTIVE_ALERTS.lock().unwrap().values().cloned().collect()
+    }
+
+    /// Collects the current state, applies the rules and broadcasts every alert that changed.
+    /// Only the alert monitor calls this, so evaluations never overlap
+    async fn evaluate_alerts() -> Result<(), leptos::prelude::ServerFnError> {
+        use crate::backend::item::get_items_by_station;
+        use crate::backend::order::ssr::{Order, ORDERS};
+        use crate::backend::product::get_products;
+        use crate::backend::settings::get_settings;
+        use crate::backend::station::get_stations;
+
+        let settings = get_settings().await?;
+        let rules = settings.alert_rules;
+        let stations = get_stations().await?;
+
+        let products = if rules.low_stock_threshold.is_some() {
+            get_products().await?
+        } else {
+            Vec::new()
+        };
+
+        let mut loads = Vec::new();
+        if rules.backlog_threshold.is_some() || rules.max_wait_minutes.is_some() {
+            // Stations are shared, so their load is the work of every active event together
+            let events: Vec<Option<String>> = if settings.active_event_ids.is_empty() {
+                vec![None]
+            } else {
+                settings.active_event_ids.into_iter().map(Some).collect()
+            };
+            let mut response = DB
+                .query("SELECT * FROM type::table($table) WHERE $all OR event IN $events")
+                .bind(("table", ORDERS))
+                .bind(("all", events.contains(&None)))
+                .bind(("events", events.iter().flatten().cloned().collect::<Vec<_>>()))
+                .await?;
+            let orders: Vec<Order> = response.take(0)?;
+            let created_at: HashMap<String, i64> = orders
+                .into_iter()
+                .filter_map(|order| {
+                    order
+                        .id
+                        .map(|id| (id.key().to_string(), order.created_at.to_secs()))
+                })
+                .collect();
+            let now = Datetime::default().to_secs();
+
+            for station in &stations {
+                let mut items = Vec::new();
+                for event in &events {
+                    items.extend(get_items_by_station(station.id.clone(), event.clone()).await?);
+                }
+                let items = items
+                    .into_iter()
+                    .map(|item| WaitingItem {
+                        quantity: item.quantity,
+                        waiting_secs: item
+                            .order_id
+                            .and_then(|order_id| created_at.get(&order_id).copied())
+                            .map(|created| now - created)
+                            .unwrap_or(0),
+                    })
+                    .collect();
+                loads.push(StationLoad {
+                    station: station.clone(),
+                    items,
+                });
+            }
+        }
+
+        let disconnected: Vec<types::Station> = {
+            let disconnected_ids = DISCONNECTED_STATIONS.lock().unwrap();
+            stations
+                .iter()
+                .filter(|station| disconnected_ids.contains(&station.id))
+                .cloned()
+                .collect()
+        };
+        let printer_errors: Vec<types::Station> = {
+            let failed_ids = FAILED_PRINTERS.lock().unwrap();
+            stations
+                .iter()
+                .filter(|station| failed_ids.contains(&station.id))
+                .cloned()
+                .collect()
+        };
+
+        publish(evaluate_rules(&rules, &products, &loads, &disconnected, &printer_errors));
+        Ok(())
+    }
+
+    fn publish(alerts: Vec<types::Alert>) {
+        let mut active = ACTIVE_ALERTS.lock().unwrap();
+        let next: HashMap<String, types::Alert> = alerts
+            .into_iter()
+            .map(|alert| (alert.id.clone(), alert))
+            .collect();
+
+        for id in active.keys() {
+            if !next.contains_key(id) {
+                broadcast_delete::<types::Alert>(id.clone());
+            }
+        }
+        for (id, alert) in &next {
+            match active.get(id) {
+                None => broadcast_add(alert.clone()),
+                Some(previous) if previous.message != alert.message => {
+                    broadcast_update(alert.clone())
+                }
+                Some(_) => {}
+            }
+        }
+
+        *active = next;
+    }
+
+    /// Asks the alert monitor to re-evaluate all alerts, call this after any change that may affect a rule
+    pub fn schedule_evaluation() {
+        EVALUATION_REQUESTED.notify_one();
+    }
+
+    /// Re-evaluates the rules after changes and periodically, so waiting times are caught without further changes
+    pub fn spawn_alert_monitor() {
+        tokio::spawn(async {
+            let mut interval =
+                tokio::time::interval(std::time::Duration::from_secs(MONITOR_INTERVAL_SECS));
+            loop {
+                tokio::select! {
+                    _ = interval.tick() => {}
+                    _ = EVALUATION_REQUESTED.notified() => {
+                        tokio::time::sleep(std::time::Duration::from_millis(EVALUATION_DELAY_MILLIS)).await;
+                    }
+                }
+                if let Err(e) = evaluate_alerts().await {
+                    log!("Alert evaluation failed: {}", e);
+                }
+            }
+        });
+    }
+
+    /// Registers a WebSocket connection that serves the given station
+    pub fn station_connected(station_id: &str) {
+        *STATION_CONNECTIONS
+            .lock()
+            .unwrap()
+            .entry(station_id.to_string())
+            .or_default() += 1;
+
+        let was_disconnected = DISCONNECTED_STATIONS.lock().unwrap().remove(station_id);
+        if was_disconnected {
+            schedule_evaluation();
+        }
+    }
+
+    /// Records whether the latest ticket of a station reached its printer, raising or clearing the alert
+    pub fn printer_result(station_id: &str, printed: bool) {
+        let changed = {
+            let mut failed = FAILED_PRINTERS.lock().unwrap();
+            if printed {
+                failed.remove(station_id)
+            } else {
+                failed.insert(station_id.to_string())
+            }
+        };
+        if changed {
+            schedule_evaluation();
+        }
+    }
+
+    /// Unregisters a station connection, raising an alert if the last one was lost unexpectedly
+    pub fn station_left(station_id: &str, connection_lost: bool) {
+        let remaining = {
+            let mut connections = STATION_CONNECTIONS.lock().unwrap();
+            let Some(count) = connections.get_mut(station_id) else {
+                return;
+            };
+            *count = count.saturating_sub(1);
+            let remaining = *count;
+            if remaining == 0 {
+                connections.remove(station_id);
+            }
+            remaining
+        };
+
+        if remaining == 0 && connection_lost {
+            DISCONNECTED_STATIONS
+                .lock()
+                .unwrap()
+                .insert(station_id.to_string());
+            schedule_evaluation();
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+use ssr::*;
+
+#[server(GetAlerts, "/api/alert")]
+pub async fn get_alerts() -> Result<Vec<types::Alert>, ServerFnError> {
+    Ok(active_alerts())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn station(id: &str) -> types::Station {
+        types::Station {
+            id: id.to_string(),
+            name: format!("Station {}", id),
+            category_ids: vec!["food".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn product(id: &str, stock: Option<u32>) -> types::Product {
+        types::Product {
+            id: id.to_string(),
+            name: format!("Product {}", id),
+            category_id: "food".to_string(),
+            price: 4.5,
+            active: true,
+            stock,
+        }
+    }
+
+    fn waiting(quantity: u32, minutes: i64) -> WaitingItem {
+        WaitingItem {
+            quantity,
+            waiting_secs: minutes * 60,
+        }
+    }
+
+    #[test]
+    fn test_disabled_rules_raise_nothing() {
+        let loads = vec![StationLoad {
+            station: station("grill"),
+            items: vec![waiting(50, 90)],
+        }];
+        let products = vec![product("wurst", Some(0))];
+
+        let alerts = evaluate_rules(&types::AlertRules::default(), &products, &loads, &[], &[]);
+
+        assert!(alerts.is_empty(), "Rules without thresholds should not raise alerts");
+    }
+
+    #[test]
+    fn test_low_stock_only_for_tracked_products() {
+        let rules = types::AlertRules {
+            low_stock_threshold: Some(5),
+            ..Default::default()
+        };
+        let products = vec![
+            product("low", Some(2)),
+            product("plenty", Some(20)),
+            product("untracked", None),
+        ];
+
+        let alerts = evaluate_rules(&rules, &products, &[], &[], &[]);
+
+        assert_eq!(alerts.len(), 1);
+        assert_eq!(alerts[0].kind, types::AlertKind::LowStock);
+        assert_eq!(alerts[0].product_id.as_deref(), Some("low"));
+    }
+
+    #[test]
+    fn test_backlog_counts_quantities() {
+        let rules = types::AlertRules {
+            backlog_threshold: Some(5),
+            ..Default::default()
+        };
+        let loads = vec![
+            StationLoad {
+                station: station("grill"),
+                items: vec![waiting(4, 0), waiting(2, 0)],
+            },
+            StationLoad {
+                station: station("bar"),
+                items: vec![waiting(5, 0)],
+            },
+        ];
+
+        let alerts = evaluate_rules(&rules, &[], &loads, &[], &[]);
+
+        assert_eq!(alerts.len(), 1, "Only stations above the threshold should alert");
+        assert_eq!(alerts[0].id, "backlog:grill");
+    }
+
+    #[test]
+    fn test_waiting_items_grouped_per_station() {
+        let rules = types::AlertRules {
+            max_wait_minutes: Some(10),
+            ..Default::default()
+        };
+        let loads = vec![StationLoad {
+            station: station("grill"),
+            items: vec![waiting(1, 5), waiting(1, 12), waiting(2, 25)],
+        }];
+
+        let alerts = evaluate_rules(&rules, &[], &loads, &[station("bar")], &[]);
+
+        assert_eq!(alerts.len(), 2);
+        assert_eq!(alerts[0].kind, types::AlertKind::ItemWaiting);
+        assert!(alerts[0].message.contains("longest 25 min"));
+        assert_eq!(alerts[1].kind, types::AlertKind::StationDisconnected);
+        assert_eq!(alerts[1].station_id.as_deref(), Some("bar"));
+    }
+}
@@ -20,6 +20,12 @@ pub mod ssr {
         pub id: Option<RecordId>,
         #[validate(length(min = 1, max = 64))]
         pub name: String,
+        #[serde(default = "default_tax_rate")]
+        pub tax_rate: f64,
+    }
+
+    fn default_tax_rate() -> f64 {
+        types::DEFAULT_TAX_RATE
     }
 
     impl From<Category> for types::Category {
@@ -27,6 +33,7 @@ pub mod ssr {
             Self {
                 id: record.id.unwrap().key().to_string(),
                 name: record.name,
+                tax_rate: record.tax_rate,
             }
         }
     }
@@ -43,6 +50,7 @@ pub async fn create_category(
         .content(Category {
             id: None,
             name: req.name,
+            tax_rate: req.tax_rate.unwrap_or(types::DEFAULT_TAX_RATE),
         })
         .await?;
 
@@ -83,6 +91,7 @@ pub async fn update_category(
     let updated = Category {
         id: category.id,
         name: update.name.or_else(|| Some(category.name)).unwrap(),
+        tax_rate: update.tax_rate.unwrap_or(category.tax_rate),
     };
     // Update the category in the database
     let updated_category: Option<Category> = DB
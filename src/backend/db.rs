@@ -7,6 +7,17 @@ pub type Database = Surreal<Client>;
 
 pub static DB: LazyLock<Surreal<Client>> = LazyLock::new(Surreal::init);
 
+/// Indexes and backfills that keep hot queries from scanning or resolving records one by one
+const MIGRATIONS: &str = "
+    DEFINE INDEX IF NOT EXISTS items_by_order ON items FIELDS order_id;
+    DEFINE INDEX IF NOT EXISTS items_by_station ON items FIELDS event, status, category_id;
+    DEFINE INDEX IF NOT EXISTS status_history_by_item ON item_status_history FIELDS item_id;
+    DEFINE INDEX IF NOT EXISTS status_history_by_order ON item_status_history FIELDS order_id;
+    DEFINE INDEX IF NOT EXISTS status_history_by_station ON item_status_history FIELDS station_id, changed_at;
+    UPDATE items SET category_id = (SELECT VALUE category_id FROM type::thing('products', $parent.product_id))[0] WHERE category_id = NONE;
+    UPDATE items SET event = (SELECT VALUE event FROM type::thing('orders', $parent.order_id))[0] WHERE event = NONE;
+";
+
 pub async fn initialize_database() -> Result<(), String> {
     use crate::backend::config::AppConfig;
 
@@ -28,5 +39,16 @@ pub async fn initialize_database() -> Result<(), String> {
         .await
         .map_err(|e| format!("Failed to select namespace/database: {}", e))?;
 
+    migrate().await
+}
+
+/// Applies `MIGRATIONS` to the selected namespace and database, safe to run on every start
+pub async fn migrate() -> Result<(), String> {
+    DB.query(MIGRATIONS)
+        .await
+        .map_err(|e| format!("Failed to migrate database: {}", e))?
+        .check()
+        .map_err(|e| format!("Failed to migrate database: {}", e))?;
+
     Ok(())
 }
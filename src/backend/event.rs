@@ -10,6 +10,8 @@ pub mod ssr {
     pub use leptos::server_fn::error::ServerFnError::ServerError;
     pub use serde::{Deserialize, Serialize};
     pub use surrealdb::sql::Thing;
+    use jiff::{tz::TimeZone, Timestamp, Zoned};
+    use std::sync::LazyLock;
     use surrealdb::RecordId;
     pub use validator::Validate;
     pub const EVENTS: &str = "events";
@@ -19,25 +21,204 @@ pub mod ssr {
         pub id: Option<RecordId>,
         #[validate(length(min = 1, max = 64))]
         pub name: String,
+        #[serde(default)]
+        pub custom_statuses: Vec<String>,
+        #[serde(default)]
+        pub name_pool: types::NamePool,
+        #[serde(default)]
+        pub order_counter: u32, // Number of the event's latest order
+        #[serde(default)]
+        pub state: types::EventState,
+        #[serde(default)]
+        pub starts_at: Option<String>,
+        #[serde(default)]
+        pub ends_at: Option<String>,
+        #[serde(default)]
+        pub menu: Vec<types::MenuEntry>,
     }
     impl From<Event> for types::Event {
         fn from(event: Event) -> Self {
             Self {
                 id: event.id.unwrap().key().to_string(),
                 name: event.name,
+                custom_statuses: event.custom_statuses,
+                name_pool: event.name_pool,
+                state: event.state,
+                starts_at: event.starts_at,
+                ends_at: event.ends_at,
+                menu: event.menu,
             }
         }
     }
+
+    /// A planned copy of an event's configuration: status flow, pickup names and the menu entries of
+    /// products that still exist. Orders start from scratch. Stations, stock and alert rules are not part
+    /// of an event, they are shared by all events and apply to the copy as they are
+    pub fn duplicate(
+        source: Event,
+        name: String,
+        (starts_at, ends_at): (Option<String>, Option<String>),
+        products: &[types::Product],
+    ) -> Event {
+        use crate::common::menu::existing_entries;
+
+        Event {
+            id: None,
+            name,
+            custom_statuses: source.custom_statuses,
+            name_pool: source.name_pool,
+            order_counter: 0,
+            state: types::EventState::Planned,
+            starts_at,
+            ends_at,
+            menu: existing_entries(&source.menu, products),
+        }
+    }
+
+    /// Time zone of event schedules and printouts, named by `EVENT_TIMEZONE`, the server's own otherwise
+    static EVENT_TIMEZONE: LazyLock<TimeZone> = LazyLock::new(|| {
+        let Ok(name) = std::env::var("EVENT_TIMEZONE") else {
+            return TimeZone::system();
+        };
+        TimeZone::get(name.trim()).unwrap_or_else(|e| {
+            leptos::logging::log!("Unknown EVENT_TIMEZONE '{}', using the system time zone: {}", name, e);
+            TimeZone::system()
+        })
+    });
+
+    /// Date and time of a moment in the format of the event schedule, daylight saving included
+    pub fn schedule_time(timestamp: Timestamp, zone: &TimeZone) -> String {
+        timestamp.to_zoned(zone.clone()).strftime("%Y-%m-%dT%H:%M").to_string()
+    }
+
+    /// A moment given in unix seconds in the event time zone, for printed times
+    pub fn event_time(secs: i64) -> Option<Zoned> {
+        Timestamp::from_second(secs).ok().map(|timestamp| timestamp.to_zoned(EVENT_TIMEZONE.clone()))
+    }
+
+    /// Current date and time in the event time zone, comparable with the event schedule
+    pub fn local_now() -> String {
+        schedule_time(Timestamp::now(), &EVENT_TIMEZONE)
+    }
+
+    /// Fails unless the event takes orders right now
+    pub async fn ensure_accepts_orders(event_id: &str) -> Result<(), leptos::prelude::ServerFnError> {
+        let event = super::get_event(event_id.to_string()).await?;
+        match event.order_refusal(&local_now()) {
+            Some(reason) => Err(ServerError(format!("Event '{}' {}", event.name, reason))),
+            None => Ok(()),
+        }
+    }
+
+    /// Fails if the event was closed, its orders and items stay as they were for reporting
+    pub async fn ensure_unlocked(event_id: Option<&str>) -> Result<(), leptos::prelude::ServerFnError> {
+        let Some(event_id) = event_id else {
+            return Ok(());
+        };
+        let event: Option<Event> = DB.select((EVENTS, event_id)).await?;
+        match event {
+            Some(event) if event.state.is_locked() => Err(ServerError(format!(
+                "Event '{}' is closed, its orders can no longer change",
+                event.name
+            ))),
+            _ => Ok(()),
+        }
+    }
+
+    /// Fails if the event was closed, its name, status flow, menu and schedule stay as they were for
+    /// reporting until it is reopened
+    pub fn ensure_editable(event: &Event) -> Result<(), leptos::prelude::ServerFnError> {
+        if event.state.is_locked() {
+            return Err(ServerError(format!("Event '{}' is closed, reopen it to change it", event.name)));
+        }
+        Ok(())
+    }
+
+    /// Start and end of an event's schedule from the form inputs, `None` keeps the current value
+    pub fn schedule(
+        starts_at: Option<String>,
+        ends_at: Option<String>,
+        current: (Option<String>, Option<String>),
+    ) -> Result<(Option<String>, Option<String>), leptos::prelude::ServerFnError> {
+        let parse = |input: Option<String>, current: Option<String>| match input {
+            Some(input) => types::parse_local_datetime(&input),
+            None => Ok(current),
+        };
+        let (starts_at, ends_at) = match (parse(starts_at, current.0), parse(ends_at, current.1)) {
+            (Ok(starts_at), Ok(ends_at)) => (starts_at, ends_at),
+            (Err(e), _) | (_, Err(e)) => return Err(ServerError(e)),
+        };
+        if let (Some(start), Some(end)) = (&starts_at, &ends_at) {
+            if end <= start {
+                return Err(ServerError("The event must end after it starts".to_string()));
+            }
+        }
+        Ok((starts_at, ends_at))
+    }
+
+    /// The event operational queries are scoped to, an explicit id wins over the active event
+    pub async fn resolve_event(
+        event_id: Option<String>,
+    ) -> Result<Option<String>, leptos::prelude::ServerFnError> {
+        match event_id {
+            Some(event_id) => Ok(Some(event_id)),
+            None => crate::backend::settings::get_active_event().await,
+        }
+    }
+
+    /// Increments the event's order counter in a single statement, returning the number of the new order
+    pub async fn next_order_number(event_id: &str) -> Result<u32, leptos::prelude::ServerFnError> {
+        let mut response = DB
+            .query("UPDATE type::thing($table, $id) SET order_counter = (order_counter OR 0) + 1 RETURN VALUE order_counter")
+            .bind(("table", EVENTS))
+            .bind(("id", event_id.to_string()))
+            .await?;
+        let number: Option<u32> = response.take(0)?;
+        number.ok_or_else(|| ServerError("Event not found".into()))
+    }
+
+    /// Fails if a custom status that is about to be removed is still used by a station
+    pub async fn ensure_statuses_unused(
+        removed: &[String],
+    ) -> Result<(), leptos::prelude::ServerFnError> {
+        use crate::backend::station::get_stations;
+
+        if removed.is_empty() {
+            return Ok(());
+        }
+        for station in get_stations().await? {
+            let used = station
+                .input_statuses
+                .iter()
+                .chain(std::iter::once(&station.output_status))
+                .find(|status| removed.iter().any(|name| name == status.label()));
+            if let Some(status) = used {
+                return Err(ServerError(format!(
+                    "Status '{}' is still used by station {}",
+                    status, station.name
+                )));
+            }
+        }
+        Ok(())
+    }
 }
 #[cfg(feature = "ssr")]
 use ssr::*;
 
 #[server(CreateEvent, "/api/event")]
 pub async fn create_event(req: requests::event::Create) -> Result<types::Event, ServerFnError> {
+    let (starts_at, ends_at) = schedule(req.starts_at, req.ends_at, (None, None))?;
     let e: Option<Event> = DB.create(EVENTS)
         .content(Event {
             id: None,
             name: req.name,
+            custom_statuses: Vec::new(),
+            name_pool: types::NamePool::default(),
+            order_counter: 0,
+            state: types::EventState::Planned,
+            starts_at,
+            ends_at,
+            menu: Vec::new(),
         })
         .await?;
 
@@ -75,14 +256,56 @@ pub async fn update_event(
         return Err(ServerError("Event not found".into()));
     }
     let event = existing_event.unwrap();
-    let updated = Event {
-        id: event.id,
-        name: update.name.or_else(|| Some(event.name)).unwrap(),
+    // Reopening and archiving are the only changes to a closed event
+    let changes_configuration = update.name.is_some()
+        || update.custom_statuses.is_some()
+        || update.name_pool.is_some()
+        || update.starts_at.is_some()
+        || update.ends_at.is_some();
+    if changes_configuration {
+        ensure_editable(&event)?;
+    }
+    let custom_statuses = match update.custom_statuses {
+        Some(input) => {
+            let statuses = match types::OrderStatus::parse_custom(&input) {
+                Ok(statuses) => statuses,
+                Err(e) => return Err(ServerError(e)),
+            };
+            let removed: Vec<String> = event
+                .custom_statuses
+                .iter()
+                .filter(|name| !statuses.contains(name))
+                .cloned()
+                .collect();
+            ensure_statuses_unused(&removed).await?;
+            statuses
+        }
+        None => event.custom_statuses,
     };
-    // Update the event in the database
+    let state = match update.state {
+        Some(state) if !event.state.can_become(state) => {
+            return Err(ServerError(format!(
+                "An event can't go from {} to {}",
+                event.state.label(),
+                state.label()
+            )));
+        }
+        Some(state) => state,
+        None => event.state,
+    };
+    let (starts_at, ends_at) = schedule(update.starts_at, update.ends_at, (event.starts_at, event.ends_at))?;
+    // Only the fields of the form are written, the order counter is incremented concurrently by new
+    // orders and the menu has its own form
     let updated_event: Option<Event> = DB
         .update((EVENTS, &id))
-        .content(updated)
+        .merge(serde_json::json!({
+            "name": update.name.unwrap_or(event.name),
+            "custom_statuses": custom_statuses,
+            "name_pool": update.name_pool.unwrap_or(event.name_pool),
+            "state": state,
+            "starts_at": starts_at,
+            "ends_at": ends_at,
+        }))
         .await?;
     
     if let Some(event) = updated_event {
@@ -96,6 +319,11 @@ pub async fn update_event(
 
 #[server(DeleteEvent, "/api/event")]
 pub async fn delete_event(id: String) -> Result<(), ServerFnError> {
+    // Closed and archived events keep their data for reporting
+    let event: Option<Event> = DB.select((EVENTS, &id)).await?;
+    if let Some(event) = event.filter(|event| event.state.is_locked()) {
+        return Err(ServerError(format!("Event '{}' is closed and cannot be deleted", event.name)));
+    }
     let deleted: Option<Event> = DB.delete((EVENTS, &id)).await?;
     if let Some(_event) = deleted {
         broadcast_delete::<types::Event>(id);
@@ -104,3 +332,205 @@ pub async fn delete_event(id: String) -> Result<(), ServerFnError> {
         Err(ServerError(format!("Event with id {} not found", id)))
     }
 }
+
+/// Replaces the event's menu, an empty menu offers every product at its list price
+#[server(SetEventMenu, "/api/event")]
+pub async fn set_event_menu(
+    event_id: String,
+    #[server(default)] menu: Vec<types::MenuEntry>,
+) -> Result<types::Event, ServerFnError> {
+    for (index, entry) in menu.iter().enumerate() {
+        if entry.price.is_some_and(|price| !price.is_finite() || price < 0.0) {
+            return Err(ServerError(format!("Invalid price for product {}", entry.product_id)));
+        }
+        if menu[..index].iter().any(|other| other.product_id == entry.product_id) {
+            return Err(ServerError(format!("Product {} is on the menu twice", entry.product_id)));
+        }
+    }
+
+    let event: Option<Event> = DB.select((EVENTS, &event_id)).await?;
+    let Some(event) = event else {
+        return Err(ServerError("Event not found".to_string()));
+    };
+    ensure_editable(&event)?;
+
+    let updated: Option<Event> = DB
+        .update((EVENTS, &event_id))
+        .merge(serde_json::json!({ "menu": menu }))
+        .await?;
+    let Some(updated) = updated else {
+        return Err(ServerError("Event not found".to_string()));
+    };
+    let result: types::Event = updated.into();
+    broadcast_update(result.clone());
+    Ok(result)
+}
+
+/// Creates a planned event with the configuration of an existing one, see `ssr::duplicate` for what is copied
+#[server(DuplicateEvent, "/api/event")]
+pub async fn duplicate_event(id: String, req: requests::event::Create) -> Result<types::Event, ServerFnError> {
+    use crate::backend::product::get_products;
+
+    let source: Option<Event> = DB.select((EVENTS, &id)).await?;
+    let Some(source) = source else {
+        return Err(ServerError("Event not found".to_string()));
+    };
+    let (starts_at, ends_at) = schedule(req.starts_at, req.ends_at, (None, None))?;
+    let products = get_products().await?;
+
+    let created: Option<Event> = DB
+        .create(EVENTS)
+        .content(duplicate(source, req.name, (starts_at, ends_at), &products))
+        .await?;
+    let Some(created) = created else {
+        return Err(ServerError("Failed to duplicate event".to_string()));
+    };
+    let result: types::Event = created.into();
+    broadcast_add(result.clone());
+    Ok(result)
+}
+
+/// Number of the event's orders that still have items to prepare or hand out
+#[server(CountOpenOrders, "/api/event")]
+pub async fn count_open_orders(event_id: String) -> Result<usize, ServerFnError> {
+    let mut response = DB
+        .query("SELECT VALUE order_id FROM items WHERE event = $event AND status NOT IN $terminal")
+        .bind(("event", event_id))
+        .bind(("terminal", [types::OrderStatus::Completed, types::OrderStatus::Cancelled]))
+        .await?;
+    let mut order_ids: Vec<String> = response.take(0)?;
+    order_ids.sort();
+    order_ids.dedup();
+    Ok(order_ids.len())
+}
+
+/// Closes an event, so it takes no more orders and its data stays as it is for reporting.
+/// Items still open are cancelled on request, otherwise they are kept as they are.
+#[server(CloseEvent, "/api/event")]
+pub async fn close_event(id: String, cancel_open_orders: bool) -> Result<types::Event, ServerFnError> {
+    use crate::backend::item::ssr::Item;
+    use crate::backend::status_history::ssr::{record_changes, StatusChange};
+    use crate::backend::websocket::broadcast_to_event;
+    use crate::common::resource_type::Message;
+
+    let event: Option<Event> = DB.select((EVENTS, &id)).await?;
+    let Some(event) = event else {
+        return Err(ServerError("Event not found".to_string()));
+    };
+    if event.state.is_locked() {
+        return Err(ServerError(format!("Event '{}' is already closed", event.name)));
+    }
+
+    if cancel_open_orders {
+        let mut response = DB
+            .query("UPDATE items SET status = $cancelled WHERE event = $event AND status NOT IN $terminal RETURN BEFORE")
+            .bind(("cancelled", types::OrderStatus::Cancelled))
+            .bind(("event", id.clone()))
+            .bind(("terminal", [types::OrderStatus::Completed, types::OrderStatus::Cancelled]))
+            .await?;
+        let previous: Vec<Item> = response.take(0)?;
+
+        let mut changes = Vec::new();
+        for item in previous {
+            let mut item_type: types::Item = item.into();
+            changes.push(StatusChange::new(
+                item_type.id.clone(),
+                item_type.order_id.clone().unwrap_or_default(),
+                Some(item_type.status.clone()),
+                types::OrderStatus::Cancelled,
+                None,
+            ));
+            item_type.status = types::OrderStatus::Cancelled;
+            broadcast_to_event(Some(&id), Message::Update(item_type));
+        }
+        record_changes(changes).await?;
+    }
+
+    let closed: Option<Event> = DB
+        .update((EVENTS, &id))
+        .merge(serde_json::json!({ "state": types::EventState::Closed }))
+        .await?;
+    let Some(closed) = closed else {
+        return Err(ServerError("Failed to close event".to_string()));
+    };
+    let result: types::Event = closed.into();
+    broadcast_update(result.clone());
+    crate::backend::alert::ssr::schedule_evaluation();
+
+    Ok(result)
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::ssr::{duplicate, ensure_editable, schedule_time, Event, EVENTS};
+    use crate::common::types;
+    use jiff::{tz::TimeZone, Timestamp};
+
+    #[test]
+    fn test_schedule_time_follows_daylight_saving() {
+        let berlin = TimeZone::get("Europe/Berlin").unwrap();
+        let summer: Timestamp = "2025-07-12T14:00:00Z".parse().unwrap();
+        let winter: Timestamp = "2025-01-12T14:00:00Z".parse().unwrap();
+
+        assert_eq!(schedule_time(summer, &berlin), "2025-07-12T16:00");
+        assert_eq!(schedule_time(winter, &berlin), "2025-01-12T15:00");
+    }
+
+    #[test]
+    fn test_duplicate_copies_only_the_event_configuration() {
+        let entry = |product_id: &str, price: Option<f64>| types::MenuEntry { product_id: product_id.to_string(), price, sort: None };
+        let source = Event {
+            id: Some(surrealdb::RecordId::from((EVENTS, "sommerfest"))),
+            name: "Sommerfest".to_string(),
+            custom_statuses: vec!["Grill".to_string()],
+            name_pool: types::NamePool::Animals,
+            order_counter: 42,
+            state: types::EventState::Closed,
+            starts_at: Some("2025-07-12T12:00".to_string()),
+            ends_at: Some("2025-07-12T22:00".to_string()),
+            menu: vec![entry("wurst", Some(3.5)), entry("deleted", None)],
+        };
+        let wurst = types::Product {
+            id: "wurst".to_string(),
+            name: "Wurst".to_string(),
+            category_id: "food".to_string(),
+            price: 3.0,
+            active: true,
+            stock: Some(80),
+        };
+
+        let copy = duplicate(source, "Sommerfest 2026".to_string(), (Some("2026-07-11T12:00".to_string()), None), &[wurst]);
+
+        // Copied: status flow, pickup names and the menu with its prices
+        assert_eq!(copy.custom_statuses, vec!["Grill".to_string()]);
+        assert_eq!(copy.name_pool, types::NamePool::Animals);
+        assert_eq!(copy.menu, vec![entry("wurst", Some(3.5))], "Deleted products are left off the menu");
+        // Not copied: identity, orders, lifecycle and schedule
+        assert!(copy.id.is_none());
+        assert_eq!(copy.name, "Sommerfest 2026");
+        assert_eq!(copy.order_counter, 0);
+        assert_eq!(copy.state, types::EventState::Planned);
+        assert_eq!(copy.starts_at.as_deref(), Some("2026-07-11T12:00"));
+        assert_eq!(copy.ends_at, None);
+    }
+
+    #[test]
+    fn test_closed_events_refuse_changes() {
+        let event = |state: types::EventState| Event {
+            id: None,
+            name: "Sommerfest".to_string(),
+            custom_statuses: Vec::new(),
+            name_pool: types::NamePool::GermanNames,
+            order_counter: 0,
+            state,
+            starts_at: None,
+            ends_at: None,
+            menu: Vec::new(),
+        };
+
+        assert!(ensure_editable(&event(types::EventState::Planned)).is_ok());
+        assert!(ensure_editable(&event(types::EventState::Open)).is_ok());
+        assert!(ensure_editable(&event(types::EventState::Closed)).is_err());
+        assert!(ensure_editable(&event(types::EventState::Archived)).is_err());
+    }
+}
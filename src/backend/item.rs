@@ -13,6 +13,17 @@ pub mod ssr {
     pub use validator::Validate;
     pub const ITEMS: &str = "items";
 
+    /// Items waiting at a station. The station is resolved inside the database, so this is a single
+    /// round trip no matter how many items are waiting
+    pub const STATION_ITEMS_QUERY: &str = "
+        LET $station = (SELECT * FROM type::thing($stations, $station_id))[0];
+        RETURN $station != NONE;
+        SELECT * FROM items
+            WHERE status IN $station.input_statuses
+            AND ($station.expo OR category_id IN $station.category_ids)
+            AND ($event = NONE OR event = $event);
+    ";
+
     #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
     pub struct Item {
         pub id: Option<RecordId>,
@@ -23,6 +34,11 @@ pub mod ssr {
         #[validate(range(min = 0.0))]
         pub price: f64,
         pub status: types::OrderStatus,
+        // Copied from the product and order so station queries don't need to resolve them per item
+        #[serde(default)]
+        pub category_id: Option<String>,
+        #[serde(default)]
+        pub event: Option<String>,
     }
 
     impl From<Item> for types::Item {
@@ -34,18 +50,28 @@ pub mod ssr {
                 quantity: record.quantity,
                 price: record.price,
                 status: record.status,
+                event: record.event,
             }
         }
     }
 
-    pub async fn create_items(order_id: String, items: Vec<types::Item>) -> Result<Vec<types::Item>, leptos::prelude::ServerFnError> {
-        use crate::backend::product::get_product;
-        use crate::backend::websocket::broadcast_add;
+    pub async fn create_items(order_id: String, event: String, items: Vec<types::Item>) -> Result<Vec<types::Item>, leptos::prelude::ServerFnError> {
+        use crate::backend::event::get_event;
+        use crate::backend::product::{get_product, ssr::consume_stock};
+        use crate::backend::status_history::ssr::{record_changes, StatusChange};
+        use crate::backend::websocket::broadcast_to_event;
+        use crate::common::resource_type::Message;
+        use crate::common::menu::menu_price;
+        let menu = get_event(event.clone()).await?.menu;
         let mut created_items = Vec::new();
+        let mut changes = Vec::new();
         
         for item in items {
-            // Get the product to fetch the current price
+            // Get the product to fetch the current price, the event's menu may override it
             let product = get_product(item.product_id.clone()).await?;
+            let Some(price) = menu_price(&product, &menu) else {
+                return Err(ServerError(format!("{} is not on the menu of this event", product.name)));
+            };
             
             let db_item: Option<Item> = DB.create(ITEMS)
                 .content(Item {
@@ -53,8 +79,10 @@ pub mod ssr {
                     order_id: order_id.clone(),
                     product_id: item.product_id,
                     quantity: item.quantity,
-                    price: product.price, // Use current product price
+                    price, // Use current menu price
                     status: types::OrderStatus::Ordered,
+                    category_id: Some(product.category_id),
+                    event: Some(event.clone()),
                 })
                 .await?;
                 
@@ -62,16 +90,36 @@ pub mod ssr {
                 let item_type: types::Item = created.clone().into();
                 
                 // Broadcast the new item
-                broadcast_add(item_type.clone());
+                broadcast_to_event(Some(&event), Message::Add(item_type.clone()));
+                consume_stock(&item_type.product_id, item_type.quantity).await?;
+                changes.push(StatusChange::new(
+                    item_type.id.clone(),
+                    order_id.clone(),
+                    None,
+                    item_type.status.clone(),
+                    None,
+                ));
                 
                 created_items.push(item_type);
             } else {
                 return Err(ServerError("Failed to create item".into()));
             }
         }
+        record_changes(changes).await?;
         
         Ok(created_items)
     }
+
+    /// Loads the given items in a single query, unknown ids are skipped
+    pub async fn items_by_ids(ids: &[String]) -> Result<Vec<Item>, leptos::prelude::ServerFnError> {
+        let ids: Vec<RecordId> = ids.iter().map(|id| RecordId::from((ITEMS, id.as_str()))).collect();
+        let mut response = DB
+            .query("SELECT * FROM type::table($table) WHERE id IN $ids")
+            .bind(("table", ITEMS))
+            .bind(("ids", ids))
+            .await?;
+        Ok(response.take(0)?)
+    }
 }
 #[cfg(feature = "ssr")]
 use ssr::*;
@@ -87,8 +135,14 @@ pub async fn get_items_by_order(order_id: String) -> Result<Vec<types::Item>, Se
 }
 
 #[server(GetItems, "/api/item")]
-pub async fn get_items() -> Result<Vec<types::Item>, ServerFnError> {
-    let items: Vec<Item> = DB.select(ITEMS).await?;
+pub async fn get_items(event_id: Option<String>) -> Result<Vec<types::Item>, ServerFnError> {
+    use crate::backend::event::ssr::resolve_event;
+
+    let event = resolve_event(event_id).await?;
+    let query_str = "SELECT * FROM items WHERE $event = NONE OR event = $event";
+    let mut response = DB.query(query_str).bind(("event", event)).await?;
+
+    let items: Vec<Item> = response.take(0)?;
     Ok(items.into_iter().map(Into::into).collect())
 }
 
@@ -105,7 +159,10 @@ pub async fn update_item(
     id: String,
     update: requests::item::Update,
 ) -> Result<types::Item, ServerFnError> {
-    use crate::backend::websocket::broadcast_update;
+    use crate::backend::event::ssr::ensure_unlocked;
+    use crate::backend::status_history::ssr::{record_changes, StatusChange};
+    use crate::backend::websocket::broadcast_to_event;
+    use crate::common::resource_type::Message;
     
     // Get the existing item
     let existing_item: Option<Item> = DB.select((ITEMS, &id)).await?;
@@ -113,18 +170,29 @@ pub async fn update_item(
         return Err(ServerError("Item not found".into()));
     }
     let item = existing_item.unwrap();
+    ensure_unlocked(item.event.as_deref()).await?;
+    let previous_status = item.status.clone();
     
-    // If product_id is being changed, get the new price
-    let new_price = if let Some(ref new_product_id) = update.product_id {
+    // If product_id is being changed, get the new price from the event's menu and the category
+    let (new_price, new_category_id) = if let Some(ref new_product_id) = update.product_id {
         if new_product_id != &item.product_id {
+            use crate::backend::event::get_event;
             use crate::backend::product::get_product;
+            use crate::common::menu::menu_price;
             let product = get_product(new_product_id.clone()).await?;
-            product.price
+            let menu = match &item.event {
+                Some(event_id) => get_event(event_id.clone()).await?.menu,
+                None => Vec::new(),
+            };
+            let Some(price) = menu_price(&product, &menu) else {
+                return Err(ServerError(format!("{} is not on the menu of this event", product.name)));
+            };
+            (price, Some(product.category_id))
         } else {
-            item.price
+            (item.price, item.category_id)
         }
     } else {
-        item.price
+        (item.price, item.category_id)
     };
     
     let updated = Item {
@@ -134,6 +202,8 @@ pub async fn update_item(
         quantity: update.quantity.or_else(|| Some(item.quantity)).unwrap(),
         price: new_price,
         status: update.status.or_else(|| Some(item.status)).unwrap(),
+        category_id: new_category_id,
+        event: item.event,
     };
     // Update the item in the database
     let updated_item: Option<Item> = DB
@@ -142,10 +212,23 @@ pub async fn update_item(
         .await?;
         
     if let Some(updated) = updated_item {
+        let event = updated.event.clone();
         let item_type: types::Item = updated.into();
         
+        if item_type.status != previous_status {
+            record_changes(vec![StatusChange::new(
+                item_type.id.clone(),
+                item_type.order_id.clone().unwrap_or_default(),
+                Some(previous_status),
+                item_type.status.clone(),
+                update.station_id,
+            )])
+            .await?;
+        }
+        
         // Broadcast the updated item
-        broadcast_update(item_type.clone());
+        broadcast_to_event(event.as_deref(), Message::Update(item_type.clone()));
+        crate::backend::alert::ssr::schedule_evaluation();
         
         Ok(item_type)
     } else {
@@ -155,93 +238,345 @@ pub async fn update_item(
 
 #[server(DeleteItem, "/api/item")]
 pub async fn delete_item(id: String) -> Result<(), ServerFnError> {
-    use crate::backend::websocket::broadcast_delete;
+    use crate::backend::event::ssr::ensure_unlocked;
+    use crate::backend::websocket::broadcast_delete_in;
     
-    let deleted: Option<Item> = DB.delete((ITEMS, &id)).await?;
-    if deleted.is_none() {
+    let item: Option<Item> = DB.select((ITEMS, &id)).await?;
+    ensure_unlocked(item.as_ref().and_then(|item| item.event.as_deref())).await?;
+    let Some(deleted) = DB.delete::<Option<Item>>((ITEMS, &id)).await? else {
         return Err(ServerError(format!("Item with id {} not found", id)));
-    }
+    };
     
     // Broadcast the item deletion
-    broadcast_delete::<types::Item>(id);
+    broadcast_delete_in::<types::Item>(deleted.event.as_deref(), Some(&deleted.order_id), id);
+    crate::backend::alert::ssr::schedule_evaluation();
     
     Ok(())
 }
 
 #[server(GetItemsByStation, "/api/item")]
-pub async fn get_items_by_station(station_id: String) -> Result<Vec<types::Item>, ServerFnError> {
-    use crate::backend::station::get_station;
-    use crate::backend::product::get_product;
-    
-    // Get the station to access its filtering criteria
-    let station = get_station(station_id).await?;
-    
-    // Query items that match the station's criteria
-    let query = "SELECT * FROM items WHERE status IN $input_statuses";
-    let mut response = DB.query(query)
-        .bind(("input_statuses", station.input_statuses))
+pub async fn get_items_by_station(
+    station_id: String,
+    event_id: Option<String>,
+) -> Result<Vec<types::Item>, ServerFnError> {
+    use crate::backend::event::ssr::resolve_event;
+    use crate::backend::station::ssr::STATIONS;
+
+    let event = resolve_event(event_id).await?;
+    let mut response = DB.query(STATION_ITEMS_QUERY)
+        .bind(("stations", STATIONS))
+        .bind(("station_id", station_id))
+        .bind(("event", event))
         .await?;
 
-    let items: Vec<Item> = response.take(0)?;
-    
-    // Filter items by category (need to check product category)
-    let mut filtered_items = Vec::new();
-    for item in items {
-        let product = get_product(item.product_id.clone()).await?;
-        if station.category_ids.contains(&product.category_id) {
-            filtered_items.push(item);
-        }
+    let found: Option<bool> = response.take(1)?;
+    if found != Some(true) {
+        return Err(ServerError("Station not found".into()));
     }
-    
-    Ok(filtered_items.into_iter().map(Into::into).collect())
+    let items: Vec<Item> = response.take(2)?;
+
+    Ok(items.into_iter().map(Into::into).collect())
 }
 
 #[server(UpdateItemsByOrder, "/api/item")]
 pub async fn update_items_by_order(order_id: String, station_id: String, new_status: types::OrderStatus) -> Result<Vec<types::Item>, ServerFnError> {
-    use crate::backend::station::get_station;
-    use crate::backend::product::get_product;
-    use crate::backend::websocket::broadcast_update;
-    
-    // Get the station to access its filtering criteria
-    let station = get_station(station_id).await?;
-    
-    // Get all items for the order
-    let query = "SELECT * FROM items WHERE order_id = $order_id";
-    let mut response = DB.query(query).bind(("order_id", order_id.clone())).await?;
-    let items: Vec<Item> = response.take(0)?;
-    
+    use crate::backend::event::ssr::ensure_unlocked;
+    use crate::backend::order::ssr::{Order, ORDERS};
+    use crate::backend::station::ssr::STATIONS;
+    use crate::backend::status_history::ssr::{record_changes, StatusChange};
+    use crate::backend::websocket::broadcast_to_event;
+    use crate::common::resource_type::Message;
+
+    let order: Option<Order> = DB.select((ORDERS, order_id.as_str())).await?;
+    let event = order.map(|order| order.event);
+    ensure_unlocked(event.as_deref()).await?;
+
+    // Only items in the station's categories AND input statuses move on, all in one statement.
+    // Expo stations take every category
+    let query_str = "
+        LET $station = (SELECT * FROM type::thing($stations, $station_id))[0];
+        RETURN $station != NONE;
+        UPDATE items SET status = $new_status
+            WHERE order_id = $order_id
+            AND status IN $station.input_statuses
+            AND ($station.expo OR category_id IN $station.category_ids)
+            RETURN BEFORE;
+    ";
+    let mut response = DB.query(query_str)
+        .bind(("stations", STATIONS))
+        .bind(("station_id", station_id.clone()))
+        .bind(("order_id", order_id))
+        .bind(("new_status", new_status.clone()))
+        .await?;
+
+    let found: Option<bool> = response.take(1)?;
+    if found != Some(true) {
+        return Err(ServerError("Station not found".into()));
+    }
+    // The items as they were before, so the history knows which status each one left
+    let previous: Vec<Item> = response.take(2)?;
+
     let mut updated_items = Vec::new();
+    let mut changes = Vec::new();
+    for item in previous {
+        let mut item_type: types::Item = item.into();
+        changes.push(StatusChange::new(
+            item_type.id.clone(),
+            item_type.order_id.clone().unwrap_or_default(),
+            Some(item_type.status.clone()),
+            new_status.clone(),
+            Some(station_id.clone()),
+        ));
+        item_type.status = new_status.clone();
+
+        // Broadcast each updated item
+        broadcast_to_event(event.as_deref(), Message::Update(item_type.clone()));
+
+        updated_items.push(item_type);
+    }
+    record_changes(changes).await?;
+    crate::backend::alert::ssr::schedule_evaluation();
     
-    // Filter and update only items that belong to products in the station's categories AND match the station's input statuses
+    Ok(updated_items)
+}
+
+/// Hands out a whole order at an expo station, only once every item of it is ready
+#[server(CompleteOrder, "/api/item")]
+pub async fn complete_order(order_id: String, station_id: String) -> Result<Vec<types::Item>, ServerFnError> {
+    use crate::backend::station::get_station;
+    use crate::common::expo::consolidate;
+
+    let station = get_station(station_id.clone()).await?;
+    if !station.expo {
+        return Err(ServerError("Only expo stations hand out whole orders".into()));
+    }
+    let items = get_items_by_order(order_id.clone()).await?;
+    let ready = consolidate(&items, &station)
+        .first()
+        .is_some_and(|order| order.is_ready());
+    if !ready {
+        return Err(ServerError("Not every item of the order is ready yet".into()));
+    }
+
+    update_items_by_order(order_id, station_id, station.output_status).await
+}
+
+/// Moves items a station bumped back to the status they had before, e.g. after a mis-tap.
+/// Only items whose latest change was made by this station and that have not moved on since are recalled.
+#[server(RecallItems, "/api/item")]
+pub async fn recall_items(item_ids: Vec<String>, station_id: String) -> Result<Vec<types::Item>, ServerFnError> {
+    use crate::backend::status_history::ssr::{history_of_items, record_changes, StatusChange};
+    use crate::backend::websocket::broadcast_to_event;
+    use crate::common::resource_type::Message;
+    use std::collections::HashMap;
+
+    let history = history_of_items(item_ids.clone()).await?;
+    let mut latest: HashMap<&str, &types::ItemStatusChange> = HashMap::new();
+    for change in &history {
+        latest.insert(change.item_id.as_str(), change);
+    }
+
+    let items = items_by_ids(&item_ids).await?;
+    for item in &items {
+        crate::backend::event::ssr::ensure_unlocked(item.event.as_deref()).await?;
+    }
+
+    let mut recalled = Vec::new();
+    let mut changes = Vec::new();
     for item in items {
-        let product = get_product(item.product_id.clone()).await?;
-        
-        // Only update items whose products belong to this station's categories AND have the correct status
-        if station.category_ids.contains(&product.category_id) && station.input_statuses.contains(&item.status) {
-            let updated = Item {
-                id: item.id.clone(),
-                order_id: item.order_id,
-                product_id: item.product_id,
-                quantity: item.quantity,
-                price: item.price,
-                status: new_status,
-            };
-            
-            let updated_item: Option<Item> = DB
-                .update((ITEMS, item.id.unwrap().key().to_string().as_str()))
-                .content(updated)
+        let id = item.id.as_ref().unwrap().key().to_string();
+        let Some(change) = latest.get(id.as_str()) else {
+            continue;
+        };
+        let Some(previous) = change.from.clone() else {
+            continue;
+        };
+        if change.station_id.as_deref() != Some(station_id.as_str()) || change.to != item.status {
+            continue;
+        }
+
+        let updated: Option<Item> = DB
+            .update((ITEMS, id.as_str()))
+            .merge(serde_json::json!({ "status": previous }))
+            .await?;
+        if let Some(updated) = updated {
+            let item_type: types::Item = updated.into();
+            changes.push(StatusChange::new(
+                item_type.id.clone(),
+                item_type.order_id.clone().unwrap_or_default(),
+                Some(item.status),
+                item_type.status.clone(),
+                Some(station_id.clone()),
+            ));
+            broadcast_to_event(item.event.as_deref(), Message::Update(item_type.clone()));
+            recalled.push(item_type);
+        }
+    }
+
+    if recalled.is_empty() {
+        return Err(ServerError("Nothing to recall, the items were already moved on".into()));
+    }
+    record_changes(changes).await?;
+    crate::backend::alert::ssr::schedule_evaluation();
+
+    Ok(recalled)
+}
+
+/// Marks `units` of a product as done at a station, advancing the waiting items in board order:
+/// rush orders first, then the oldest. An item is split when only part of its quantity is done.
+#[server(CompleteUnits, "/api/item")]
+pub async fn complete_units(
+    station_id: String,
+    product_id: String,
+    units: u32,
+    event_id: Option<String>,
+) -> Result<Vec<types::Item>, ServerFnError> {
+    use crate::backend::event::ssr::resolve_event;
+    use crate::backend::order::ssr::{Order, ORDERS};
+    use crate::backend::station::get_station;
+    use crate::backend::status_history::ssr::{copy_history, record_changes, StatusChange};
+    use crate::backend::websocket::broadcast_to_event;
+    use crate::common::resource_type::Message;
+    use crate::common::batch::allocate;
+    use std::collections::{HashMap, HashSet};
+
+    let station = get_station(station_id.clone()).await?;
+    let event = resolve_event(event_id).await?;
+    let waiting: Vec<types::Item> = get_items_by_station(station_id.clone(), event.clone())
+        .await?
+        .into_iter()
+        .filter(|item| item.product_id == product_id)
+        .collect();
+    // A board of all events shows items of closed events too, so each item's own event is checked
+    let item_events: HashSet<Option<&str>> = waiting.iter().map(|item| item.event.as_deref()).collect();
+    for item_event in item_events {
+        crate::backend::event::ssr::ensure_unlocked(item_event).await?;
+    }
+
+    let order_ids: Vec<surrealdb::RecordId> = waiting
+        .iter()
+        .filter_map(|item| item.order_id.as_deref())
+        .map(|id| surrealdb::RecordId::from((ORDERS, id)))
+        .collect();
+    let mut response = DB
+        .query("SELECT * FROM type::table($table) WHERE id IN $ids")
+        .bind(("table", ORDERS))
+        .bind(("ids", order_ids))
+        .await?;
+    let orders: Vec<Order> = response.take(0)?;
+    let order_rank: HashMap<String, (bool, i64)> = orders
+        .into_iter()
+        .filter_map(|order| {
+            let id = order.id?.key().to_string();
+            Some((id, (!order.rush, order.created_at.to_secs())))
+        })
+        .collect();
+
+    let mut waiting = waiting;
+    waiting.sort_by_key(|item| {
+        item.order_id
+            .as_ref()
+            .and_then(|id| order_rank.get(id).copied())
+            .unwrap_or((true, i64::MAX))
+    });
+    // Units an item that changed meanwhile did not take are allocated again over the items after it
+    let mut remaining = units;
+    let mut advanced = Vec::new();
+    let mut changes = Vec::new();
+    while remaining > 0 && !waiting.is_empty() {
+        let quantities: Vec<u32> = waiting.iter().map(|item| item.quantity).collect();
+        let taken = allocate(&quantities, remaining);
+        let tried = taken.iter().take_while(|taken| **taken > 0).count();
+        for (item, taken) in waiting.drain(..tried).zip(taken) {
+            let previous_status = item.status.clone();
+
+            // Claim the done part only if nobody changed the item since it was read, so two taps or two
+            // devices never split and advance the same item twice. Items that changed meanwhile are skipped.
+            let query_str = "
+                UPDATE type::thing($table, $id) SET status = $new_status, quantity = $taken
+                    WHERE status = $previous_status AND quantity = $expected
+                    RETURN BEFORE;
+            ";
+            let mut response = DB.query(query_str)
+                .bind(("table", ITEMS))
+                .bind(("id", item.id.clone()))
+                .bind(("new_status", station.output_status.clone()))
+                .bind(("taken", taken))
+                .bind(("previous_status", previous_status.clone()))
+                .bind(("expected", item.quantity))
                 .await?;
-                
-            if let Some(item) = updated_item {
-                let item_type: types::Item = item.into();
-                
-                // Broadcast each updated item
-                broadcast_update(item_type.clone());
-                
-                updated_items.push(item_type);
+            let claimed: Option<Item> = response.take(0)?;
+            let Some(before) = claimed else {
+                continue;
+            };
+            remaining -= taken;
+
+            // The part still waiting becomes a new item, the done part keeps the id and its history
+            if taken < item.quantity {
+                let rest: Option<Item> = DB
+                    .create(ITEMS)
+                    .content(Item {
+                        id: None,
+                        quantity: item.quantity - taken,
+                        ..before.clone()
+                    })
+                    .await?;
+                if let Some(rest) = rest {
+                    let rest: types::Item = rest.into();
+                    copy_history(item.id.clone(), rest.id.clone()).await?;
+                    broadcast_to_event(item.event.as_deref(), Message::Add(rest));
+                }
             }
+
+            let item_type = types::Item {
+                quantity: taken,
+                status: station.output_status.clone(),
+                ..before.into()
+            };
+            changes.push(StatusChange::new(
+                item_type.id.clone(),
+                item_type.order_id.clone().unwrap_or_default(),
+                Some(previous_status),
+                item_type.status.clone(),
+                Some(station_id.clone()),
+            ));
+            broadcast_to_event(item.event.as_deref(), Message::Update(item_type.clone()));
+            advanced.push(item_type);
         }
     }
-    
-    Ok(updated_items)
+    record_changes(changes).await?;
+    crate::backend::alert::ssr::schedule_evaluation();
+
+    Ok(advanced)
+}
+
+#[cfg(all(test, feature = "ssr"))]
+mod tests {
+    use super::ssr::STATION_ITEMS_QUERY;
+
+    fn statements(query: &str) -> Vec<String> {
+        query
+            .split(';')
+            .map(|statement| statement.split_whitespace().collect::<Vec<_>>().join(" "))
+            .filter(|statement| !statement.is_empty())
+            .collect()
+    }
+
+    #[test]
+    fn test_station_items_use_constant_query_count() {
+        let statements = statements(STATION_ITEMS_QUERY);
+
+        // One request with a fixed set of statements, so the number of round trips does not grow with the items
+        assert_eq!(statements.len(), 3);
+        assert!(statements[0].starts_with("LET $station = (SELECT * FROM type::thing($stations, $station_id))"));
+        assert!(statements[1].starts_with("RETURN"));
+        assert!(statements[2].starts_with("SELECT * FROM items WHERE"));
+
+        // Category and event are stored on the items, nothing is resolved per item
+        let items = &statements[2];
+        assert!(items.contains("category_id IN $station.category_ids"));
+        assert!(items.contains("event = $event"));
+        assert!(!items.contains("products"), "Items should not look up their product");
+        assert!(!items.contains("FETCH"), "Items should not fetch linked records");
+    }
 }
@@ -0,0 +1,133 @@
+use leptos::prelude::*;
+
+use crate::common::types;
+
+/// Per-station throughput, prep times, backlog and slowest products of an event, the active one by default.
+/// Only items that changed within the reported windows or are still open are read, with their history
+#[server(GetStationMetrics, "/api/metrics")]
+pub async fn get_station_metrics(event_id: Option<String>) -> Result<Vec<types::StationMetrics>, ServerFnError> {
+    use crate::backend::db::DB;
+    use crate::backend::event::ssr::resolve_event;
+    use crate::backend::item::ssr::{Item, ITEMS};
+    use crate::backend::product::get_products;
+    use crate::backend::station::get_stations;
+    use crate::backend::status_history::ssr::{StatusChange, STATUS_HISTORY};
+    use crate::common::metrics::{compute, WINDOW_COUNT, WINDOW_SECS};
+    use surrealdb::sql::Datetime;
+
+    let event = resolve_event(event_id).await?;
+    let query = "
+        LET $moved = array::distinct((SELECT VALUE item_id FROM type::table($history)
+            WHERE changed_at > time::now() - type::duration($reach)));
+        LET $items = SELECT * FROM type::table($items)
+            WHERE ($event = NONE OR event = $event)
+            AND (record::id(id) IN $moved OR status NOT IN $closed);
+        RETURN $items;
+        SELECT * FROM type::table($history)
+            WHERE item_id IN (SELECT VALUE record::id(id) FROM $items)
+            ORDER BY changed_at;
+    ";
+    let mut response = DB
+        .query(query)
+        .bind(("history", STATUS_HISTORY))
+        .bind(("items", ITEMS))
+        .bind(("reach", format!("{}s", WINDOW_SECS * WINDOW_COUNT as i64)))
+        .bind(("event", event))
+        .bind(("closed", vec![types::OrderStatus::Completed, types::OrderStatus::Cancelled]))
+        .await?;
+
+    let items: Vec<Item> = response.take(2)?;
+    let history: Vec<StatusChange> = response.take(3)?;
+    let items: Vec<types::Item> = items.into_iter().map(Into::into).collect();
+    let history: Vec<types::ItemStatusChange> = history.into_iter().map(Into::into).collect();
+    let stations = get_stations().await?;
+    let products = get_products().await?;
+
+    Ok(compute(&stations, &products, &items, &history, Datetime::default().to_secs()))
+}
+
+/// Throughput, prep times and backlog of one station over the last window, the active event by default.
+/// Only the history of that station and window is read, so the cost does not grow with the event
+#[server(GetRecentStationMetrics, "/api/metrics")]
+pub async fn get_recent_station_metrics(
+    station_id: String,
+    event_id: Option<String>,
+) -> Result<types::RecentStationMetrics, ServerFnError> {
+    use crate::backend::db::DB;
+    use crate::backend::event::ssr::resolve_event;
+    use crate::backend::item::ssr::ITEMS;
+    use crate::backend::station::ssr::STATIONS;
+    use crate::backend::status_history::ssr::STATUS_HISTORY;
+    use crate::common::metrics::{percentile, WINDOW_SECS};
+    use leptos::server_fn::error::ServerFnError::ServerError;
+    use serde::Deserialize;
+    use std::collections::HashMap;
+    use surrealdb::sql::Datetime;
+
+    /// Earliest change of an item into one of the statuses asked for
+    #[derive(Deserialize)]
+    struct FirstChange {
+        item_id: String,
+        at: Datetime,
+    }
+
+    let event = resolve_event(event_id).await?;
+    // Items the station moved on within the window count once, however often they were recalled
+    let query = "
+        LET $station = (SELECT * FROM type::thing($stations, $station_id))[0];
+        RETURN $station != NONE;
+        LET $bumped = array::distinct((SELECT VALUE type::thing($items, item_id) FROM type::table($history)
+            WHERE station_id = $station_id
+            AND `to` = $station.output_status
+            AND changed_at > time::now() - type::duration($window)));
+        LET $done = SELECT record::id(id) AS key, quantity FROM $bumped WHERE $event = NONE OR event = $event;
+        RETURN math::sum($done.quantity);
+        RETURN math::sum((SELECT VALUE quantity FROM type::table($items)
+            WHERE status IN $station.input_statuses
+            AND ($station.expo OR category_id IN $station.category_ids)
+            AND ($event = NONE OR event = $event)));
+        SELECT item_id, time::min(changed_at) AS at FROM type::table($history)
+            WHERE item_id IN $done.key AND `to` = $ordered
+            GROUP BY item_id;
+        SELECT item_id, time::min(changed_at) AS at FROM type::table($history)
+            WHERE item_id IN $done.key AND `to` IN $finished
+            GROUP BY item_id;
+    ";
+    let mut response = DB
+        .query(query)
+        .bind(("stations", STATIONS))
+        .bind(("station_id", station_id))
+        .bind(("items", ITEMS))
+        .bind(("history", STATUS_HISTORY))
+        .bind(("window", format!("{}s", WINDOW_SECS)))
+        .bind(("event", event))
+        .bind(("ordered", types::OrderStatus::Ordered))
+        .bind(("finished", vec![types::OrderStatus::Ready, types::OrderStatus::Completed]))
+        .await?;
+
+    let found: Option<bool> = response.take(1)?;
+    if found != Some(true) {
+        return Err(ServerError("Station not found".into()));
+    }
+    let processed: Option<u32> = response.take(4)?;
+    let backlog: Option<u32> = response.take(5)?;
+    let ordered: Vec<FirstChange> = response.take(6)?;
+    let finished: Vec<FirstChange> = response.take(7)?;
+
+    let ordered_at: HashMap<String, i64> = ordered
+        .into_iter()
+        .map(|change| (change.item_id, change.at.to_secs()))
+        .collect();
+    let mut waits: Vec<i64> = finished
+        .into_iter()
+        .filter_map(|change| Some((change.at.to_secs() - ordered_at.get(&change.item_id)?).max(0)))
+        .collect();
+    waits.sort_unstable();
+
+    Ok(types::RecentStationMetrics {
+        processed: processed.unwrap_or_default(),
+        median_wait_secs: percentile(&waits, 50),
+        p90_wait_secs: percentile(&waits, 90),
+        backlog: backlog.unwrap_or_default(),
+    })
+}
@@ -7,11 +7,17 @@ pub mod auth;
 #[cfg(feature = "ssr")]
 pub mod websocket;
 
+pub mod alert;
 pub mod category;
 pub mod event;
 pub mod item;
+pub mod metrics;
 pub mod order;
+pub mod printing;
 pub mod product;
+pub mod receipt;
 pub mod settings;
 pub mod station;
+pub mod status_history;
+pub mod tracking;
 pub mod user;
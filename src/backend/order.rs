@@ -20,23 +20,74 @@ pub mod ssr {
         #[validate(length(min = 1))]
         pub event: String,
         pub created_at: Datetime,
+        #[serde(default)]
+        pub rush: bool,
+        #[serde(default)]
+        pub name: String,
+        #[serde(default)]
+        pub number: u32,
+        #[serde(default)]
+        pub payment_method: types::PaymentMethod,
     }
 
     impl From<Order> for types::Order {
         fn from(record: Order) -> Self {
             Self {
                 id: record.id.unwrap().key().to_string(),
+                created_at: record.created_at.to_secs(),
+                rush: record.rush,
+                name: record.name,
+                number: record.number,
+                payment_method: record.payment_method,
             }
         }
     }
+
+    /// Held while an order is named and its items are created, so concurrent orders can't pick the same name
+    pub static NAMING: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+    /// Pickup names of the event's orders that still have items to hand out
+    pub async fn open_order_names(event: &str) -> Result<Vec<String>, leptos::prelude::ServerFnError> {
+        let query = "
+            SELECT VALUE name FROM type::table($table)
+                WHERE event = $event AND name != NONE AND meta::id(id) IN (
+                    SELECT VALUE order_id FROM items WHERE event = $event AND status NOT IN $terminal
+                )
+        ";
+        let mut response = DB
+            .query(query)
+            .bind(("table", ORDERS))
+            .bind(("event", event.to_string()))
+            .bind(("terminal", [types::OrderStatus::Completed, types::OrderStatus::Cancelled]))
+            .await?;
+        Ok(response.take(0)?)
+    }
 }
 #[cfg(feature = "ssr")]
 use ssr::*;
 
 #[server(CreateOrder, "/api/order")]
 pub async fn create_order(req: requests::order::Create) -> Result<types::Order, ServerFnError> {
+    use crate::backend::event::{get_event, ssr::{ensure_accepts_orders, next_order_number}};
     use crate::backend::item::ssr::create_items;
-    use crate::backend::websocket::broadcast_add;
+    use crate::backend::websocket::broadcast_to_event;
+    use crate::common::german_names::unique_name;
+    use crate::common::resource_type::Message;
+
+    ensure_accepts_orders(&req.event).await?;
+    // Products the event doesn't sell are refused before anything is created
+    let event = get_event(req.event.clone()).await?;
+    let off_menu = req.items.iter().find(|item| {
+        !event.menu.is_empty() && !event.menu.iter().any(|entry| entry.product_id == item.product_id)
+    });
+    if let Some(item) = off_menu {
+        return Err(ServerError(format!("Product {} is not on the menu of this event", item.product_id)));
+    }
+
+    let _naming = NAMING.lock().await;
+    let name_pool = event.name_pool;
+    let taken = open_order_names(&req.event).await?;
+    let number = next_order_number(&req.event).await?;
     
     // First create the order
     let order: Option<Order> = DB.create(ORDERS)
@@ -44,32 +95,53 @@ pub async fn create_order(req: requests::order::Create) -> Result<types::Order,
             id: None,
             event: req.event,
             created_at: Datetime::default(),
+            rush: req.rush,
+            name: String::new(),
+            number,
+            payment_method: req.payment_method,
         })
         .await?;
     
     if order.is_none() {
         return Err(ServerError("Failed to create order".to_string().into()));
     }
-    let order = order.unwrap();
-    let order_id = order.id.as_ref().unwrap().key().to_string();
+    let order_id = order.as_ref().unwrap().id.as_ref().unwrap().key().to_string();
+
+    // The name is derived from the new id, so it can only be assigned once the order exists
+    let order: Option<Order> = DB
+        .update((ORDERS, order_id.as_str()))
+        .merge(serde_json::json!({ "name": unique_name(name_pool, &order_id, &taken) }))
+        .await?;
+    let Some(order) = order else {
+        return Err(ServerError("Failed to name order".to_string()));
+    };
     
     // Convert to types::Order for broadcasting
     let order_type: types::Order = order.clone().into();
     
     // Broadcast the new order
-    broadcast_add(order_type.clone());
+    broadcast_to_event(Some(&order.event), Message::Add(order_type.clone()));
     
     // Then create all the items
     if !req.items.is_empty() {
-        create_items(order_id, req.items).await?;
+        create_items(order_id, order.event.clone(), req.items).await?;
     }
+    crate::backend::alert::ssr::schedule_evaluation();
     
     Ok(order_type)
 }
 
 #[server(GetOrders, "/api/order")]
-pub async fn get_orders() -> Result<Vec<types::Order>, ServerFnError> {
-    let orders: Vec<Order> = DB.select(ORDERS).await?;
+pub async fn get_orders(event_id: Option<String>) -> Result<Vec<types::Order>, ServerFnError> {
+    use crate::backend::event::ssr::resolve_event;
+
+    let event = resolve_event(event_id).await?;
+    let mut response = DB
+        .query("SELECT * FROM type::table($table) WHERE $event = NONE OR event = $event")
+        .bind(("table", ORDERS))
+        .bind(("event", event))
+        .await?;
+    let orders: Vec<Order> = response.take(0)?;
     Ok(orders.into_iter().map(Into::into).collect())
 }
 
@@ -83,15 +155,17 @@ pub async fn get_order(id: String) -> Result<types::Order, ServerFnError> {
 
 #[server(DeleteOrder, "/api/order")]
 pub async fn delete_order(id: String) -> Result<(), ServerFnError> {
-    use crate::backend::websocket::broadcast_delete;
+    use crate::backend::event::ssr::ensure_unlocked;
+    use crate::backend::websocket::broadcast_delete_in;
     
-    let deleted: Option<Order> = DB.delete((ORDERS, &id)).await?;
-    if deleted.is_none() {
+    let order: Option<Order> = DB.select((ORDERS, &id)).await?;
+    ensure_unlocked(order.as_ref().map(|order| order.event.as_str())).await?;
+    let Some(deleted) = DB.delete::<Option<Order>>((ORDERS, &id)).await? else {
         return Err(ServerError(format!("Order with id {} not found", id)));
-    }
+    };
     
     // Broadcast the order deletion
-    broadcast_delete::<types::Order>(id);
+    broadcast_delete_in::<types::Order>(Some(&deleted.event), Some(&id), id.clone());
     
     Ok(())
 }
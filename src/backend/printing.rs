@@ -0,0 +1,574 @@
+use std::collections::HashSet;
+use std::io::Write;
+use std::net::{TcpStream, ToSocketAddrs};
+use std::time::Duration;
+
+use crate::common::german_names::pickup_name;
+use crate::common::receipt::Receipt;
+use crate::common::types;
+
+/// Port of raw ESC/POS network printers (JetDirect)
+pub const DEFAULT_PRINTER_PORT: u16 = 9100;
+/// How long connecting to or writing to a printer may take before the attempt counts as failed
+pub const PRINTER_TIMEOUT_SECS: u64 = 3;
+/// Characters per line on 80mm paper with the default font
+const LINE_WIDTH: usize = 48;
+
+const ESC: u8 = 0x1B;
+const GS: u8 = 0x1D;
+
+/// Everything printed on a kitchen ticket for the items of one order at one station
+#[derive(Debug, Clone)]
+pub struct Ticket {
+    pub station_name: String,
+    pub order_name: String,
+    pub number: String,            // e.g. `#042`
+    pub items: Vec<(u32, String)>, // Quantity and product name
+    pub notes: Vec<String>,
+    pub time: String, // e.g. `18:42`
+}
+
+/// Ticket for items of one order that arrived at a station, `order` is `None` while it is unknown
+pub fn ticket(
+    station: &types::Station,
+    order_id: &str,
+    order: Option<&types::Order>,
+    items: &[types::Item],
+    products: &[types::Product],
+    time: String,
+) -> Ticket {
+    Ticket {
+        station_name: station.name.clone(),
+        order_name: pickup_name(order_id, order),
+        number: order.map(|order| order.number_label()).unwrap_or_default(),
+        items: items
+            .iter()
+            .map(|item| {
+                let name = products
+                    .iter()
+                    .find(|p| p.id == item.product_id)
+                    .map(|p| p.name.clone())
+                    .unwrap_or_default();
+                (item.quantity, name)
+            })
+            .collect(),
+        notes: if order.is_some_and(|order| order.rush) { vec!["RUSH".to_string()] } else { Vec::new() },
+        time,
+    }
+}
+
+/// Remembers which items were printed at which station, so every arrival is printed exactly once
+#[derive(Debug, Default)]
+pub struct PrintTracker {
+    printed: HashSet<(String, String)>, // Station and item, until the item leaves the station's input statuses
+}
+
+impl PrintTracker {
+    /// Whether the item just entered one of the station's input statuses and needs a ticket
+    pub fn entered(&mut self, station: &types::Station, item: &types::Item) -> bool {
+        let key = (station.id.clone(), item.id.clone());
+        if station.input_statuses.contains(&item.status) {
+            self.printed.insert(key)
+        } else {
+            self.printed.remove(&key);
+            false
+        }
+    }
+
+    /// Forgets the item at every station, once it was deleted or finished and never comes back
+    pub fn forget(&mut self, item_id: &str) {
+        self.printed.retain(|(_, id)| id != item_id);
+    }
+}
+
+/// Encodes text for code page 858, which covers German umlauts and the euro sign
+fn encode(text: &str) -> Vec<u8> {
+    text.chars()
+        .map(|c| match c {
+            c if c.is_ascii() => c as u8,
+            'ä' => 0x84,
+            'ö' => 0x94,
+            'ü' => 0x81,
+            'Ä' => 0x8E,
+            'Ö' => 0x99,
+            'Ü' => 0x9A,
+            'ß' => 0xE1,
+            '€' => 0xD5,
+            _ => b'?',
+        })
+        .collect()
+}
+
+/// Renders a ticket as ESC/POS commands, ending with a paper cut
+pub fn render_ticket(ticket: &Ticket) -> Vec<u8> {
+    let mut out = Vec::new();
+    let separator = "-".repeat(LINE_WIDTH);
+
+    out.extend([ESC, b'@']); // Reset the printer
+    out.extend([ESC, b't', 19]); // Code page 858
+
+    // Header with the station, then the pickup name and number in large letters
+    out.extend([ESC, b'a', 1]); // Centered
+    out.extend(encode(&ticket.station_name));
+    out.push(b'\n');
+    out.extend([ESC, b'E', 1, GS, b'!', 0x11]); // Bold, double width and height
+    out.extend(encode(&ticket.order_name));
+    out.push(b'\n');
+    out.extend(encode(&ticket.number));
+    out.push(b'\n');
+    out.extend([GS, b'!', 0x00, ESC, b'E', 0]);
+    out.extend(encode(&ticket.time));
+    out.push(b'\n');
+
+    out.extend([ESC, b'a', 0]); // Left aligned
+    out.extend(encode(&separator));
+    out.push(b'\n');
+    out.extend([GS, b'!', 0x01]); // Double height keeps items readable from a distance
+    for (quantity, name) in &ticket.items {
+        out.extend(encode(&format!("{}x {}", quantity, name)));
+        out.push(b'\n');
+    }
+    out.extend([GS, b'!', 0x00]);
+
+    if !ticket.notes.is_empty() {
+        out.extend(encode(&separator));
+        out.push(b'\n');
+        out.extend([ESC, b'E', 1]);
+        for note in &ticket.notes {
+            out.extend(encode(note));
+            out.push(b'\n');
+        }
+        out.extend([ESC, b'E', 0]);
+    }
+
+    out.extend([ESC, b'd', 4]); // Feed past the cutter
+    out.extend([GS, b'V', 66, 0]); // Partial cut
+    out
+}
+
+/// A line with text on the left and an amount on the right edge
+fn columns(left: &str, right: &str) -> String {
+    let width = LINE_WIDTH.saturating_sub(right.chars().count() + 1);
+    let left: String = left.chars().take(width).collect();
+    format!("{:<width$} {}", left, right, width = width)
+}
+
+/// Prints the data as a QR code with the printer's built-in generator
+fn qr_code(out: &mut Vec<u8>, data: &str) {
+    let data = data.as_bytes();
+    let length = data.len() + 3;
+    out.extend([GS, b'(', b'k', 4, 0, 49, 65, 50, 0]); // Model 2
+    out.extend([GS, b'(', b'k', 3, 0, 49, 67, 6]); // Module size
+    out.extend([GS, b'(', b'k', 3, 0, 49, 69, 49]); // Error correction level M
+    out.extend([GS, b'(', b'k', (length % 256) as u8, (length / 256) as u8, 49, 80, 48]);
+    out.extend(data);
+    out.extend([GS, b'(', b'k', 3, 0, 49, 81, 48]); // Print the stored code
+}
+
+fn line(out: &mut Vec<u8>, text: &str) {
+    out.extend(encode(text));
+    out.push(b'\n');
+}
+
+/// Renders a customer receipt as ESC/POS commands, ending with a paper cut
+pub fn render_receipt(receipt: &Receipt) -> Vec<u8> {
+    let mut out = Vec::new();
+    let separator = "-".repeat(LINE_WIDTH);
+
+    out.extend([ESC, b'@']);
+    out.extend([ESC, b't', 19]);
+
+    out.extend([ESC, b'a', 1, ESC, b'E', 1]);
+    line(&mut out, &receipt.event_name);
+    out.extend([GS, b'!', 0x11]);
+    line(&mut out, &format!("{} {}", receipt.number, receipt.order_name));
+    out.extend([GS, b'!', 0x00, ESC, b'E', 0]);
+    line(&mut out, &receipt.date);
+
+    out.extend([ESC, b'a', 0]);
+    line(&mut out, &separator);
+    for item in &receipt.lines {
+        line(&mut out, &columns(&format!("{}x {}", item.quantity, item.name), &format!("{:.2} €", item.total())));
+    }
+    line(&mut out, &separator);
+    out.extend([ESC, b'E', 1]);
+    line(&mut out, &columns("Total", &format!("{:.2} €", receipt.total())));
+    out.extend([ESC, b'E', 0]);
+    line(&mut out, &columns("Paid", receipt.payment_method.label()));
+
+    line(&mut out, &separator);
+    for tax in receipt.tax_breakdown() {
+        line(
+            &mut out,
+            &columns(&format!("VAT {}% of {:.2} € net", tax.rate, tax.net), &format!("{:.2} €", tax.tax)),
+        );
+    }
+
+    // Customers scan this to follow their order on their phone
+    out.extend([ESC, b'a', 1]);
+    out.push(b'\n');
+    line(&mut out, "Follow your order:");
+    qr_code(&mut out, &receipt.tracking_url);
+    out.push(b'\n');
+    line(&mut out, "Thank you!");
+
+    out.extend([ESC, b'd', 4]);
+    out.extend([GS, b'V', 66, 0]);
+    out
+}
+
+/// Address of a configured printer, adding the default port when only a host is given
+pub fn printer_address(printer: &str) -> String {
+    let printer = printer.trim();
+    if printer.rsplit_once(':').is_some_and(|(_, port)| port.parse::<u16>().is_ok()) {
+        printer.to_string()
+    } else {
+        format!("{}:{}", printer, DEFAULT_PRINTER_PORT)
+    }
+}
+
+/// Sends rendered ticket bytes to a printer over raw TCP
+pub fn send_ticket(address: &str, bytes: &[u8]) -> std::io::Result<()> {
+    let timeout = Duration::from_secs(PRINTER_TIMEOUT_SECS);
+    let address = address
+        .to_socket_addrs()?
+        .next()
+        .ok_or_else(|| std::io::Error::new(std::io::ErrorKind::NotFound, "Printer address did not resolve"))?;
+    let mut stream = TcpStream::connect_timeout(&address, timeout)?;
+    stream.set_write_timeout(Some(timeout))?;
+    stream.write_all(bytes)?;
+    stream.flush()
+}
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    use crate::backend::alert::ssr::printer_result;
+    use crate::common::resource_type::{GenericWebSocketMessage, Message, ResourceData, ResourceName};
+    use crate::common::types;
+    use leptos::logging::log;
+    use std::collections::HashMap;
+    use std::sync::{LazyLock, Mutex};
+    use std::time::Duration;
+
+    use super::{printer_address, render_ticket, send_ticket, ticket, PrintTracker};
+
+    /// Items of an order reaching a station shortly after each other end up on the same ticket
+    pub const BATCH_DELAY_MILLIS: u64 = 500;
+    /// Attempts per ticket before the printer is reported as failing
+    pub const PRINT_ATTEMPTS: u32 = 3;
+
+    // Station and order to the items waiting to be printed together
+    type Batches = HashMap<(String, String), Vec<types::Item>>;
+    static PENDING: LazyLock<Mutex<Batches>> =
+        LazyLock::new(|| Mutex::new(HashMap::new()));
+
+    /// Time of day printed on kitchen tickets, in the event time zone
+    pub fn ticket_time(secs: i64) -> String {
+        crate::backend::event::ssr::event_time(secs)
+            .map(|time| time.strftime("%H:%M").to_string())
+            .unwrap_or_default()
+    }
+
+    /// Sends bytes to a printer, retrying with a growing pause before giving up
+    pub async fn send_with_retry(printer: &str, bytes: Vec<u8>) -> bool {
+        let address = printer_address(printer);
+        for attempt in 1..=PRINT_ATTEMPTS {
+            let (address, bytes) = (address.clone(), bytes.clone());
+            match tokio::task::spawn_blocking(move || send_ticket(&address, &bytes)).await {
+                Ok(Ok(())) => return true,
+                Ok(Err(e)) => log!("Printing to {} failed (attempt {}): {}", printer, attempt, e),
+                Err(e) => log!("Printing to {} failed (attempt {}): {}", printer, attempt, e),
+            }
+            if attempt < PRINT_ATTEMPTS {
+                tokio::time::sleep(Duration::from_secs(u64::from(attempt))).await;
+            }
+        }
+        false
+    }
+
+    fn apply<T: serde::de::DeserializeOwned + ResourceData>(
+        map: &mut HashMap<String, T>,
+        message: serde_json::Value,
+        id: impl Fn(&T) -> String,
+    ) {
+        match serde_json::from_value::<Message<T>>(message) {
+            Ok(Message::Add(resource)) | Ok(Message::Update(resource)) => {
+                map.insert(id(&resource), resource);
+            }
+            Ok(Message::Delete(removed)) => {
+                map.remove(&removed);
+            }
+            Err(_) => {}
+        }
+    }
+
+    /// What the printer service knows about the stations and products, loaded once and then kept up to
+    /// date from the broadcast messages, like the agent does
+    #[derive(Default)]
+    struct PrinterState {
+        loaded: bool,
+        stations: HashMap<String, types::Station>,
+        products: HashMap<String, types::Product>,
+        tracker: PrintTracker,
+    }
+
+    impl PrinterState {
+        async fn load(&mut self) -> Result<(), leptos::prelude::ServerFnError> {
+            use crate::backend::product::get_products;
+            use crate::backend::station::get_stations;
+
+            self.stations = get_stations().await?.into_iter().map(|s| (s.id.clone(), s)).collect();
+            self.products = get_products().await?.into_iter().map(|p| (p.id.clone(), p)).collect();
+            self.loaded = true;
+            Ok(())
+        }
+
+        async fn handle(&mut self, generic: GenericWebSocketMessage) {
+            match generic.resource_type.as_str() {
+                types::Station::RESOURCE_NAME => apply(&mut self.stations, generic.message, |s| s.id.clone()),
+                types::Product::RESOURCE_NAME => apply(&mut self.products, generic.message, |p| p.id.clone()),
+                types::Item::RESOURCE_NAME => {
+                    let item = match serde_json::from_value::<Message<types::Item>>(generic.message) {
+                        Ok(Message::Add(item) | Message::Update(item)) => item,
+                        Ok(Message::Delete(id)) => {
+                            self.tracker.forget(&id);
+                            return;
+                        }
+                        Err(_) => return,
+                    };
+                    if !self.loaded {
+                        if let Err(e) = self.load().await {
+                            log!("Printer service failed to load stations and products: {}", e);
+                            return;
+                        }
+                    }
+                    self.item_changed(item);
+                }
+                _ => {}
+            }
+        }
+
+        fn item_changed(&mut self, item: types::Item) {
+            use crate::backend::websocket::served_by_agent;
+
+            let (Some(order_id), Some(product)) = (item.order_id.clone(), self.products.get(&item.product_id)) else {
+                return;
+            };
+            // Stations an agent serves get their tickets from the agent, printing here too would double them
+            let stations = self.stations.values().filter(|station| {
+                station.printer.is_some()
+                    && !served_by_agent(&station.id)
+                    && station.handles_category(&product.category_id)
+            });
+
+            for station in stations {
+                if !self.tracker.entered(station, &item) {
+                    continue;
+                }
+
+                let first_of_batch = {
+                    let mut pending = PENDING.lock().unwrap();
+                    let batch = pending.entry((station.id.clone(), order_id.clone())).or_default();
+                    batch.push(item.clone());
+                    batch.len() == 1
+                };
+                if first_of_batch {
+                    let station = station.clone();
+                    let order_id = order_id.clone();
+                    let products: Vec<types::Product> = self.products.values().cloned().collect();
+                    tokio::spawn(async move {
+                        tokio::time::sleep(Duration::from_millis(BATCH_DELAY_MILLIS)).await;
+                        if let Err(e) = print_batch(station, order_id, products).await {
+                            log!("Failed to print ticket: {}", e);
+                        }
+                    });
+                }
+            }
+            if item.status.is_terminal() {
+                self.tracker.forget(&item.id);
+            }
+        }
+    }
+
+    /// Prints tickets for items entering the input status of stations that have a printer
+    pub fn spawn_printer_service() {
+        let Some(mut receiver) = crate::backend::websocket::subscribe() else {
+            log!("Printer service not started, the WebSocket sender is not initialized");
+            return;
+        };
+        tokio::spawn(async move {
+            let mut state = PrinterState::default();
+            if let Err(e) = state.load().await {
+                log!("Printer service failed to load stations and products, retrying with the next item: {}", e);
+            }
+            loop {
+                let json = match receiver.recv().await {
+                    Ok(json) => json,
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(skipped)) => {
+                        // Missed station or product changes would go unnoticed, so start over from the database
+                        log!("Printer service skipped {} messages", skipped);
+                        state.loaded = false;
+                        continue;
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                };
+                if let Ok(generic) = serde_json::from_str::<GenericWebSocketMessage>(&json) {
+                    state.handle(generic).await;
+                }
+            }
+        });
+    }
+
+    async fn print_batch(
+        station: types::Station,
+        order_id: String,
+        products: Vec<types::Product>,
+    ) -> Result<(), leptos::prelude::ServerFnError> {
+        use crate::backend::order::get_order;
+
+        let items = PENDING
+            .lock()
+            .unwrap()
+            .remove(&(station.id.clone(), order_id.clone()))
+            .unwrap_or_default();
+        let Some(printer) = station.printer.clone().filter(|_| !items.is_empty()) else {
+            return Ok(());
+        };
+
+        let order = get_order(order_id.clone()).await?;
+        let ticket = ticket(&station, &order_id, Some(&order), &items, &products, ticket_time(order.created_at));
+
+        let printed = send_with_retry(&printer, render_ticket(&ticket)).await;
+        printer_result(&station.id, printed);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::net::TcpListener;
+
+    fn ticket() -> Ticket {
+        Ticket {
+            station_name: "Grill".to_string(),
+            order_name: "Jürgen".to_string(),
+            number: "#042".to_string(),
+            items: vec![(2, "Bratwurst".to_string()), (1, "Pommes groß".to_string())],
+            notes: vec!["RUSH".to_string()],
+            time: "18:42".to_string(),
+        }
+    }
+
+    #[test]
+    fn test_render_receipt() {
+        use crate::common::receipt::ReceiptLine;
+        use crate::common::types::PaymentMethod;
+
+        let receipt = Receipt {
+            event_name: "Sommerfest".to_string(),
+            order_id: "abc".to_string(),
+            order_name: "Igel".to_string(),
+            number: "#007".to_string(),
+            date: "18.10.2026 18:42".to_string(),
+            lines: vec![ReceiptLine { name: "Bratwurst".to_string(), quantity: 2, unit_price: 3.5, tax_rate: 7.0 }],
+            payment_method: PaymentMethod::Card,
+            tracking_url: "http://kasse/order/abc".to_string(),
+        };
+
+        let bytes = render_receipt(&receipt);
+
+        assert!(contains(&bytes, b"#007 Igel"));
+        assert!(contains(&bytes, b"2x Bratwurst"));
+        assert!(contains(&bytes, &[b'7', b'.', b'0', b'0', b' ', 0xD5]), "Amounts carry the euro sign");
+        assert!(contains(&bytes, b"Card"));
+        assert!(contains(&bytes, b"VAT 7% of 6.54"));
+        assert!(contains(&bytes, b"http://kasse/order/abc"), "The tracking link is stored as QR code");
+        assert!(bytes.ends_with(&[GS, b'V', 66, 0]));
+    }
+
+    fn contains(haystack: &[u8], needle: &[u8]) -> bool {
+        haystack.windows(needle.len()).any(|window| window == needle)
+    }
+
+    #[test]
+    fn test_render_ticket() {
+        let bytes = render_ticket(&ticket());
+
+        assert!(bytes.starts_with(&[ESC, b'@']), "Tickets start by resetting the printer");
+        assert!(bytes.ends_with(&[GS, b'V', 66, 0]), "Tickets end with a cut");
+        assert!(contains(&bytes, &[b'J', 0x81, b'r', b'g', b'e', b'n']), "Umlauts use code page 858");
+        assert!(contains(&bytes, b"#042"));
+        assert!(contains(&bytes, b"2x Bratwurst"));
+        assert!(contains(&bytes, &[b'g', b'r', b'o', 0xE1]));
+        assert!(contains(&bytes, b"RUSH"));
+        assert!(contains(&bytes, b"18:42"));
+    }
+
+    #[test]
+    fn test_print_tracker_prints_each_arrival_once() {
+        let station = types::Station {
+            id: "grill".to_string(),
+            name: "Grill".to_string(),
+            category_ids: vec!["food".to_string()],
+            input_statuses: vec![types::OrderStatus::Ordered, types::OrderStatus::Custom("Grill".to_string())],
+            ..Default::default()
+        };
+        let mut item = types::Item {
+            id: "wurst".to_string(),
+            order_id: Some("order".to_string()),
+            product_id: "bratwurst".to_string(),
+            quantity: 1,
+            price: 3.5,
+            status: types::OrderStatus::Ordered,
+            event: None,
+        };
+        let mut tracker = PrintTracker::default();
+
+        assert!(tracker.entered(&station, &item));
+        assert!(!tracker.entered(&station, &item), "Repeated updates are not printed again");
+        item.status = types::OrderStatus::Custom("Grill".to_string());
+        assert!(!tracker.entered(&station, &item), "Moving between input statuses is no new arrival");
+        item.status = types::OrderStatus::Ready;
+        assert!(!tracker.entered(&station, &item));
+        item.status = types::OrderStatus::Ordered;
+        assert!(tracker.entered(&station, &item), "Items sent back are printed again");
+
+        tracker.forget(&item.id);
+        assert!(tracker.printed.is_empty(), "Deleted and finished items are not remembered");
+    }
+
+    #[test]
+    fn test_printer_address() {
+        assert_eq!(printer_address("192.168.1.50"), "192.168.1.50:9100");
+        assert_eq!(printer_address(" 192.168.1.50:9101 "), "192.168.1.50:9101");
+        assert_eq!(printer_address("kitchen-printer"), "kitchen-printer:9100");
+    }
+
+    #[test]
+    fn test_send_ticket_to_stand_in_printer() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let printer = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut received = Vec::new();
+            stream.read_to_end(&mut received).unwrap();
+            received
+        });
+
+        let bytes = render_ticket(&ticket());
+        send_ticket(&address, &bytes).unwrap();
+
+        assert_eq!(printer.join().unwrap(), bytes, "The printer receives the rendered ticket unchanged");
+    }
+
+    #[test]
+    fn test_send_ticket_fails_without_printer() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        drop(listener);
+
+        assert!(send_ticket(&address, &render_ticket(&ticket())).is_err());
+    }
+}
@@ -23,6 +23,7 @@ pub mod ssr {
         #[validate(range(min = 0.0))]
         pub price: f64,
         pub active: bool,
+        pub stock: Option<u32>,
     }
 
     impl From<Product> for types::Product {
@@ -33,9 +34,26 @@ pub mod ssr {
                 category_id: record.category_id,
                 price: record.price,
                 active: record.active,
+                stock: record.stock,
             }
         }
     }
+
+    /// Reduces the stock of a tracked product, never going below zero
+    pub async fn consume_stock(id: &str, quantity: u32) -> Result<(), leptos::prelude::ServerFnError> {
+        let query = "UPDATE type::thing($table, $id) SET stock = math::max([stock - $quantity, 0]) WHERE stock != NONE";
+        let mut response = DB
+            .query(query)
+            .bind(("table", PRODUCTS))
+            .bind(("id", id.to_string()))
+            .bind(("quantity", quantity))
+            .await?;
+        let updated: Vec<Product> = response.take(0)?;
+        for product in updated {
+            broadcast_update::<types::Product>(product.into());
+        }
+        Ok(())
+    }
 }
 #[cfg(feature = "ssr")]
 use ssr::*;
@@ -52,6 +70,7 @@ pub async fn create_product(
             category_id: req.category_id,
             price: req.price,
             active: true,
+            stock: req.stock,
         })
         .await?;
     if let Some(product) = p {
@@ -88,12 +107,17 @@ pub async fn update_product(
         return Err(ServerError("Product not found".into()));
     }
     let product = existing_product.unwrap();
+    let category_changed = update
+        .category_id
+        .as_ref()
+        .is_some_and(|category_id| *category_id != product.category_id);
     let updated = Product {
         id: product.id,
         name: update.name.or_else(|| Some(product.name)).unwrap(),
         category_id: update.category_id.or_else(|| Some(product.category_id)).unwrap(),
         price: update.price.or_else(|| Some(product.price)).unwrap(),
         active: update.active.or_else(|| Some(product.active)).unwrap(),
+        stock: update.stock.unwrap_or(product.stock),
     };
     // Update the product in the database
     let updated_product: Option<Product> = DB
@@ -103,7 +127,23 @@ pub async fn update_product(
 
     if let Some(product) = updated_product {
         let result: types::Product = product.into();
+        if category_changed {
+            // Items carry their product's category for station queries, items of closed events stay as
+            // they were for reporting
+            let query = "
+                UPDATE items SET category_id = $category_id
+                    WHERE product_id = $product_id
+                    AND event NOT IN (SELECT VALUE record::id(id) FROM events WHERE state IN $locked)
+            ";
+            DB.query(query)
+                .bind(("category_id", result.category_id.clone()))
+                .bind(("product_id", result.id.clone()))
+                .bind(("locked", [types::EventState::Closed, types::EventState::Archived]))
+                .await?
+                .check()?;
+        }
         broadcast_update(result.clone());
+        crate::backend::alert::ssr::schedule_evaluation();
         Ok(result)
     } else {
         Err(ServerError("Failed to update product".into()))
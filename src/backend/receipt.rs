@@ -0,0 +1,75 @@
+use leptos::prelude::*;
+
+use crate::common::receipt::Receipt;
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    pub use crate::backend::db::DB;
+    pub use leptos::server_fn::error::ServerFnError::ServerError;
+
+    /// Network printer customer receipts are sent to, e.g. `192.168.1.60` or `192.168.1.60:9100`
+    pub fn receipt_printer() -> Option<String> {
+        std::env::var("RECEIPT_PRINTER")
+            .ok()
+            .map(|printer| printer.trim().to_string())
+            .filter(|printer| !printer.is_empty())
+    }
+}
+#[cfg(feature = "ssr")]
+use ssr::*;
+
+/// Receipt of an order with its lines, VAT and tracking link, for the print view and the receipt printer
+#[server(GetReceipt, "/api/receipt")]
+pub async fn get_receipt(order_id: String) -> Result<Receipt, ServerFnError> {
+    use crate::backend::category::get_categories;
+    use crate::backend::event::get_event;
+    use crate::backend::event::ssr::event_time;
+    use crate::backend::item::get_items_by_order;
+    use crate::backend::order::ssr::{Order, ORDERS};
+    use crate::backend::product::get_products;
+    use crate::backend::tracking::ssr::tracking_url;
+    use crate::common::german_names::pickup_name;
+    use crate::common::receipt::receipt_lines;
+    use crate::common::types;
+
+    let record: Option<Order> = DB.select((ORDERS, &order_id)).await?;
+    let Some(record) = record else {
+        return Err(ServerError("Order not found".to_string()));
+    };
+    let event_name = get_event(record.event.clone()).await.map(|e| e.name).unwrap_or_default();
+    let order: types::Order = record.into();
+
+    let products = get_products().await?;
+    let categories = get_categories().await?;
+    let items = get_items_by_order(order_id.clone()).await?;
+    let lines = receipt_lines(&items, &products, &categories);
+
+    Ok(Receipt {
+        event_name,
+        order_name: pickup_name(&order_id, Some(&order)),
+        number: order.number_label(),
+        date: event_time(order.created_at)
+            .map(|time| time.strftime("%d.%m.%Y %H:%M").to_string())
+            .unwrap_or_default(),
+        lines,
+        payment_method: order.payment_method,
+        tracking_url: tracking_url(&order_id).await?,
+        order_id,
+    })
+}
+
+/// Sends the receipt of an order to the receipt printer configured with `RECEIPT_PRINTER`
+#[server(PrintReceipt, "/api/receipt")]
+pub async fn print_receipt(order_id: String) -> Result<(), ServerFnError> {
+    use crate::backend::printing::{render_receipt, ssr::send_with_retry};
+
+    let Some(printer) = receipt_printer() else {
+        return Err(ServerError("No receipt printer configured".to_string()));
+    };
+    let receipt = get_receipt(order_id).await?;
+    if send_with_retry(&printer, render_receipt(&receipt)).await {
+        Ok(())
+    } else {
+        Err(ServerError(format!("Receipt printer {} is not reachable", printer)))
+    }
+}
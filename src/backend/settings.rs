@@ -19,14 +19,21 @@ pub mod ssr {
     #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
     pub struct Settings {
         pub id: Option<RecordId>,
+        #[serde(default)]
+        pub active_event_ids: Vec<String>,
+        // Single active event from before multi-booth mode, moved into `active_event_ids` on first read
+        #[serde(default, skip_serializing_if = "Option::is_none")]
         pub active_event_id: Option<String>,
+        #[serde(default)]
+        pub alert_rules: types::AlertRules,
     }
 
     impl From<Settings> for types::Settings {
         fn from(settings: Settings) -> Self {
             Self {
                 id: settings.id.unwrap().key().to_string(),
-                active_event_id: settings.active_event_id,
+                active_event_ids: settings.active_event_ids,
+                alert_rules: settings.alert_rules,
             }
         }
     }
@@ -36,10 +43,29 @@ use ssr::*;
 
 #[server(GetSettings, "/api/settings")]
 pub async fn get_settings() -> Result<types::Settings, ServerFnError> {
+    let settings = load_settings().await?;
+    crate::backend::websocket::set_active_event_count(settings.active_event_ids.len());
+    Ok(settings)
+}
+
+#[cfg(feature = "ssr")]
+async fn load_settings() -> Result<types::Settings, ServerFnError> {
     // Try to get existing settings
     let existing: Option<Settings> = DB.select((SETTINGS, SETTINGS_ID)).await?;
     
-    if let Some(settings) = existing {
+    if let Some(mut settings) = existing {
+        if let Some(legacy) = settings.active_event_id.take() {
+            if !settings.active_event_ids.contains(&legacy) {
+                settings.active_event_ids.insert(0, legacy);
+            }
+            let migrated: Option<Settings> = DB
+                .update((SETTINGS, SETTINGS_ID))
+                .content(Settings { id: None, ..settings.clone() })
+                .await?;
+            return migrated
+                .map(Into::into)
+                .ok_or_else(|| ServerError("Failed to migrate settings".into()));
+        }
         Ok(settings.into())
     } else {
         // Create default settings if they don't exist
@@ -47,7 +73,9 @@ pub async fn get_settings() -> Result<types::Settings, ServerFnError> {
             .create((SETTINGS, SETTINGS_ID))
             .content(Settings {
                 id: None,
+                active_event_ids: Vec::new(),
                 active_event_id: None,
+                alert_rules: types::AlertRules::default(),
             })
             .await?;
             
@@ -66,7 +94,9 @@ pub async fn update_settings(
     
     let updated = Settings {
         id: None, // Will be ignored by SurrealDB for updates
-        active_event_id: update.active_event_id.or(current_settings.active_event_id),
+        active_event_ids: update.active_event_ids.unwrap_or(current_settings.active_event_ids),
+        active_event_id: None,
+        alert_rules: update.alert_rules.unwrap_or(current_settings.alert_rules),
     };
     
     // Update the settings in the database
@@ -77,23 +107,56 @@ pub async fn update_settings(
     
     if let Some(settings) = updated_settings {
         let result: types::Settings = settings.clone().into();
+        crate::backend::websocket::set_active_event_count(result.active_event_ids.len());
         broadcast_update(result.clone());
+        crate::backend::alert::ssr::schedule_evaluation();
         Ok(result)
     } else {
         Err(ServerError("Failed to update settings".into()))
     }
 }
 
-#[server(SetActiveEvent, "/api/settings")]
-pub async fn set_active_event(event_id: String) -> Result<types::Settings, ServerFnError> {
+/// Makes an event active next to the ones already active, e.g. a second booth at a festival
+#[server(ActivateEvent, "/api/settings")]
+pub async fn activate_event(event_id: String) -> Result<types::Settings, ServerFnError> {
+    let mut active_event_ids = get_settings().await?.active_event_ids;
+    if !active_event_ids.contains(&event_id) {
+        active_event_ids.push(event_id);
+    }
     let update = requests::settings::Update {
-        active_event_id: Some(event_id),
+        active_event_ids: Some(active_event_ids),
+        alert_rules: None,
     };
     update_settings(update).await
 }
 
+#[server(DeactivateEvent, "/api/settings")]
+pub async fn deactivate_event(event_id: String) -> Result<types::Settings, ServerFnError> {
+    let mut active_event_ids = get_settings().await?.active_event_ids;
+    active_event_ids.retain(|id| *id != event_id);
+    let update = requests::settings::Update {
+        active_event_ids: Some(active_event_ids),
+        alert_rules: None,
+    };
+    update_settings(update).await
+}
+
+#[server(SetAlertRules, "/api/settings")]
+pub async fn set_alert_rules(alert_rules: types::AlertRules) -> Result<types::Settings, ServerFnError> {
+    let update = requests::settings::Update {
+        active_event_ids: None,
+        alert_rules: Some(alert_rules),
+    };
+    update_settings(update).await
+}
+
+/// The event for callers that don't name one, the only active event. Fails while several events
+/// are active, since any of them could be meant.
 #[server(GetActiveEvent, "/api/settings")]
 pub async fn get_active_event() -> Result<Option<String>, ServerFnError> {
     let settings = get_settings().await?;
-    Ok(settings.active_event_id)
+    if settings.active_event_ids.len() > 1 {
+        return Err(ServerError("Several events are active, choose the event of this device".into()));
+    }
+    Ok(settings.active_event_ids.first().cloned())
 }
\ No newline at end of file
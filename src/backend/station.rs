@@ -1,3 +1,6 @@
+// Station server functions take one argument per form field
+#![allow(clippy::too_many_arguments)]
+
 use leptos::prelude::*;
 
 use crate::common::types;
@@ -22,6 +25,12 @@ pub mod ssr {
         pub category_ids: Vec<String>,
         pub input_statuses: Vec<types::OrderStatus>,
         pub output_status: types::OrderStatus,
+        #[serde(default)]
+        pub sla: types::SlaThresholds,
+        #[serde(default)]
+        pub expo: bool,
+        #[serde(default)]
+        pub printer: Option<String>,
     }
 
     impl From<Station> for types::Station {
@@ -32,9 +41,61 @@ pub mod ssr {
                 category_ids: station.category_ids,
                 input_statuses: station.input_statuses,
                 output_status: station.output_status,
+                sla: station.sla,
+                expo: station.expo,
+                printer: station.printer,
+            }
+        }
+    }
+
+    /// Record of a station, the id is left to the database
+    impl From<types::Station> for Station {
+        fn from(station: types::Station) -> Self {
+            Self {
+                id: None,
+                name: station.name,
+                category_ids: station.category_ids,
+                input_statuses: station.input_statuses,
+                output_status: station.output_status,
+                sla: station.sla,
+                expo: station.expo,
+                printer: station.printer,
             }
         }
     }
+
+    /// Parses the SLA thresholds sent by the station forms, an empty value disables both
+    pub fn parse_sla(sla_json: &str) -> Result<types::SlaThresholds, leptos::prelude::ServerFnError> {
+        if sla_json.is_empty() {
+            return Ok(types::SlaThresholds::default());
+        }
+        serde_json::from_str(sla_json).map_err(|_| ServerError("Failed to parse sla thresholds".into()))
+    }
+
+    /// Rejects custom statuses that none of the active events define, stations are shared by all booths
+    pub async fn ensure_statuses_defined(
+        input_statuses: &[types::OrderStatus],
+        output_status: &types::OrderStatus,
+    ) -> Result<(), leptos::prelude::ServerFnError> {
+        use crate::backend::event::get_event;
+        use crate::backend::settings::get_settings;
+
+        let mut defined = Vec::new();
+        for event_id in get_settings().await?.active_event_ids {
+            defined.extend(get_event(event_id).await?.custom_statuses);
+        }
+        let unknown = input_statuses
+            .iter()
+            .chain(std::iter::once(output_status))
+            .find(|status| matches!(status, types::OrderStatus::Custom(name) if !defined.contains(name)));
+        match unknown {
+            Some(status) => Err(ServerError(format!(
+                "Status '{}' is not defined for any active event",
+                status
+            ))),
+            None => Ok(()),
+        }
+    }
 }
 #[cfg(feature = "ssr")]
 use ssr::*;
@@ -45,6 +106,9 @@ pub async fn create_station(
     category_ids_json: String,
     input_statuses_json: String,
     output_status: types::OrderStatus,
+    sla_json: String,
+    expo: bool,
+    printer: String,
 ) -> Result<types::Station, ServerFnError> {
     // Deserialize the JSON arrays
     let category_ids: Vec<String> = if category_ids_json.is_empty() {
@@ -69,6 +133,10 @@ pub async fn create_station(
         }
     };
     
+    ensure_statuses_defined(&input_statuses, &output_status).await?;
+    let sla = parse_sla(&sla_json)?;
+    let printer = Some(printer.trim().to_string()).filter(|printer| !printer.is_empty());
+
     let s: Option<Station> = DB.create(STATIONS)
         .content(Station {
             id: None,
@@ -76,11 +144,15 @@ pub async fn create_station(
             category_ids,
             input_statuses,
             output_status,
+            sla,
+            expo,
+            printer,
         })
         .await?;
     if let Some(station) = s {
         let result: types::Station = station.into();
         broadcast_add(result.clone());
+        crate::backend::alert::ssr::schedule_evaluation();
         Ok(result)
     } else {
         Err(ServerError("Failed to create station".into()))
@@ -108,6 +180,9 @@ pub async fn update_station(
     category_ids_json: String,
     input_statuses_json: String,
     output_status: types::OrderStatus,
+    sla_json: String,
+    expo: bool,
+    printer: String,
 ) -> Result<types::Station, ServerFnError> {
     
     // Deserialize the JSON arrays
@@ -133,6 +208,10 @@ pub async fn update_station(
         }
     };
     
+    ensure_statuses_defined(&input_statuses, &output_status).await?;
+    let sla = parse_sla(&sla_json)?;
+    let printer = Some(printer.trim().to_string()).filter(|printer| !printer.is_empty());
+
     // Get the existing station
     let existing_station: Option<Station> = DB.select((STATIONS, &id)).await?;
     if existing_station.is_none() {
@@ -145,6 +224,9 @@ pub async fn update_station(
         category_ids,
         input_statuses,
         output_status,
+        sla,
+        expo,
+        printer,
     };
     // Update the station in the database
     let updated_station: Option<Station> = DB
@@ -155,6 +237,7 @@ pub async fn update_station(
     if let Some(station) = updated_station {
         let result: types::Station = station.into();
         broadcast_update(result.clone());
+        crate::backend::alert::ssr::schedule_evaluation();
         Ok(result)
     } else {
         Err(ServerError("Failed to update station".into()))
@@ -168,5 +251,6 @@ pub async fn delete_station(id: String) -> Result<(), ServerFnError> {
         return Err(ServerError(format!("Station with id {} not found", id)));
     }
     broadcast_delete::<types::Station>(id);
+    crate::backend::alert::ssr::schedule_evaluation();
     Ok(())
 }
@@ -0,0 +1,252 @@
+use leptos::prelude::*;
+use std::collections::HashMap;
+
+use crate::common::types;
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    pub use crate::backend::db::DB;
+    pub use crate::common::types;
+    pub use serde::{Deserialize, Serialize};
+    pub use surrealdb::sql::Datetime;
+    use surrealdb::RecordId;
+    pub use validator::Validate;
+
+    pub const STATUS_HISTORY: &str = "item_status_history";
+
+    #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+    pub struct StatusChange {
+        pub id: Option<RecordId>,
+        pub item_id: String,
+        pub order_id: String,
+        pub from: Option<types::OrderStatus>,
+        pub to: types::OrderStatus,
+        pub changed_at: Datetime,
+        pub user_id: Option<String>,
+        pub station_id: Option<String>,
+    }
+
+    impl From<StatusChange> for types::ItemStatusChange {
+        fn from(record: StatusChange) -> Self {
+            Self {
+                id: record.id.unwrap().key().to_string(),
+                item_id: record.item_id,
+                order_id: record.order_id,
+                from: record.from,
+                to: record.to,
+                changed_at: record.changed_at.to_secs(),
+                user_id: record.user_id,
+                station_id: record.station_id,
+            }
+        }
+    }
+
+    impl StatusChange {
+        pub fn new(
+            item_id: String,
+            order_id: String,
+            from: Option<types::OrderStatus>,
+            to: types::OrderStatus,
+            station_id: Option<String>,
+        ) -> Self {
+            Self {
+                id: None,
+                item_id,
+                order_id,
+                from,
+                to,
+                changed_at: Datetime::default(),
+                user_id: None,
+                station_id,
+            }
+        }
+    }
+
+    /// Id of the signed in user making the current request, `None` outside of requests or when signed out
+    pub async fn current_user_id() -> Option<String> {
+        let session: tower_sessions::Session = leptos_axum::extract().await.ok()?;
+        crate::backend::auth::get_authenticated_user(&session)
+            .await
+            .ok()
+            .map(|user| user.id)
+    }
+
+    /// Appends status transitions to the history, attributed to the current user
+    pub async fn record_changes(
+        mut changes: Vec<StatusChange>,
+    ) -> Result<(), leptos::prelude::ServerFnError> {
+        if changes.is_empty() {
+            return Ok(());
+        }
+        let user_id = current_user_id().await;
+        for change in &mut changes {
+            change.user_id = user_id.clone();
+        }
+        let _: Vec<StatusChange> = DB.insert(STATUS_HISTORY).content(changes).await?;
+        Ok(())
+    }
+
+    /// Gives an item split off another one the same timeline, so its prep time stays measurable
+    pub async fn copy_history(from_item_id: String, to_item_id: String) -> Result<(), leptos::prelude::ServerFnError> {
+        let mut response = DB
+            .query("SELECT * FROM type::table($table) WHERE item_id = $item_id")
+            .bind(("table", STATUS_HISTORY))
+            .bind(("item_id", from_item_id))
+            .await?;
+        let changes: Vec<StatusChange> = response.take(0)?;
+        if changes.is_empty() {
+            return Ok(());
+        }
+
+        let copies: Vec<StatusChange> = changes
+            .into_iter()
+            .map(|change| StatusChange {
+                id: None,
+                item_id: to_item_id.clone(),
+                ..change
+            })
+            .collect();
+        let _: Vec<StatusChange> = DB.insert(STATUS_HISTORY).content(copies).await?;
+        Ok(())
+    }
+
+    /// Full history of the given items, oldest change first
+    pub async fn history_of_items(
+        item_ids: Vec<String>,
+    ) -> Result<Vec<types::ItemStatusChange>, leptos::prelude::ServerFnError> {
+        let query = "SELECT * FROM type::table($table) WHERE item_id IN $item_ids ORDER BY changed_at";
+        let mut response = DB
+            .query(query)
+            .bind(("table", STATUS_HISTORY))
+            .bind(("item_ids", item_ids))
+            .await?;
+
+        let changes: Vec<StatusChange> = response.take(0)?;
+        Ok(changes.into_iter().map(Into::into).collect())
+    }
+}
+#[cfg(feature = "ssr")]
+use ssr::*;
+
+/// How far back the recently completed list of a station reaches
+pub const RECENT_BUMP_MINUTES: i64 = 30;
+/// Maximum number of orders in the recently completed list of a station
+pub const RECENT_BUMP_LIMIT: usize = 10;
+
+/// Orders the station recently moved on and that are still in its output status, newest first
+#[server(GetRecentBumps, "/api/status_history")]
+pub async fn get_recent_bumps(station_id: String) -> Result<Vec<types::BumpedOrder>, ServerFnError> {
+    use crate::backend::item::ssr::items_by_ids;
+    use crate::backend::order::get_order;
+    use crate::backend::station::get_station;
+    use crate::common::german_names::pickup_name;
+
+    let station = get_station(station_id.clone()).await?;
+    let query = "
+        SELECT * FROM type::table($table)
+            WHERE station_id = $station_id AND changed_at > time::now() - type::duration($window)
+            ORDER BY changed_at DESC
+    ";
+    let mut response = DB
+        .query(query)
+        .bind(("table", STATUS_HISTORY))
+        .bind(("station_id", station_id))
+        .bind(("window", format!("{}m", RECENT_BUMP_MINUTES)))
+        .await?;
+    let changes: Vec<StatusChange> = response.take(0)?;
+
+    // Only the newest change per item counts
+    let mut latest: HashMap<String, types::ItemStatusChange> = HashMap::new();
+    for change in changes {
+        let change: types::ItemStatusChange = change.into();
+        latest.entry(change.item_id.clone()).or_insert(change);
+    }
+    let item_ids: Vec<String> = latest.keys().cloned().collect();
+    let items = items_by_ids(&item_ids).await?;
+
+    let mut orders: Vec<types::BumpedOrder> = Vec::new();
+    for item in items {
+        let item: types::Item = item.into();
+        let Some(change) = latest.get(&item.id) else {
+            continue;
+        };
+        // Recalled items and items that moved on since cannot be recalled from here
+        if change.to != station.output_status || change.to != item.status {
+            continue;
+        }
+        match orders.iter_mut().find(|o| o.order_id == change.order_id) {
+            Some(order) => {
+                order.item_ids.push(item.id.clone());
+                order.quantity += item.quantity;
+                order.bumped_at = order.bumped_at.max(change.changed_at);
+            }
+            None => orders.push(types::BumpedOrder {
+                order_id: change.order_id.clone(),
+                name: String::new(),
+                item_ids: vec![item.id.clone()],
+                quantity: item.quantity,
+                bumped_at: change.changed_at,
+            }),
+        }
+    }
+    orders.sort_by_key(|order| std::cmp::Reverse(order.bumped_at));
+    orders.truncate(RECENT_BUMP_LIMIT);
+    for bumped in orders.iter_mut() {
+        let order = get_order(bumped.order_id.clone()).await.ok();
+        bumped.name = pickup_name(&bumped.order_id, order.as_ref());
+    }
+
+    Ok(orders)
+}
+
+/// When each of the given orders became ready, from the latest change of one of its items to `Ready`.
+/// Orders without such a change are left out.
+#[server(GetReadySince, "/api/status_history")]
+pub async fn get_ready_since(order_ids: Vec<String>) -> Result<HashMap<String, i64>, ServerFnError> {
+    #[derive(Deserialize)]
+    struct ReadyAt {
+        order_id: String,
+        at: Datetime,
+    }
+
+    let query = "
+        SELECT order_id, time::max(changed_at) AS at FROM type::table($table)
+            WHERE order_id IN $order_ids AND `to` = $ready
+            GROUP BY order_id
+    ";
+    let mut response = DB
+        .query(query)
+        .bind(("table", STATUS_HISTORY))
+        .bind(("order_ids", order_ids))
+        .bind(("ready", types::OrderStatus::Ready))
+        .await?;
+
+    let ready: Vec<ReadyAt> = response.take(0)?;
+    Ok(ready.into_iter().map(|ready| (ready.order_id, ready.at.to_secs())).collect())
+}
+
+#[server(GetItemHistory, "/api/status_history")]
+pub async fn get_item_history(item_id: String) -> Result<Vec<types::ItemStatusChange>, ServerFnError> {
+    let query = "SELECT * FROM type::table($table) WHERE item_id = $item_id ORDER BY changed_at";
+    let mut response = DB
+        .query(query)
+        .bind(("table", STATUS_HISTORY))
+        .bind(("item_id", item_id))
+        .await?;
+
+    let changes: Vec<StatusChange> = response.take(0)?;
+    Ok(changes.into_iter().map(Into::into).collect())
+}
+
+#[server(GetOrderHistory, "/api/status_history")]
+pub async fn get_order_history(order_id: String) -> Result<Vec<types::ItemStatusChange>, ServerFnError> {
+    let query = "SELECT * FROM type::table($table) WHERE order_id = $order_id ORDER BY changed_at";
+    let mut response = DB
+        .query(query)
+        .bind(("table", STATUS_HISTORY))
+        .bind(("order_id", order_id))
+        .await?;
+
+    let changes: Vec<StatusChange> = response.take(0)?;
+    Ok(changes.into_iter().map(Into::into).collect())
+}
@@ -0,0 +1,133 @@
+use leptos::prelude::*;
+
+use crate::common::types;
+
+#[cfg(feature = "ssr")]
+pub mod ssr {
+    pub use crate::common::rate_limit::{client_address, RateLimiter};
+    pub use leptos::server_fn::error::ServerFnError::ServerError;
+    pub use std::sync::{LazyLock, Mutex};
+    pub use surrealdb::sql::Datetime;
+
+    /// Tracking requests a single client may make per window, enough for a phone polling a page now and then
+    pub const TRACKING_LIMIT: u32 = 30;
+    pub const TRACKING_WINDOW_SECS: i64 = 60;
+
+    pub static TRACKING_LIMITER: LazyLock<Mutex<RateLimiter>> =
+        LazyLock::new(|| Mutex::new(RateLimiter::new(TRACKING_LIMIT, TRACKING_WINDOW_SECS)));
+
+    /// Reverse proxies whose `X-Forwarded-For` is believed, from the comma separated `TRUSTED_PROXIES`
+    pub static TRUSTED_PROXIES: LazyLock<Vec<std::net::IpAddr>> = LazyLock::new(|| {
+        std::env::var("TRUSTED_PROXIES")
+            .unwrap_or_default()
+            .split(',')
+            .filter_map(|address| address.trim().parse().ok())
+            .collect()
+    });
+
+    /// Address of the requesting client, the peer or, behind a trusted proxy, the one it forwarded
+    pub async fn requesting_client() -> String {
+        use axum::extract::ConnectInfo;
+        use axum::http::HeaderMap;
+        use std::net::SocketAddr;
+
+        let Ok(ConnectInfo(peer)) = leptos_axum::extract::<ConnectInfo<SocketAddr>>().await else {
+            return "unknown".to_string();
+        };
+        let headers = leptos_axum::extract::<HeaderMap>().await.unwrap_or_default();
+        let forwarded_for = headers.get("x-forwarded-for").and_then(|value| value.to_str().ok());
+        client_address(peer.ip(), forwarded_for, &TRUSTED_PROXIES).to_string()
+    }
+
+    /// Link to the tracking page of an order, using the host the requesting device is connected to.
+    /// The scheme a proxy forwarded is only believed from a trusted proxy
+    pub async fn tracking_url(order_id: &str) -> Result<String, leptos::prelude::ServerFnError> {
+        use axum::extract::ConnectInfo;
+        use axum::http::{header, HeaderMap};
+        use std::net::SocketAddr;
+
+        let headers: HeaderMap = leptos_axum::extract().await?;
+        let host = headers
+            .get(header::HOST)
+            .and_then(|value| value.to_str().ok())
+            .unwrap_or("127.0.0.1:3000");
+        let trusted = leptos_axum::extract::<ConnectInfo<SocketAddr>>()
+            .await
+            .is_ok_and(|ConnectInfo(peer)| TRUSTED_PROXIES.contains(&peer.ip()));
+        let scheme = headers
+            .get("x-forwarded-proto")
+            .filter(|_| trusted)
+            .and_then(|value| value.to_str().ok())
+            .filter(|scheme| matches!(*scheme, "http" | "https"))
+            .unwrap_or("http");
+        Ok(format!("{}://{}/order/{}", scheme, host, order_id))
+    }
+
+    /// Rejects the request once the client exceeded its tracking budget
+    pub async fn check_rate_limit() -> Result<(), leptos::prelude::ServerFnError> {
+        let client = requesting_client().await;
+        let allowed = TRACKING_LIMITER
+            .lock()
+            .map(|mut limiter| limiter.allow(&client, Datetime::default().to_secs()))
+            .unwrap_or(true);
+        if allowed {
+            Ok(())
+        } else {
+            Err(ServerError("Too many requests, please try again in a minute".into()))
+        }
+    }
+}
+#[cfg(feature = "ssr")]
+use ssr::*;
+
+/// Public status of a single order for the customer's phone, only exposing the order's own data
+#[server(GetOrderTracking, "/api/tracking")]
+pub async fn get_order_tracking(order_id: String) -> Result<types::OrderTracking, ServerFnError> {
+    use crate::backend::item::get_items_by_order;
+    use crate::backend::order::get_order;
+    use crate::backend::product::get_products;
+    use crate::common::german_names::pickup_name;
+
+    check_rate_limit().await?;
+
+    let order = get_order(order_id.clone()).await?;
+    let products = get_products().await?;
+    let items = get_items_by_order(order_id.clone())
+        .await?
+        .into_iter()
+        .map(|item| types::TrackedItem {
+            id: item.id,
+            product_name: products
+                .iter()
+                .find(|p| p.id == item.product_id)
+                .map(|p| p.name.clone())
+                .unwrap_or_default(),
+            quantity: item.quantity,
+            status: item.status,
+        })
+        .collect();
+
+    Ok(types::OrderTracking {
+        name: pickup_name(&order_id, Some(&order)),
+        number: order.number_label(),
+        order_id,
+        created_at: order.created_at,
+        items,
+    })
+}
+
+/// QR code as SVG linking to the tracking page of an order, using the host the cashier is connected to
+#[server(GetOrderQr, "/api/tracking")]
+pub async fn get_order_qr(order_id: String) -> Result<String, ServerFnError> {
+    use crate::common::errors::Error;
+    use qrcode::{render::svg, QrCode};
+
+    let url = tracking_url(&order_id).await?;
+
+    let code = QrCode::new(url.as_bytes()).map_err(|e| Error::InternalError(format!("QR code generation failed: {}", e)))?;
+    Ok(code
+        .render::<svg::Color>()
+        .min_dimensions(160, 160)
+        .quiet_zone(true)
+        .build())
+}
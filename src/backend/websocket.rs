@@ -1,8 +1,9 @@
 use axum::{
-    extract::{ws::WebSocket, WebSocketUpgrade},
-    response::Response,
+    extract::{ws::WebSocket, Query, WebSocketUpgrade},
+    http::StatusCode,
+    response::{IntoResponse, Response},
 };
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc, watch};
 use futures_util::{SinkExt, StreamExt};
 
 use crate::common::resource_type::*;
@@ -10,11 +11,43 @@ use crate::common::resource_type::*;
 
 pub type WebSocketSender = broadcast::Sender<String>;
 
-use std::sync::OnceLock;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{LazyLock, Mutex, OnceLock};
 
 // Global WebSocket sender for server functions
 static WS_SENDER: OnceLock<WebSocketSender> = OnceLock::new();
 
+// Whether several events are active, connections that chose no event then receive no event's changes
+static SEVERAL_EVENTS_ACTIVE: AtomicBool = AtomicBool::new(false);
+
+// Stations served by a connected agent, with the number of agents serving each
+static AGENT_STATIONS: LazyLock<Mutex<HashMap<String, usize>>> = LazyLock::new(|| Mutex::new(HashMap::new()));
+
+/// Whether an agent serves the station, the agent then prints its tickets instead of the server
+pub fn served_by_agent(station_id: &str) -> bool {
+    AGENT_STATIONS.lock().unwrap().contains_key(station_id)
+}
+
+fn agent_joined(station_id: &str) {
+    *AGENT_STATIONS.lock().unwrap().entry(station_id.to_string()).or_default() += 1;
+}
+
+fn agent_left(station_id: &str) {
+    let mut stations = AGENT_STATIONS.lock().unwrap();
+    if let Some(count) = stations.get_mut(station_id) {
+        *count -= 1;
+        if *count == 0 {
+            stations.remove(station_id);
+        }
+    }
+}
+
+/// Keeps the event filter of the connections in line with the settings
+pub fn set_active_event_count(count: usize) {
+    SEVERAL_EVENTS_ACTIVE.store(count > 1, Ordering::Relaxed);
+}
+
 /// Initialize the global WebSocket sender
 pub fn init_websocket_sender(sender: WebSocketSender) {
     WS_SENDER
@@ -22,20 +55,172 @@ pub fn init_websocket_sender(sender: WebSocketSender) {
         .expect("WebSocket sender already initialized");
 }
 
+/// Receiver of every broadcast message, for server side services reacting to changes
+pub fn subscribe() -> Option<broadcast::Receiver<String>> {
+    WS_SENDER.get().map(|sender| sender.subscribe())
+}
+
+#[derive(Debug, serde::Deserialize)]
+pub struct WebSocketParams {
+    token: Option<String>,
+}
+
+/// Whether the token matches `AGENT_TOKEN`, headless agents authenticate with it
+fn valid_agent_token(token: &str) -> bool {
+    std::env::var("AGENT_TOKEN").is_ok_and(|expected| !expected.is_empty() && expected == token)
+}
+
 pub async fn websocket_handler(
     ws: WebSocketUpgrade,
+    Query(params): Query<WebSocketParams>,
     sender: axum::extract::State<WebSocketSender>,
 ) -> Response {
-    ws.on_upgrade(move |socket| websocket_connection(socket, sender.0))
+    // Browsers connect without a token, a token that is given has to be right
+    let agent = match params.token {
+        Some(token) if valid_agent_token(&token) => true,
+        Some(_) => return StatusCode::UNAUTHORIZED.into_response(),
+        None => false,
+    };
+    ws.on_upgrade(move |socket| websocket_connection(socket, sender.0, agent))
 }
 
-async fn websocket_connection(socket: WebSocket, sender: WebSocketSender) {
+/// Current stations, products and orders as `Add` messages, so an agent can start from a full picture.
+/// Orders are those of the event the agent follows, or of every event. The items waiting at the agent's
+/// stations come last, so it treats them as arrivals, including those that arrived while it was away.
+async fn snapshot(
+    event_id: Option<String>,
+    station_ids: &[String],
+) -> Result<Vec<String>, leptos::prelude::ServerFnError> {
+    use crate::backend::item::get_items_by_station;
+    use crate::backend::order::get_orders;
+    use crate::backend::product::get_products;
+    use crate::backend::station::get_stations;
+
+    fn add<T: ResourceData>(item: T) -> Option<String> {
+        serde_json::to_string(&WebSocketMessage::new(Message::Add(item))).ok()
+    }
+
+    let mut messages: Vec<String> = get_stations().await?.into_iter().filter_map(add).collect();
+    messages.extend(get_products().await?.into_iter().filter_map(add));
+    messages.extend(get_orders(event_id.clone()).await?.into_iter().filter_map(add));
+
+    let mut item_ids = std::collections::HashSet::new();
+    for station_id in station_ids {
+        let items = get_items_by_station(station_id.clone(), event_id.clone()).await?;
+        let waiting = items.into_iter().filter(|item| item_ids.insert(item.id.clone()));
+        messages.extend(waiting.filter_map(add));
+    }
+    Ok(messages)
+}
+
+/// Event and order of a broadcast message, `None` for messages every client receives
+#[derive(serde::Deserialize)]
+struct Scope {
+    #[serde(default)]
+    event: Option<String>,
+    #[serde(default)]
+    order: Option<String>,
+}
+
+/// What a connection chose to receive, set by the client with subscription messages
+#[derive(Debug, Clone, Default)]
+struct Subscription {
+    event: Option<String>,
+    order: Option<String>, // Only this order's changes, for the public order tracking page
+}
+
+/// Whether a connection with `subscription` receives the message. Connections that chose no event
+/// receive the changes of every event, unless several are active and any of them could be meant.
+fn in_scope(json_msg: &str, subscription: &Subscription, several_events_active: bool) -> bool {
+    let Ok(scope) = serde_json::from_str::<Scope>(json_msg) else {
+        return subscription.order.is_none();
+    };
+    if let Some(order) = &subscription.order {
+        return scope.order.as_ref() == Some(order);
+    }
+    match (scope.event, &subscription.event) {
+        (Some(event), Some(subscribed)) => &event == subscribed,
+        (Some(_), None) => !several_events_active,
+        (None, _) => true,
+    }
+}
+
+/// What an order tracking page receives of a message in its scope: item changes as the public
+/// `TrackedItem` view without prices, and the deletion of the order. Everything else is dropped
+async fn tracking_view(json_msg: &str) -> Option<String> {
+    use crate::backend::product::get_product;
+    use crate::common::types::{Item, Order, TrackedItem};
+
+    fn to_json<T: ResourceData>(message: Message<T>) -> Option<String> {
+        serde_json::to_string(&WebSocketMessage::new(message)).ok()
+    }
+
+    let generic = serde_json::from_str::<GenericWebSocketMessage>(json_msg).ok()?;
+    match generic.resource_type.as_str() {
+        Item::RESOURCE_NAME => {
+            let tracked = |item: Item, product_name: String| TrackedItem {
+                id: item.id,
+                product_name,
+                quantity: item.quantity,
+                status: item.status,
+            };
+            match serde_json::from_value::<Message<Item>>(generic.message).ok()? {
+                Message::Add(item) => {
+                    let name = get_product(item.product_id.clone()).await.map(|p| p.name).unwrap_or_default();
+                    to_json(Message::Add(tracked(item, name)))
+                }
+                Message::Update(item) => {
+                    let name = get_product(item.product_id.clone()).await.map(|p| p.name).unwrap_or_default();
+                    to_json(Message::Update(tracked(item, name)))
+                }
+                Message::Delete(id) => to_json(Message::<TrackedItem>::Delete(id)),
+            }
+        }
+        Order::RESOURCE_NAME => match serde_json::from_value::<Message<Order>>(generic.message).ok()? {
+            Message::Delete(id) => to_json(Message::<Order>::Delete(id)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+async fn websocket_connection(socket: WebSocket, sender: WebSocketSender, agent: bool) {
     let mut receiver = sender.subscribe();
+    // Set by the client with `EVENT_SUBSCRIPTION` and `ORDER_SUBSCRIPTION` messages
+    let (subscription_sender, subscription) = watch::channel(Subscription::default());
+    // Messages meant for this connection only
+    let (direct_sender, mut direct_receiver) = mpsc::unbounded_channel::<String>();
     let (mut ws_sender, mut ws_receiver) = socket.split();
 
     // Task to forward broadcast messages to WebSocket client
     let send_task = tokio::spawn(async move {
-        while let Ok(json_msg) = receiver.recv().await {
+        loop {
+            let json_msg = tokio::select! {
+                result = receiver.recv() => match result {
+                    Ok(json_msg)
+                        if in_scope(
+                            &json_msg,
+                            &subscription.borrow(),
+                            SEVERAL_EVENTS_ACTIVE.load(Ordering::Relaxed),
+                        ) =>
+                    {
+                        json_msg
+                    }
+                    Ok(_) => continue,
+                    Err(_) => break,
+                },
+                Some(json_msg) = direct_receiver.recv() => json_msg,
+            };
+            // Tracking pages are public, they only get the trimmed view of their order
+            let tracking = subscription.borrow().order.is_some();
+            let json_msg = if tracking {
+                match tracking_view(&json_msg).await {
+                    Some(json_msg) => json_msg,
+                    None => continue,
+                }
+            } else {
+                json_msg
+            };
             // Send the JSON data directly to client
             if ws_sender
                 .send(axum::extract::ws::Message::Text(json_msg.into()))
@@ -47,14 +232,67 @@ async fn websocket_connection(socket: WebSocket, sender: WebSocketSender) {
         }
     });
 
-    // Task to handle incoming WebSocket messages (if needed for bidirectional communication)
+    // Task to handle incoming WebSocket messages (station presence and event subscriptions)
     let recv_task = tokio::spawn(async move {
+        use crate::backend::alert::ssr::{station_connected, station_left};
+        let mut station_ids: Vec<String> = Vec::new();
+
         while let Some(Ok(msg)) = ws_receiver.next().await {
             match msg {
                 axum::extract::ws::Message::Close(_) => break,
+                axum::extract::ws::Message::Text(text) => {
+                    let Ok(generic) = serde_json::from_str::<GenericWebSocketMessage>(text.as_str()) else {
+                        continue;
+                    };
+                    if generic.resource_type == EVENT_SUBSCRIPTION {
+                        let event_id = generic.message.as_str().map(str::to_string);
+                        subscription_sender.send_modify(|subscription| subscription.event = event_id);
+                        continue;
+                    }
+                    if generic.resource_type == ORDER_SUBSCRIPTION {
+                        let order_id = generic.message.as_str().map(str::to_string);
+                        subscription_sender.send_modify(|subscription| subscription.order = order_id);
+                        continue;
+                    }
+                    if generic.resource_type != STATION_PRESENCE {
+                        continue;
+                    }
+                    for previous in station_ids.drain(..) {
+                        station_left(&previous, false);
+                        if agent {
+                            agent_left(&previous);
+                        }
+                    }
+                    station_ids = presence_station_ids(&generic.message);
+                    for id in &station_ids {
+                        station_connected(id);
+                        if agent {
+                            agent_joined(id);
+                        }
+                    }
+                    if agent && !station_ids.is_empty() {
+                        let event_id = subscription_sender.borrow().event.clone();
+                        match snapshot(event_id, &station_ids).await {
+                            Ok(messages) => {
+                                for message in messages {
+                                    let _ = direct_sender.send(message);
+                                }
+                            }
+                            Err(e) => leptos::logging::log!("Failed to send snapshot to agent: {}", e),
+                        }
+                    }
+                }
                 _ => {} // Handle other message types if needed
             }
         }
+
+        // The socket is gone while still serving stations
+        for id in station_ids {
+            station_left(&id, true);
+            if agent {
+                agent_left(&id);
+            }
+        }
     });
 
     // Wait for either task to complete (connection closed or error)
@@ -98,6 +336,43 @@ where
     let ws_message: WebSocketMessage<T> = WebSocketMessage {
         resource_type: T::RESOURCE_NAME.to_string(),
         message: Message::Delete(item_id),
+        event: None,
+        order: None,
+    };
+    if let Ok(json_data) = serde_json::to_string(&ws_message) {
+        if let Some(sender) = WS_SENDER.get() {
+            let _ = sender.send(json_data);
+        }
+    }
+}
+
+/// Broadcast of an order or item deletion, scoped like `broadcast_to_event` and to the order,
+/// so the order's tracking page learns about it as well
+pub fn broadcast_delete_in<T>(event_id: Option<&str>, order_id: Option<&str>, id: String)
+where
+    T: ResourceData,
+{
+    let ws_message: WebSocketMessage<T> = WebSocketMessage {
+        resource_type: T::RESOURCE_NAME.to_string(),
+        message: Message::Delete(id),
+        event: event_id.map(str::to_string),
+        order: order_id.map(str::to_string),
+    };
+    if let Ok(json_data) = serde_json::to_string(&ws_message) {
+        if let Some(sender) = WS_SENDER.get() {
+            let _ = sender.send(json_data);
+        }
+    }
+}
+
+/// Broadcast of an order or item change, only clients following the event or no event at all receive it
+pub fn broadcast_to_event<T>(event_id: Option<&str>, message: Message<T>)
+where
+    T: ResourceData,
+{
+    let ws_message = WebSocketMessage {
+        event: event_id.map(str::to_string),
+        ..WebSocketMessage::new(message)
     };
     if let Ok(json_data) = serde_json::to_string(&ws_message) {
         if let Some(sender) = WS_SENDER.get() {
@@ -105,3 +380,69 @@ where
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types;
+
+    fn following(event: Option<&str>, order: Option<&str>) -> Subscription {
+        Subscription {
+            event: event.map(str::to_string),
+            order: order.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_event_scope() {
+        let scoped = r#"{"resource_type":"items","message":{"Delete":"a"},"event":"fest"}"#;
+        let unscoped = r#"{"resource_type":"products","message":{"Delete":"a"}}"#;
+
+        assert!(in_scope(scoped, &following(Some("fest"), None), true));
+        assert!(!in_scope(scoped, &following(Some("bar"), None), false));
+        assert!(in_scope(scoped, &following(None, None), false), "Without a choice the only active event is meant");
+        assert!(!in_scope(scoped, &following(None, None), true), "Without a choice no booth's changes are sent");
+        assert!(in_scope(unscoped, &following(Some("bar"), None), true));
+        assert!(in_scope(unscoped, &following(None, None), true));
+    }
+
+    #[test]
+    fn test_order_scope() {
+        let own = r#"{"resource_type":"item","message":{"Delete":"a"},"event":"fest","order":"o1"}"#;
+        let other = r#"{"resource_type":"item","message":{"Delete":"b"},"event":"fest","order":"o2"}"#;
+        let product = r#"{"resource_type":"product","message":{"Delete":"a"}}"#;
+        let tracking = following(None, Some("o1"));
+
+        assert!(in_scope(own, &tracking, true));
+        assert!(!in_scope(other, &tracking, false));
+        assert!(!in_scope(product, &tracking, false), "Tracking pages receive nothing but their order");
+        assert!(!in_scope("not json", &tracking, false));
+    }
+
+    #[tokio::test]
+    async fn test_tracking_view_drops_orders_but_their_deletion() {
+        let order = types::Order {
+            id: "o1".to_string(),
+            created_at: 0,
+            rush: false,
+            name: "Anna".to_string(),
+            number: 1,
+            payment_method: types::PaymentMethod::Card,
+        };
+        let update = serde_json::to_string(&WebSocketMessage::new(Message::Update(order))).unwrap();
+        let delete = serde_json::to_string(&WebSocketMessage::new(Message::<types::Order>::Delete("o1".into()))).unwrap();
+
+        assert_eq!(tracking_view(&update).await, None, "Orders carry totals and payment details");
+        assert_eq!(tracking_view(&delete).await, Some(delete));
+    }
+
+    #[tokio::test]
+    async fn test_tracking_view_of_item_deletion() {
+        let delete = r#"{"resource_type":"item","message":{"Delete":"a"},"event":"fest","order":"o1"}"#;
+
+        let view = tracking_view(delete).await.expect("Deletions reach the tracking page");
+
+        assert!(view.contains(r#""resource_type":"tracked_item""#));
+        assert!(view.contains(r#"{"Delete":"a"}"#));
+    }
+}
@@ -0,0 +1,242 @@
+//! Headless agent that serves stations without a browser, e.g. a Raspberry Pi next to a kitchen printer.
+//!
+//! Configured through the environment (or a `.env` file):
+//! - `AGENT_SERVER`: WebSocket endpoint of the server, defaults to `ws://127.0.0.1:3000/ws`
+//! - `AGENT_TOKEN`: must match the server's `AGENT_TOKEN`
+//! - `AGENT_STATIONS`: comma separated ids of the stations to serve
+//! - `AGENT_PRINTER`: optional ESC/POS network printer for all served stations, without one tickets go to the
+//!   station's own printer, or to stdout for stations without a printer
+
+#[cfg(feature = "ssr")]
+mod agent {
+    use futures_util::{SinkExt, StreamExt};
+    use order_stream::backend::printing::{render_ticket, ssr::send_with_retry, ssr::ticket_time, ticket, PrintTracker, Ticket};
+    use order_stream::common::resource_type::{
+        GenericWebSocketMessage, Message, ResourceName, EVENT_SUBSCRIPTION, STATION_PRESENCE,
+    };
+    use order_stream::common::types;
+    use std::collections::HashMap;
+    use std::time::{Duration, Instant};
+    use tokio_tungstenite::{connect_async, tungstenite};
+
+    /// Items of an order arriving shortly after each other end up on the same ticket
+    const BATCH_DELAY: Duration = Duration::from_millis(500);
+    /// Pause before connecting again after the server went away
+    const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+    pub struct Config {
+        pub server: String,
+        pub token: String,
+        pub station_ids: Vec<String>,
+        pub printer: Option<String>,
+        pub event: Option<String>, // Only orders of this event, all events when unset
+    }
+
+    impl Config {
+        pub fn from_env() -> Result<Self, String> {
+            dotenvy::dotenv().ok();
+
+            let token = std::env::var("AGENT_TOKEN").map_err(|_| "AGENT_TOKEN is not set".to_string())?;
+            let station_ids: Vec<String> = std::env::var("AGENT_STATIONS")
+                .unwrap_or_default()
+                .split(',')
+                .map(|id| id.trim().to_string())
+                .filter(|id| !id.is_empty())
+                .collect();
+            if station_ids.is_empty() {
+                return Err("AGENT_STATIONS names no station".to_string());
+            }
+
+            Ok(Self {
+                server: std::env::var("AGENT_SERVER").unwrap_or_else(|_| "ws://127.0.0.1:3000/ws".to_string()),
+                token,
+                station_ids,
+                printer: std::env::var("AGENT_PRINTER").ok().filter(|printer| !printer.trim().is_empty()),
+                event: std::env::var("AGENT_EVENT").ok().filter(|event| !event.trim().is_empty()),
+            })
+        }
+    }
+
+    /// What the agent knows about the server, kept up to date from the broadcast messages
+    #[derive(Default)]
+    struct State {
+        stations: HashMap<String, types::Station>,
+        products: HashMap<String, types::Product>,
+        orders: HashMap<String, types::Order>,
+        tracker: PrintTracker,
+        // Station and order to the items waiting to be printed together, with the arrival of the first
+        pending: HashMap<(String, String), (Instant, Vec<types::Item>)>,
+    }
+
+    fn apply<T: serde::de::DeserializeOwned + order_stream::common::resource_type::ResourceData>(
+        map: &mut HashMap<String, T>,
+        message: serde_json::Value,
+        id: impl Fn(&T) -> String,
+    ) {
+        match serde_json::from_value::<Message<T>>(message) {
+            Ok(Message::Add(resource)) | Ok(Message::Update(resource)) => {
+                map.insert(id(&resource), resource);
+            }
+            Ok(Message::Delete(removed)) => {
+                map.remove(&removed);
+            }
+            Err(_) => {}
+        }
+    }
+
+    impl State {
+        fn handle(&mut self, generic: GenericWebSocketMessage, config: &Config) {
+            match generic.resource_type.as_str() {
+                types::Station::RESOURCE_NAME => apply(&mut self.stations, generic.message, |s| s.id.clone()),
+                types::Product::RESOURCE_NAME => apply(&mut self.products, generic.message, |p| p.id.clone()),
+                types::Order::RESOURCE_NAME => apply(&mut self.orders, generic.message, |o| o.id.clone()),
+                types::Item::RESOURCE_NAME => match serde_json::from_value::<Message<types::Item>>(generic.message) {
+                    Ok(Message::Add(item) | Message::Update(item)) => self.item_changed(item, config),
+                    Ok(Message::Delete(id)) => self.tracker.forget(&id),
+                    Err(_) => {}
+                },
+                _ => {}
+            }
+        }
+
+        fn item_changed(&mut self, item: types::Item, config: &Config) {
+            let (Some(order_id), Some(product)) = (item.order_id.clone(), self.products.get(&item.product_id)) else {
+                return;
+            };
+            for station_id in &config.station_ids {
+                let Some(station) = self.stations.get(station_id) else {
+                    continue;
+                };
+                if station.handles_category(&product.category_id) && self.tracker.entered(station, &item) {
+                    self.pending
+                        .entry((station_id.clone(), order_id.clone()))
+                        .or_insert_with(|| (Instant::now(), Vec::new()))
+                        .1
+                        .push(item.clone());
+                }
+            }
+            if item.status.is_terminal() {
+                self.tracker.forget(&item.id);
+            }
+        }
+
+        /// Tickets of all batches that waited long enough for further items, with the printer to send them to
+        fn due_tickets(&mut self, now: Instant, printer: Option<&String>) -> Vec<(Ticket, Option<String>)> {
+            let due: Vec<(String, String)> = self
+                .pending
+                .iter()
+                .filter(|(_, (since, _))| now.duration_since(*since) >= BATCH_DELAY)
+                .map(|(key, _)| key.clone())
+                .collect();
+            let products: Vec<types::Product> = self.products.values().cloned().collect();
+
+            due.into_iter()
+                .filter_map(|(station_id, order_id)| {
+                    let (_, items) = self.pending.remove(&(station_id.clone(), order_id.clone()))?;
+                    let station = self.stations.get(&station_id)?;
+                    let order = self.orders.get(&order_id);
+                    let time = order.map(|o| ticket_time(o.created_at)).unwrap_or_default();
+                    let printer = printer.or(station.printer.as_ref()).cloned();
+                    Some((ticket(station, &order_id, order, &items, &products, time), printer))
+                })
+                .collect()
+        }
+    }
+
+    async fn output(ticket: Ticket, printer: Option<String>) {
+        match printer {
+            Some(printer) => {
+                if !send_with_retry(&printer, render_ticket(&ticket)).await {
+                    eprintln!("Ticket for {} {} could not be printed", ticket.number, ticket.order_name);
+                }
+            }
+            None => {
+                println!("[{}] {} {} {}", ticket.station_name, ticket.number, ticket.order_name, ticket.time);
+                for (quantity, name) in &ticket.items {
+                    println!("  {}x {}", quantity, name);
+                }
+                for note in &ticket.notes {
+                    println!("  {}", note);
+                }
+            }
+        }
+    }
+
+    /// Serves the stations until the connection is lost
+    async fn serve(config: &Config, state: &mut State) -> Result<(), String> {
+        let mut url = reqwest::Url::parse(&config.server).map_err(|e| e.to_string())?;
+        url.query_pairs_mut().append_pair("token", &config.token);
+        let (socket, _) = connect_async(url.as_str()).await.map_err(|e| e.to_string())?;
+        let (mut sender, mut receiver) = socket.split();
+        println!("Connected to {}, serving {}", config.server, config.station_ids.join(", "));
+
+        // The event comes first, announcing the stations makes the server send its current state
+        // including the items waiting at them
+        let subscription = GenericWebSocketMessage {
+            resource_type: EVENT_SUBSCRIPTION.to_string(),
+            message: serde_json::json!(config.event),
+        };
+        let presence = GenericWebSocketMessage {
+            resource_type: STATION_PRESENCE.to_string(),
+            message: serde_json::json!(config.station_ids),
+        };
+        for message in [subscription, presence] {
+            if let Ok(json) = serde_json::to_string(&message) {
+                sender
+                    .send(tungstenite::Message::Text(json.into()))
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+
+        let mut flush = tokio::time::interval(Duration::from_millis(100));
+        loop {
+            tokio::select! {
+                message = receiver.next() => match message {
+                    Some(Ok(tungstenite::Message::Text(text))) => {
+                        if let Ok(generic) = serde_json::from_str::<GenericWebSocketMessage>(text.as_str()) {
+                            state.handle(generic, config);
+                        }
+                    }
+                    Some(Ok(tungstenite::Message::Close(_))) | None => return Ok(()),
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(e.to_string()),
+                },
+                _ = flush.tick() => {
+                    for (ticket, printer) in state.due_tickets(Instant::now(), config.printer.as_ref()) {
+                        tokio::spawn(output(ticket, printer));
+                    }
+                }
+            }
+        }
+    }
+
+    pub async fn run(config: Config) {
+        // Kept across reconnects, so the items of the snapshot that were printed before are not printed again
+        let mut state = State::default();
+        loop {
+            match serve(&config, &mut state).await {
+                Ok(()) => eprintln!("Connection to {} closed", config.server),
+                Err(e) => eprintln!("Connection to {} failed: {}", config.server, e),
+            }
+            tokio::time::sleep(RECONNECT_DELAY).await;
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[tokio::main]
+async fn main() {
+    match agent::Config::from_env() {
+        Ok(config) => agent::run(config).await,
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[cfg(not(feature = "ssr"))]
+pub fn main() {
+    // The agent talks to the server over the network and needs the server side dependencies
+}
@@ -0,0 +1,509 @@
+//! Terminal station client for machines too weak for the browser app.
+//!
+//! Usage: `order-stream-tui [server]`, the server defaults to `http://127.0.0.1:3000`.
+//! After signing in and picking a station, and the event while several are active,
+//! pending orders are listed like on the station page.
+//! Type the position of an order and Enter to bump it, or e.g. `3.2` to bump only the second item of the third order.
+//! `u` undoes the last bump, `r` reloads and `q` quits. The live feed reconnects on its own after the server went away.
+
+#[cfg(feature = "ssr")]
+mod tui {
+    use futures_util::{SinkExt, StreamExt};
+    use leptos::server_fn::ServerFn;
+    use order_stream::backend::event::GetEvents;
+    use order_stream::backend::item::{GetItemsByStation, RecallItems, UpdateItem, UpdateItemsByOrder};
+    use order_stream::backend::order::GetOrders;
+    use order_stream::backend::product::GetProducts;
+    use order_stream::backend::settings::GetSettings;
+    use order_stream::backend::station::GetStations;
+    use order_stream::backend::user::Login;
+    use order_stream::common::german_names::pickup_name;
+    use order_stream::common::metrics::format_duration;
+    use order_stream::common::resource_type::{
+        GenericWebSocketMessage, Message, ResourceName, EVENT_SUBSCRIPTION, STATION_PRESENCE,
+    };
+    use order_stream::common::station::{apply_item_message, group_by_order, is_waiting_at};
+    use order_stream::common::{requests, types};
+    use serde::{de::DeserializeOwned, Serialize};
+    use std::collections::HashMap;
+    use std::io::Write;
+    use std::time::Duration;
+    use tokio::io::{AsyncBufReadExt, BufReader, Lines, Stdin};
+    use tokio_tungstenite::{connect_async, tungstenite};
+
+    /// Seconds between redraws without any change, so waiting times stay current
+    const REDRAW_SECS: u64 = 15;
+    /// Pause before connecting to the live feed again after the server went away
+    const RECONNECT_DELAY: Duration = Duration::from_secs(5);
+
+    type Feed = tokio_tungstenite::WebSocketStream<tokio_tungstenite::MaybeTlsStream<tokio::net::TcpStream>>;
+
+    /// Calls server functions over HTTP the same way the browser does
+    pub struct Client {
+        http: reqwest::Client,
+        server: String,
+        cookie: Option<String>, // Session of the signed in user
+    }
+
+    impl Client {
+        pub fn new(server: String) -> Self {
+            Self {
+                http: reqwest::Client::new(),
+                server: server.trim_end_matches('/').to_string(),
+                cookie: None,
+            }
+        }
+
+        async fn call<F: ServerFn + Serialize, T: DeserializeOwned>(&mut self, args: F) -> Result<T, String> {
+            let body = serde_qs::to_string(&args).map_err(|e| e.to_string())?;
+            let mut request = self
+                .http
+                .post(format!("{}{}", self.server, F::PATH))
+                .header("Content-Type", "application/x-www-form-urlencoded")
+                .header("Accept", "application/json")
+                .body(body);
+            if let Some(cookie) = &self.cookie {
+                request = request.header("Cookie", cookie);
+            }
+            let response = request.send().await.map_err(|e| e.to_string())?;
+
+            if let Some(cookie) = response
+                .headers()
+                .get("set-cookie")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.split(';').next())
+            {
+                self.cookie = Some(cookie.to_string());
+            }
+            let success = response.status().is_success();
+            let text = response.text().await.map_err(|e| e.to_string())?;
+            if success {
+                serde_json::from_str(&text).map_err(|e| e.to_string())
+            } else {
+                Err(text)
+            }
+        }
+
+        fn websocket_url(&self) -> String {
+            format!("{}/ws", self.server.replacen("http", "ws", 1))
+        }
+    }
+
+    fn now() -> i64 {
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_secs() as i64)
+            .unwrap_or(0)
+    }
+
+    fn set_echo(on: bool) {
+        // Hides the password while typing, terminals without `stty` just show it
+        let _ = std::process::Command::new("stty").arg(if on { "echo" } else { "-echo" }).status();
+    }
+
+    /// Number of lines of the terminal, the prompt stays on the last one
+    fn terminal_rows() -> usize {
+        std::process::Command::new("stty")
+            .arg("size")
+            .stdin(std::process::Stdio::inherit())
+            .output()
+            .ok()
+            .and_then(|output| String::from_utf8(output.stdout).ok())
+            .and_then(|size| size.split_whitespace().next()?.parse().ok())
+            .unwrap_or(24)
+    }
+
+    /// Parses `3` as the third order and `3.2` as the second item of the third order
+    fn parse_position(input: &str) -> Option<(usize, Option<usize>)> {
+        let index = |number: &str| number.parse::<usize>().ok()?.checked_sub(1);
+        match input.split_once('.') {
+            Some((order, item)) => Some((index(order)?, Some(index(item)?))),
+            None => Some((index(input)?, None)),
+        }
+    }
+
+    async fn prompt(lines: &mut Lines<BufReader<Stdin>>, label: &str) -> Option<String> {
+        print!("{}", label);
+        let _ = std::io::stdout().flush();
+        lines.next_line().await.ok().flatten().map(|line| line.trim().to_string())
+    }
+
+    async fn sign_in(client: &mut Client, lines: &mut Lines<BufReader<Stdin>>) -> Option<types::User> {
+        loop {
+            let email = prompt(lines, "Email: ").await?;
+            set_echo(false);
+            let password = prompt(lines, "Password: ").await;
+            set_echo(true);
+            println!();
+
+            match client.call::<_, types::User>(Login { email, password: password? }).await {
+                Ok(user) if user.role != types::Role::Visitor => return Some(user),
+                Ok(_) => println!("This user may not operate a station"),
+                Err(e) => println!("Login failed: {}", e),
+            }
+        }
+    }
+
+    async fn pick_station(client: &mut Client, lines: &mut Lines<BufReader<Stdin>>) -> Option<types::Station> {
+        let stations: Vec<types::Station> = match client.call(GetStations {}).await {
+            Ok(stations) => stations,
+            Err(e) => {
+                println!("Could not load stations: {}", e);
+                return None;
+            }
+        };
+        for (index, station) in stations.iter().enumerate() {
+            println!("{:>3}) {}", index + 1, station.name);
+        }
+        loop {
+            let choice = prompt(lines, "Station: ").await?;
+            match choice.parse::<usize>().ok().and_then(|n| n.checked_sub(1)).and_then(|n| stations.get(n)) {
+                Some(station) => return Some(station.clone()),
+                None => println!("Please enter the number of a station"),
+            }
+        }
+    }
+
+    /// Event the station works for, only asked while several events are active
+    async fn pick_event(client: &mut Client, lines: &mut Lines<BufReader<Stdin>>) -> Option<Option<String>> {
+        let loaded = async {
+            let settings: types::Settings = client.call(GetSettings {}).await?;
+            let events: Vec<types::Event> = client.call(GetEvents {}).await?;
+            Ok::<_, String>((settings, events))
+        };
+        let (settings, events) = match loaded.await {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                println!("Could not load events: {}", e);
+                return None;
+            }
+        };
+        if settings.active_event_ids.len() < 2 {
+            return Some(settings.active_event_ids.first().cloned());
+        }
+        for (index, id) in settings.active_event_ids.iter().enumerate() {
+            let name = events.iter().find(|e| &e.id == id).map(|e| e.name.as_str()).unwrap_or(id);
+            println!("{:>3}) {}", index + 1, name);
+        }
+        loop {
+            let choice = prompt(lines, "Event: ").await?;
+            match choice.parse::<usize>().ok().and_then(|n| n.checked_sub(1)).and_then(|n| settings.active_event_ids.get(n)) {
+                Some(id) => return Some(Some(id.clone())),
+                None => println!("Please enter the number of an event"),
+            }
+        }
+    }
+
+    /// Everything shown for the station, kept current from the WebSocket feed
+    struct Board {
+        station: types::Station,
+        event: Option<String>,
+        items: Vec<types::Item>,
+        orders: HashMap<String, types::Order>,
+        products: Vec<types::Product>,
+        last_bump: Option<(String, Vec<String>)>, // What was bumped and its items, for undo
+        status: String,
+    }
+
+    impl Board {
+        fn groups(&self) -> Vec<(String, Vec<types::Item>)> {
+            group_by_order(self.items.clone(), &self.orders)
+        }
+
+        fn product_name(&self, product_id: &str) -> &str {
+            self.products.iter().find(|p| p.id == product_id).map(|p| p.name.as_str()).unwrap_or("?")
+        }
+
+        async fn reload(&mut self, client: &mut Client) {
+            let loaded = async {
+                let products: Vec<types::Product> = client.call(GetProducts {}).await?;
+                let orders: Vec<types::Order> = client.call(GetOrders { event_id: self.event.clone() }).await?;
+                let items: Vec<types::Item> = client
+                    .call(GetItemsByStation { station_id: self.station.id.clone(), event_id: self.event.clone() })
+                    .await?;
+                Ok::<_, String>((products, orders, items))
+            };
+            match loaded.await {
+                Ok((products, orders, items)) => {
+                    self.products = products;
+                    self.orders = orders.into_iter().map(|o| (o.id.clone(), o)).collect();
+                    self.items = items;
+                }
+                Err(e) => self.status = format!("Loading failed: {}", e),
+            }
+        }
+
+        fn handle(&mut self, generic: GenericWebSocketMessage) {
+            match generic.resource_type.as_str() {
+                types::Item::RESOURCE_NAME => {
+                    if let Ok(message) = serde_json::from_value::<Message<types::Item>>(generic.message) {
+                        let (station, products) = (&self.station, &self.products);
+                        apply_item_message(&mut self.items, message, self.event.as_deref(), |item| {
+                            is_waiting_at(station, products, item)
+                        });
+                    }
+                }
+                types::Order::RESOURCE_NAME => match serde_json::from_value::<Message<types::Order>>(generic.message) {
+                    Ok(Message::Add(order) | Message::Update(order)) => {
+                        self.orders.insert(order.id.clone(), order);
+                    }
+                    Ok(Message::Delete(id)) => {
+                        self.orders.remove(&id);
+                    }
+                    Err(_) => {}
+                },
+                types::Station::RESOURCE_NAME => {
+                    if let Ok(Message::Update(station)) = serde_json::from_value::<Message<types::Station>>(generic.message) {
+                        if station.id == self.station.id {
+                            self.station = station;
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        async fn bump(&mut self, client: &mut Client, position: usize) {
+            let Some((order_id, _)) = self.groups().into_iter().nth(position) else {
+                self.status = format!("No order at position {}", position + 1);
+                return;
+            };
+            let request = UpdateItemsByOrder {
+                order_id: order_id.clone(),
+                station_id: self.station.id.clone(),
+                new_status: self.station.output_status.clone(),
+            };
+            match client.call::<_, Vec<types::Item>>(request).await {
+                Ok(updated) => {
+                    let label = format!("Order '{}'", pickup_name(&order_id, self.orders.get(&order_id)));
+                    self.status = format!("{} passed on, [u] to undo", label);
+                    self.last_bump = Some((label, updated.into_iter().map(|item| item.id).collect()));
+                }
+                Err(e) => self.status = format!("Passing on failed: {}", e),
+            }
+        }
+
+        /// Moves a single item of an order on, e.g. the drink while the food still takes a while
+        async fn bump_item(&mut self, client: &mut Client, position: usize, index: usize) {
+            let Some((order_id, items)) = self.groups().into_iter().nth(position) else {
+                self.status = format!("No order at position {}", position + 1);
+                return;
+            };
+            let Some(item) = items.into_iter().nth(index) else {
+                self.status = format!("No item at position {}.{}", position + 1, index + 1);
+                return;
+            };
+            let request = UpdateItem {
+                id: item.id.clone(),
+                update: requests::item::Update {
+                    product_id: None,
+                    quantity: None,
+                    status: Some(self.station.output_status.clone()),
+                    station_id: Some(self.station.id.clone()),
+                },
+            };
+            match client.call::<_, types::Item>(request).await {
+                Ok(updated) => {
+                    let label = format!(
+                        "{}x {} of order '{}'",
+                        item.quantity,
+                        self.product_name(&item.product_id),
+                        pickup_name(&order_id, self.orders.get(&order_id))
+                    );
+                    self.status = format!("{} passed on, [u] to undo", label);
+                    self.last_bump = Some((label, vec![updated.id]));
+                }
+                Err(e) => self.status = format!("Passing on failed: {}", e),
+            }
+        }
+
+        async fn undo(&mut self, client: &mut Client) {
+            let Some((label, item_ids)) = self.last_bump.take() else {
+                self.status = "Nothing to undo".to_string();
+                return;
+            };
+            let request = RecallItems { item_ids, station_id: self.station.id.clone() };
+            match client.call::<_, Vec<types::Item>>(request).await {
+                Ok(_) => self.status = format!("{} brought back", label),
+                Err(e) => self.status = format!("Bringing back failed: {}", e),
+            }
+        }
+
+        /// Draws the board above the prompt. Only after an input the prompt is drawn anew, otherwise the
+        /// cursor goes back to where it was, so input typed so far stays on screen
+        fn draw(&self, with_prompt: bool) {
+            let now = now();
+            let mut lines = vec![
+                format!(
+                    "\x1b[1m{}\x1b[0m  [No.] pass on  [No.No.] pass on an item  [u] undo  [r] reload  [q] quit",
+                    self.station.name
+                ),
+                String::new(),
+            ];
+
+            let groups = self.groups();
+            if groups.is_empty() {
+                lines.push("  No open orders".to_string());
+            }
+            for (position, (order_id, items)) in groups.iter().enumerate() {
+                let order = self.orders.get(order_id);
+                lines.push(format!(
+                    "\x1b[1m{:>3}) {} {}\x1b[0m{}{}",
+                    position + 1,
+                    order.map(|o| o.number_label()).unwrap_or_default(),
+                    pickup_name(order_id, order),
+                    order.map(|o| format!("  waiting {}", format_duration(now - o.created_at))).unwrap_or_default(),
+                    if order.is_some_and(|o| o.rush) { "  \x1b[31mRUSH\x1b[0m" } else { "" },
+                ));
+                for (index, item) in items.iter().enumerate() {
+                    let position = format!("{}.{}", position + 1, index + 1);
+                    lines.push(format!("  {:>7}  {}x {}", position, item.quantity, self.product_name(&item.product_id)));
+                }
+            }
+
+            // The last two lines are kept for the status and the prompt
+            let rows = terminal_rows().max(4) - 2;
+            if lines.len() > rows {
+                lines.truncate(rows - 1);
+                lines.push("  ...".to_string());
+            }
+            lines.resize(rows, String::new());
+            lines.push(self.status.clone());
+
+            let mut out = String::from(if with_prompt { "\x1b[2J\x1b[H" } else { "\x1b7\x1b[H" });
+            for line in lines {
+                out.push_str(&line);
+                out.push_str("\x1b[K\n");
+            }
+            out.push_str(if with_prompt { "> " } else { "\x1b8" });
+            print!("{}", out);
+            let _ = std::io::stdout().flush();
+        }
+    }
+
+    /// Connects to the live feed and announces the station and the event it works for
+    async fn subscribe(client: &Client, board: &Board) -> Result<Feed, String> {
+        let (mut socket, _) = connect_async(client.websocket_url().as_str()).await.map_err(|e| e.to_string())?;
+        let subscription = GenericWebSocketMessage {
+            resource_type: EVENT_SUBSCRIPTION.to_string(),
+            message: serde_json::json!(board.event),
+        };
+        let presence = GenericWebSocketMessage {
+            resource_type: STATION_PRESENCE.to_string(),
+            message: serde_json::json!(board.station.id),
+        };
+        for message in [subscription, presence] {
+            if let Ok(json) = serde_json::to_string(&message) {
+                socket
+                    .send(tungstenite::Message::Text(json.into()))
+                    .await
+                    .map_err(|e| e.to_string())?;
+            }
+        }
+        Ok(socket)
+    }
+
+    /// Next message of the live feed, waits forever while disconnected
+    async fn next_message(feed: &mut Option<Feed>) -> Option<Result<tungstenite::Message, tungstenite::Error>> {
+        match feed {
+            Some(socket) => socket.next().await,
+            None => std::future::pending().await,
+        }
+    }
+
+    pub async fn run(server: String) {
+        let mut client = Client::new(server);
+        let mut lines = BufReader::new(tokio::io::stdin()).lines();
+
+        let Some(user) = sign_in(&mut client, &mut lines).await else {
+            return;
+        };
+        println!("Logged in as {}", user.email);
+        let Some(station) = pick_station(&mut client, &mut lines).await else {
+            return;
+        };
+        let Some(event) = pick_event(&mut client, &mut lines).await else {
+            return;
+        };
+
+        let mut board = Board {
+            station,
+            event,
+            items: Vec::new(),
+            orders: HashMap::new(),
+            products: Vec::new(),
+            last_bump: None,
+            status: "Connecting...".to_string(),
+        };
+        board.draw(true);
+
+        let mut feed = None;
+        let reconnect = tokio::time::sleep(Duration::ZERO);
+        tokio::pin!(reconnect);
+        let mut redraw = tokio::time::interval(Duration::from_secs(REDRAW_SECS));
+        loop {
+            let mut with_prompt = false;
+            tokio::select! {
+                line = lines.next_line() => {
+                    let Ok(Some(line)) = line else { return };
+                    // Enter moved the cursor below the prompt, nothing is left half typed
+                    with_prompt = true;
+                    match line.trim() {
+                        "q" => return,
+                        "u" => board.undo(&mut client).await,
+                        "r" => board.reload(&mut client).await,
+                        "" => {}
+                        input => match parse_position(input) {
+                            Some((position, None)) => board.bump(&mut client, position).await,
+                            Some((position, Some(index))) => board.bump_item(&mut client, position, index).await,
+                            None => board.status = format!("Unknown input '{}'", input),
+                        },
+                    }
+                }
+                message = next_message(&mut feed) => match message {
+                    Some(Ok(tungstenite::Message::Text(text))) => {
+                        if let Ok(generic) = serde_json::from_str::<GenericWebSocketMessage>(text.as_str()) {
+                            board.handle(generic);
+                        }
+                    }
+                    Some(Ok(_)) => continue,
+                    Some(Err(_)) | None => {
+                        feed = None;
+                        board.status = format!("Connection to the server lost, reconnecting in {}s", RECONNECT_DELAY.as_secs());
+                        reconnect.as_mut().reset(tokio::time::Instant::now() + RECONNECT_DELAY);
+                    }
+                },
+                _ = &mut reconnect, if feed.is_none() => match subscribe(&client, &board).await {
+                    Ok(socket) => {
+                        feed = Some(socket);
+                        // Changes may have been missed while disconnected, so everything is loaded again
+                        board.status.clear();
+                        board.reload(&mut client).await;
+                    }
+                    Err(e) => {
+                        board.status = format!(
+                            "No connection to the live feed: {}, retrying in {}s",
+                            e,
+                            RECONNECT_DELAY.as_secs()
+                        );
+                        reconnect.as_mut().reset(tokio::time::Instant::now() + RECONNECT_DELAY);
+                    }
+                },
+                _ = redraw.tick() => {}
+            }
+            board.draw(with_prompt);
+        }
+    }
+}
+
+#[cfg(feature = "ssr")]
+#[tokio::main]
+async fn main() {
+    let server = std::env::args().nth(1).unwrap_or_else(|| "http://127.0.0.1:3000".to_string());
+    tui::run(server).await;
+}
+
+#[cfg(not(feature = "ssr"))]
+pub fn main() {
+    // The terminal client uses the server side HTTP and WebSocket dependencies
+}
@@ -0,0 +1,92 @@
+use crate::common::types::Item;
+
+/// Pending units of one product at a station, summed over all orders
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProductBatch {
+    pub product_id: String,
+    pub quantity: u32,
+    pub orders: usize, // Number of orders waiting for the product
+}
+
+/// Sums the pending items per product, the largest batch first
+pub fn aggregate(items: &[Item]) -> Vec<ProductBatch> {
+    let mut batches: Vec<(ProductBatch, Vec<&str>)> = Vec::new();
+    for item in items {
+        let order_id = item.order_id.as_deref().unwrap_or_default();
+        match batches.iter_mut().find(|(batch, _)| batch.product_id == item.product_id) {
+            Some((batch, order_ids)) => {
+                batch.quantity += item.quantity;
+                if !order_ids.contains(&order_id) {
+                    order_ids.push(order_id);
+                    batch.orders += 1;
+                }
+            }
+            None => batches.push((
+                ProductBatch {
+                    product_id: item.product_id.clone(),
+                    quantity: item.quantity,
+                    orders: 1,
+                },
+                vec![order_id],
+            )),
+        }
+    }
+
+    let mut batches: Vec<ProductBatch> = batches.into_iter().map(|(batch, _)| batch).collect();
+    batches.sort_by(|a, b| b.quantity.cmp(&a.quantity).then_with(|| a.product_id.cmp(&b.product_id)));
+    batches
+}
+
+/// Distributes `units` over items in the given order, returning how many units each item gives up.
+/// An item is only partially taken when the units run out on it.
+pub fn allocate(quantities: &[u32], units: u32) -> Vec<u32> {
+    let mut remaining = units;
+    quantities
+        .iter()
+        .map(|quantity| {
+            let taken = (*quantity).min(remaining);
+            remaining -= taken;
+            taken
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::types::OrderStatus;
+
+    fn item(order_id: &str, product_id: &str, quantity: u32) -> Item {
+        Item {
+            id: format!("{}-{}", order_id, product_id),
+            order_id: Some(order_id.to_string()),
+            product_id: product_id.to_string(),
+            quantity,
+            price: 3.0,
+            status: OrderStatus::Ordered,
+            event: None,
+        }
+    }
+
+    #[test]
+    fn test_aggregate_by_product() {
+        let items = vec![
+            item("a", "steak", 2),
+            item("a", "wurst", 4),
+            item("b", "wurst", 10),
+            item("c", "steak", 4),
+        ];
+
+        let batches = aggregate(&items);
+
+        assert_eq!(batches[0], ProductBatch { product_id: "wurst".to_string(), quantity: 14, orders: 2 });
+        assert_eq!(batches[1], ProductBatch { product_id: "steak".to_string(), quantity: 6, orders: 2 });
+    }
+
+    #[test]
+    fn test_allocate_oldest_first() {
+        assert_eq!(allocate(&[2, 3, 4], 4), vec![2, 2, 0]);
+        assert_eq!(allocate(&[2, 3], 10), vec![2, 3]);
+        assert_eq!(allocate(&[2, 3], 0), vec![0, 0]);
+    }
+}
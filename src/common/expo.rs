@@ -0,0 +1,97 @@
+use crate::common::types::{Item, OrderStatus, Station};
+
+/// An order as seen by an expo station
+#[derive(Debug, Clone)]
+pub struct ExpoOrder {
+    pub order_id: String,
+    /// Items still to be handed out, cancelled and already handed out items are left out
+    pub items: Vec<Item>,
+    /// Number of items that reached one of the station's input statuses
+    pub ready: usize,
+}
+
+impl ExpoOrder {
+    /// Everything for the order is ready, so it can be handed out in one go
+    pub fn is_ready(&self) -> bool {
+        !self.items.is_empty() && self.ready == self.items.len()
+    }
+}
+
+/// Groups items by order and counts how many are ready at the expo station.
+/// Orders without any ready item are left out, they have nothing to consolidate yet.
+pub fn consolidate(items: &[Item], station: &Station) -> Vec<ExpoOrder> {
+    let mut orders: Vec<ExpoOrder> = Vec::new();
+    for item in items {
+        if item.status == OrderStatus::Cancelled || item.status == station.output_status {
+            continue;
+        }
+        let Some(order_id) = &item.order_id else {
+            continue;
+        };
+        let ready = usize::from(station.input_statuses.contains(&item.status));
+        match orders.iter_mut().find(|o| &o.order_id == order_id) {
+            Some(order) => {
+                order.items.push(item.clone());
+                order.ready += ready;
+            }
+            None => orders.push(ExpoOrder {
+                order_id: order_id.clone(),
+                items: vec![item.clone()],
+                ready,
+            }),
+        }
+    }
+    orders.retain(|order| order.ready > 0);
+    orders
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn expo() -> Station {
+        Station {
+            id: "expo".to_string(),
+            name: "Expo".to_string(),
+            category_ids: Vec::new(),
+            input_statuses: vec![OrderStatus::Ready],
+            output_status: OrderStatus::Completed,
+            expo: true,
+            ..Default::default()
+        }
+    }
+
+    fn item(id: &str, order_id: &str, status: OrderStatus) -> Item {
+        Item {
+            id: id.to_string(),
+            order_id: Some(order_id.to_string()),
+            product_id: "wurst".to_string(),
+            quantity: 1,
+            price: 3.0,
+            status,
+            event: None,
+        }
+    }
+
+    #[test]
+    fn test_consolidate_ready_and_partial_orders() {
+        let items = vec![
+            item("fries", "guenther", OrderStatus::Ready),
+            item("drink", "guenther", OrderStatus::Ready),
+            item("cancelled", "guenther", OrderStatus::Cancelled),
+            item("wurst", "anna", OrderStatus::Ready),
+            item("steak", "anna", OrderStatus::Ordered),
+            item("beer", "karl", OrderStatus::Ordered),
+            item("done", "eva", OrderStatus::Completed),
+        ];
+
+        let orders = consolidate(&items, &expo());
+
+        assert_eq!(orders.len(), 2, "Orders without ready items are not shown");
+        assert_eq!(orders[0].order_id, "guenther");
+        assert!(orders[0].is_ready(), "Cancelled items don't hold an order back");
+        assert_eq!(orders[1].order_id, "anna");
+        assert!(!orders[1].is_ready());
+        assert_eq!(orders[1].ready, 1);
+    }
+}
@@ -1,10 +1,12 @@
 use std::collections::hash_map::DefaultHasher;
 use std::hash::{Hash, Hasher};
 
-/// Fun German first names - memorable but not too long
+use crate::common::types::{NamePool, Order};
+
+/// Fun German first names - memorable but not too long, and none sounding like another when called out
 const GERMAN_NAMES: &[&str] = &[
     "Günther", "Brunhilde", "Wolfgang", "Gisela", "Friedrich", "Lieselotte",
-    "Siegfried", "Brunhild", "Dietrich", "Edeltraud", "Gottfried", "Waltraud",
+    "Siegfried", "Dietrich", "Edeltraud", "Gottfried", "Waltraud",
     "Bernhard", "Kunigunde", "Rüdiger", "Elfriede", "Adalbert", "Mechthild",
     "Willibald", "Roswitha", "Reinhard", "Hannelore", "Gerhard", "Ingeborg",
     "Manfred", "Christa", "Dieter", "Ursula", "Herbert", "Brigitte",
@@ -13,11 +15,86 @@ const GERMAN_NAMES: &[&str] = &[
     "Horst", "Karin", "Willi", "Ingrid", "Ernst", "Gabriele", "Rolf", "Martina",
     "Heinz", "Susanne", "Jürgen", "Birgit", "Bernd", "Ulrike", "Uwe", "Silke",
     "Lothar", "Cornelia", "Norbert", "Renate", "Joachim", "Heike", "Volker", "Ute",
-    "Günter", "Angelika", "Helmut", "Christine", "Werner", "Barbara", "Georg", "Elisabeth",
-    "Wulfgang", "Brunhildegard", "Siegbert", "Irmtraud", "Gottlob", "Adelheid",
-    "Hubertus", "Walpurgis", "Balduin", "Kunigundis", "Friedhelm", "Mechthildis"
+    "Angelika", "Helmut", "Christine", "Werner", "Barbara", "Georg", "Elisabeth",
+    "Siegbert", "Irmtraud", "Gottlob", "Adelheid",
+    "Hubertus", "Walpurgis", "Balduin", "Friedhelm"
+];
+
+/// Animals, easy to call out and to recognise from afar
+const ANIMALS: &[&str] = &[
+    "Igel", "Fuchs", "Dachs", "Biber", "Otter", "Luchs", "Wolf", "Bär",
+    "Hase", "Reh", "Hirsch", "Eule", "Uhu", "Specht", "Rabe", "Storch",
+    "Schwan", "Ente", "Gans", "Frosch", "Kröte", "Molch", "Eichhörnchen", "Maulwurf",
+    "Marder", "Wiesel", "Hamster", "Maus", "Pinguin", "Robbe", "Wal", "Delfin",
+    "Hai", "Krake", "Qualle", "Seestern", "Hummel", "Biene", "Käfer", "Falter",
+    "Lama", "Alpaka", "Kamel", "Zebra", "Giraffe", "Nashorn", "Nilpferd", "Elefant",
+    "Löwe", "Tiger", "Panther", "Gepard", "Koala", "Känguru", "Panda", "Faultier",
+];
+
+/// Colours, also understood by guests who don't speak German
+const COLOURS: &[&str] = &[
+    "Rot", "Blau", "Grün", "Gelb", "Orange", "Lila", "Rosa", "Türkis",
+    "Braun", "Grau", "Schwarz", "Weiß", "Gold", "Silber", "Bronze", "Beige",
+    "Petrol", "Oliv", "Mint", "Flieder", "Bordeaux", "Koralle", "Ocker", "Magenta",
 ];
 
+/// Names an event's pickup names are drawn from
+pub fn pool_names(pool: NamePool) -> &'static [&'static str] {
+    match pool {
+        NamePool::GermanNames => GERMAN_NAMES,
+        NamePool::Animals => ANIMALS,
+        NamePool::Colours => COLOURS,
+    }
+}
+
+/// How a name sounds when called out at the counter, umlauts lose their dots, `th` is spoken as `t`
+/// and endings like `-is` or `-e` are easily missed, so `Günter` sounds like `Günther`
+fn called_out(name: &str) -> String {
+    let name = name.to_lowercase().replace('ä', "a").replace('ö', "o").replace('ü', "u").replace("th", "t");
+    name.strip_suffix("is").or_else(|| name.strip_suffix('e')).unwrap_or(&name).to_string()
+}
+
+fn sounds_taken(name: &str, taken: &[String]) -> bool {
+    let name = called_out(name);
+    taken.iter().any(|t| called_out(t) == name)
+}
+
+fn hash_index(order_id: &str, len: usize) -> usize {
+    let mut hasher = DefaultHasher::new();
+    order_id.hash(&mut hasher);
+    (hasher.finish() as usize) % len
+}
+
+/// Picks a pickup name that none of the `taken` names clashes with or sounds like
+///
+/// Starts at the name the order id hashes to and walks the pool from there, once the
+/// whole pool is in use the hashed name gets a number appended, e.g. `Igel 2`.
+pub fn unique_name(pool: NamePool, order_id: &str, taken: &[String]) -> String {
+    let names = pool_names(pool);
+    let start = hash_index(order_id, names.len());
+    let free = names
+        .iter()
+        .cycle()
+        .skip(start)
+        .take(names.len())
+        .find(|name| !sounds_taken(name, taken));
+    if let Some(name) = free {
+        return name.to_string();
+    }
+    (2..)
+        .map(|n| format!("{} {}", names[start], n))
+        .find(|name| !sounds_taken(name, taken))
+        .unwrap_or_default()
+}
+
+/// Name an order is called out with, orders from before names were stored fall back to the hashed name
+pub fn pickup_name(order_id: &str, order: Option<&Order>) -> String {
+    order
+        .map(|order| order.name.clone())
+        .filter(|name| !name.is_empty())
+        .unwrap_or_else(|| generate_german_name(order_id))
+}
+
 /// Generates a deterministic German first name from an order ID
 /// 
 /// The same order ID will always generate the same German name across all devices
@@ -102,4 +179,43 @@ mod tests {
         println!("Generated {} unique names from 20 different order IDs", names_generated.len());
         assert!(names_generated.len() > 1, "Should generate multiple different names");
     }
+
+    #[test]
+    fn test_unique_name_skips_taken_names() {
+        let first = unique_name(NamePool::Animals, "order-1", &[]);
+        let second = unique_name(NamePool::Animals, "order-1", &[first.clone()]);
+        assert_ne!(first, second, "A taken name is never handed out twice");
+
+        let all: Vec<String> = ANIMALS.iter().map(|name| name.to_string()).collect();
+        let numbered = unique_name(NamePool::Animals, "order-1", &all);
+        assert_eq!(numbered, format!("{} 2", first), "A full pool falls back to numbered names");
+        let mut taken = all.clone();
+        taken.push(numbered);
+        assert_eq!(unique_name(NamePool::Animals, "order-1", &taken), format!("{} 3", first));
+    }
+
+    #[test]
+    fn test_unique_name_skips_names_that_sound_taken() {
+        let taken = vec!["Gunter".to_string(), "Brunhild".to_string()];
+        for i in 0..100 {
+            let name = unique_name(NamePool::GermanNames, &format!("order-{}", i), &taken);
+            assert_ne!(name, "Günther", "Günther sounds like the taken Gunter");
+            assert_ne!(name, "Brunhilde", "Brunhilde sounds like the taken Brunhild");
+        }
+    }
+
+    #[test]
+    fn test_pools_have_no_names_that_sound_alike() {
+        for pool in NamePool::ALL {
+            let mut seen = std::collections::HashMap::new();
+            for name in pool_names(pool) {
+                if let Some(other) = seen.insert(called_out(name), name) {
+                    panic!("{} and {} sound alike when called out", other, name);
+                }
+            }
+        }
+        assert_eq!(called_out("Günther"), called_out("Günter"));
+        assert_eq!(called_out("Kunigundis"), called_out("Kunigunde"));
+        assert_eq!(called_out("Mechthildis"), called_out("Mechthild"));
+    }
 }
\ No newline at end of file
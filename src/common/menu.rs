@@ -0,0 +1,80 @@
+use crate::common::types::{MenuEntry, Product};
+
+/// The products an event offers at its prices, in menu order. An empty menu offers every product
+/// at its list price, as events did before they had menus.
+pub fn menu_products(products: &[Product], menu: &[MenuEntry]) -> Vec<Product> {
+    if menu.is_empty() {
+        return products.to_vec();
+    }
+    let mut offered: Vec<(Option<i32>, usize, Product)> = products
+        .iter()
+        .enumerate()
+        .filter_map(|(index, product)| {
+            let entry = menu.iter().find(|entry| entry.product_id == product.id)?;
+            let mut product = product.clone();
+            product.price = entry.price.unwrap_or(product.price);
+            Some((entry.sort, index, product))
+        })
+        .collect();
+    // Products without a position keep their list order after the positioned ones
+    offered.sort_by_key(|(sort, index, _)| (sort.is_none(), *sort, *index));
+    offered.into_iter().map(|(_, _, product)| product).collect()
+}
+
+/// Menu entries of products that still exist, so a copied menu doesn't point at deleted products
+pub fn existing_entries(menu: &[MenuEntry], products: &[Product]) -> Vec<MenuEntry> {
+    menu.iter()
+        .filter(|entry| products.iter().any(|product| product.id == entry.product_id))
+        .cloned()
+        .collect()
+}
+
+/// Price of a product at an event, `None` if the event's menu doesn't offer it
+pub fn menu_price(product: &Product, menu: &[MenuEntry]) -> Option<f64> {
+    if menu.is_empty() {
+        return Some(product.price);
+    }
+    menu.iter()
+        .find(|entry| entry.product_id == product.id)
+        .map(|entry| entry.price.unwrap_or(product.price))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn product(id: &str, price: f64) -> Product {
+        Product {
+            id: id.to_string(),
+            name: id.to_string(),
+            category_id: "food".to_string(),
+            price,
+            active: true,
+            stock: None,
+        }
+    }
+
+    fn entry(product_id: &str, price: Option<f64>, sort: Option<i32>) -> MenuEntry {
+        MenuEntry {
+            product_id: product_id.to_string(),
+            price,
+            sort,
+        }
+    }
+
+    #[test]
+    fn test_menu_selects_prices_and_orders_products() {
+        let products = vec![product("wurst", 3.5), product("pommes", 2.5), product("bier", 3.9), product("cola", 2.0)];
+        let menu = vec![entry("cola", None, None), entry("bier", Some(4.5), Some(2)), entry("wurst", None, Some(1))];
+
+        let offered = menu_products(&products, &menu);
+
+        let ids: Vec<&str> = offered.iter().map(|p| p.id.as_str()).collect();
+        assert_eq!(ids, vec!["wurst", "bier", "cola"]);
+        assert_eq!(offered[1].price, 4.5);
+        assert_eq!(menu_price(&products[1], &menu), None);
+        assert_eq!(menu_price(&products[2], &menu), Some(4.5));
+        assert_eq!(menu_products(&products, &[]).len(), 4);
+        assert_eq!(existing_entries(&menu, &products[..2]).len(), 1);
+    }
+}
@@ -0,0 +1,252 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::common::types::{
+    Item, ItemStatusChange, OrderStatus, Product, ProductTiming, Station, StationMetrics,
+};
+
+/// Length of one throughput window
+pub const WINDOW_SECS: i64 = 15 * 60;
+/// Number of throughput windows reported, covering the last two hours
+pub const WINDOW_COUNT: usize = 8;
+/// Number of slowest products reported per station
+pub const SLOWEST_PRODUCTS: usize = 3;
+
+/// Nearest-rank percentile of an ascending list
+pub fn percentile(sorted: &[i64], percent: usize) -> Option<i64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = (percent * sorted.len()).div_ceil(100).max(1);
+    sorted.get(rank - 1).copied()
+}
+
+/// Formats a duration as minutes and seconds, e.g. `4m 05s`
+pub fn format_duration(secs: i64) -> String {
+    format!("{}m {:02}s", secs / 60, secs % 60)
+}
+
+/// Seconds each item took from being ordered until it was ready, items that skip `Ready` count when they complete
+fn prep_times(history: &[ItemStatusChange]) -> HashMap<&str, i64> {
+    let mut ordered_at: HashMap<&str, i64> = HashMap::new();
+    let mut ready_at: HashMap<&str, i64> = HashMap::new();
+
+    for change in history {
+        let item_id = change.item_id.as_str();
+        if change.to == OrderStatus::Ordered {
+            let at = ordered_at.entry(item_id).or_insert(change.changed_at);
+            *at = (*at).min(change.changed_at);
+        }
+        if matches!(change.to, OrderStatus::Ready | OrderStatus::Completed) {
+            let at = ready_at.entry(item_id).or_insert(change.changed_at);
+            *at = (*at).min(change.changed_at);
+        }
+    }
+
+    ready_at
+        .into_iter()
+        .filter_map(|(item_id, ready)| {
+            let ordered = ordered_at.get(item_id)?;
+            Some((item_id, (ready - ordered).max(0)))
+        })
+        .collect()
+}
+
+/// Computes throughput, prep times, backlog and the slowest products of every station
+pub fn compute(
+    stations: &[Station],
+    products: &[Product],
+    items: &[Item],
+    history: &[ItemStatusChange],
+    now: i64,
+) -> Vec<StationMetrics> {
+    let items_by_id: HashMap<&str, &Item> = items.iter().map(|item| (item.id.as_str(), item)).collect();
+    let products_by_id: HashMap<&str, &Product> =
+        products.iter().map(|product| (product.id.as_str(), product)).collect();
+    let prep_times = prep_times(history);
+
+    stations
+        .iter()
+        .map(|station| {
+            let mut processed = vec![0; WINDOW_COUNT];
+            let mut handled: HashSet<&str> = HashSet::new();
+            // An item recalled and moved on again counts once, in the window of its latest move
+            let mut moved_on_at: HashMap<&str, i64> = HashMap::new();
+
+            for change in history.iter().filter(|c| c.station_id.as_deref() == Some(station.id.as_str())) {
+                handled.insert(change.item_id.as_str());
+                if change.to == station.output_status {
+                    let at = moved_on_at.entry(change.item_id.as_str()).or_insert(change.changed_at);
+                    *at = (*at).max(change.changed_at);
+                }
+            }
+            for (item_id, at) in moved_on_at {
+                let age = now - at;
+                let window = (age.max(0) / WINDOW_SECS) as usize;
+                if window < WINDOW_COUNT {
+                    let quantity = items_by_id.get(item_id).map(|item| item.quantity).unwrap_or(1);
+                    processed[WINDOW_COUNT - 1 - window] += quantity;
+                }
+            }
+
+            let mut waits: Vec<i64> = Vec::new();
+            let mut waits_by_product: HashMap<&str, Vec<i64>> = HashMap::new();
+            for item_id in &handled {
+                let Some(wait) = prep_times.get(item_id) else {
+                    continue;
+                };
+                waits.push(*wait);
+                if let Some(item) = items_by_id.get(item_id) {
+                    waits_by_product.entry(item.product_id.as_str()).or_default().push(*wait);
+                }
+            }
+            waits.sort_unstable();
+
+            let mut slowest_products: Vec<ProductTiming> = waits_by_product
+                .into_iter()
+                .map(|(product_id, mut waits)| {
+                    waits.sort_unstable();
+                    ProductTiming {
+                        product_id: product_id.to_string(),
+                        product_name: products_by_id
+                            .get(product_id)
+                            .map(|p| p.name.clone())
+                            .unwrap_or_else(|| product_id.to_string()),
+                        median_secs: percentile(&waits, 50).unwrap_or_default(),
+                        count: waits.len(),
+                    }
+                })
+                .collect();
+            slowest_products.sort_by(|a, b| {
+                b.median_secs
+                    .cmp(&a.median_secs)
+                    .then_with(|| a.product_name.cmp(&b.product_name))
+            });
+            slowest_products.truncate(SLOWEST_PRODUCTS);
+
+            let backlog = items
+                .iter()
+                .filter(|item| station.input_statuses.contains(&item.status))
+                .filter(|item| {
+                    products_by_id
+                        .get(item.product_id.as_str())
+                        .is_some_and(|p| station.handles_category(&p.category_id))
+                })
+                .map(|item| item.quantity)
+                .sum();
+
+            StationMetrics {
+                station_id: station.id.clone(),
+                station_name: station.name.clone(),
+                processed,
+                median_wait_secs: percentile(&waits, 50),
+                p90_wait_secs: percentile(&waits, 90),
+                backlog,
+                slowest_products,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOW: i64 = 10_000;
+
+    fn station() -> Station {
+        Station {
+            id: "grill".to_string(),
+            name: "Grill".to_string(),
+            category_ids: vec!["food".to_string()],
+            ..Default::default()
+        }
+    }
+
+    fn product(id: &str) -> Product {
+        Product {
+            id: id.to_string(),
+            name: id.to_string(),
+            category_id: "food".to_string(),
+            price: 3.0,
+            active: true,
+            stock: None,
+        }
+    }
+
+    fn item(id: &str, product_id: &str, quantity: u32, status: OrderStatus) -> Item {
+        Item {
+            id: id.to_string(),
+            order_id: Some("order".to_string()),
+            product_id: product_id.to_string(),
+            quantity,
+            price: 3.0,
+            status,
+            event: None,
+        }
+    }
+
+    fn change(item_id: &str, to: OrderStatus, changed_at: i64, station_id: Option<&str>) -> ItemStatusChange {
+        ItemStatusChange {
+            id: format!("{}-{}", item_id, changed_at),
+            item_id: item_id.to_string(),
+            order_id: "order".to_string(),
+            from: None,
+            to,
+            changed_at,
+            user_id: None,
+            station_id: station_id.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let values: Vec<i64> = (1..=10).collect();
+        assert_eq!(percentile(&values, 50), Some(5));
+        assert_eq!(percentile(&values, 90), Some(9));
+        assert_eq!(percentile(&[42], 90), Some(42));
+        assert_eq!(percentile(&[], 50), None);
+    }
+
+    #[test]
+    fn test_station_metrics() {
+        let items = vec![
+            item("a", "wurst", 2, OrderStatus::Ready),
+            item("b", "steak", 1, OrderStatus::Ready),
+            item("c", "wurst", 3, OrderStatus::Ordered),
+        ];
+        let history = vec![
+            change("a", OrderStatus::Ordered, NOW - 600, None),
+            change("a", OrderStatus::Ready, NOW - 300, Some("grill")),
+            change("b", OrderStatus::Ordered, NOW - 3000, None),
+            change("b", OrderStatus::Ready, NOW - 1200, Some("grill")),
+            change("c", OrderStatus::Ordered, NOW - 60, None),
+        ];
+
+        let metrics = compute(&[station()], &[product("wurst"), product("steak")], &items, &history, NOW);
+        let grill = &metrics[0];
+
+        assert_eq!(grill.processed[WINDOW_COUNT - 1], 2, "Item a finished in the current window");
+        assert_eq!(grill.processed[WINDOW_COUNT - 2], 1, "Item b finished in the window before");
+        assert_eq!(grill.median_wait_secs, Some(300));
+        assert_eq!(grill.p90_wait_secs, Some(1800));
+        assert_eq!(grill.backlog, 3);
+        assert_eq!(grill.slowest_products[0].product_id, "steak");
+        assert_eq!(grill.slowest_products[1].median_secs, 300);
+    }
+
+    #[test]
+    fn test_recalled_items_count_once() {
+        let items = vec![item("a", "wurst", 2, OrderStatus::Ready)];
+        let history = vec![
+            change("a", OrderStatus::Ordered, NOW - 600, None),
+            change("a", OrderStatus::Ready, NOW - 300, Some("grill")),
+            change("a", OrderStatus::Ordered, NOW - 240, Some("grill")),
+            change("a", OrderStatus::Ready, NOW - 120, Some("grill")),
+        ];
+
+        let metrics = compute(&[station()], &[product("wurst")], &items, &history, NOW);
+
+        assert_eq!(metrics[0].processed.iter().sum::<u32>(), 2, "The recalled item is processed once");
+        assert_eq!(metrics[0].processed[WINDOW_COUNT - 1], 2);
+    }
+}
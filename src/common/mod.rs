@@ -2,4 +2,14 @@ pub mod types;
 pub mod requests;
 pub mod errors;
 pub mod resource_type;
-pub mod german_names;
\ No newline at end of file
+pub mod german_names;
+pub mod pipeline;
+pub mod metrics;
+pub mod batch;
+pub mod expo;
+pub mod station;
+pub mod pickup;
+pub mod rate_limit;
+pub mod receipt;
+pub mod shortcuts;
+pub mod menu;
\ No newline at end of file
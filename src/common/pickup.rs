@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+
+use crate::common::german_names::pickup_name;
+use crate::common::types::{Item, Order, OrderStatus};
+
+/// Column an order is shown in on the pickup board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickupStage {
+    Preparing,
+    Ready,
+}
+
+/// An order on the pickup board, only carrying what customers may see
+#[derive(Debug, Clone, PartialEq)]
+pub struct PickupEntry {
+    pub order_id: String,
+    pub name: String,
+    pub number: Option<String>, // e.g. `#042`, unknown until the order is loaded
+    pub stage: PickupStage,
+}
+
+/// Stage of an order from the statuses of its items, `None` once everything was handed out or cancelled
+pub fn stage<'a>(statuses: impl IntoIterator<Item = &'a OrderStatus>) -> Option<PickupStage> {
+    let open: Vec<&OrderStatus> = statuses.into_iter().filter(|status| !status.is_terminal()).collect();
+    if open.is_empty() {
+        None
+    } else if open.iter().all(|status| **status == OrderStatus::Ready) {
+        Some(PickupStage::Ready)
+    } else {
+        Some(PickupStage::Preparing)
+    }
+}
+
+/// Pickup board entries of all open orders, in the order their first item arrived
+pub fn board(items: &[Item], orders: &HashMap<String, Order>) -> Vec<PickupEntry> {
+    let mut grouped: Vec<(&str, Vec<&Item>)> = Vec::new();
+    for item in items {
+        let Some(order_id) = item.order_id.as_deref() else {
+            continue;
+        };
+        match grouped.iter_mut().find(|(id, _)| *id == order_id) {
+            Some((_, order_items)) => order_items.push(item),
+            None => grouped.push((order_id, vec![item])),
+        }
+    }
+
+    grouped
+        .into_iter()
+        .filter_map(|(order_id, order_items)| {
+            stage(order_items.iter().map(|item| &item.status)).map(|stage| PickupEntry {
+                order_id: order_id.to_string(),
+                name: pickup_name(order_id, orders.get(order_id)),
+                number: orders.get(order_id).map(|order| order.number_label()),
+                stage,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(order_id: &str, status: OrderStatus) -> Item {
+        Item {
+            id: format!("{}-{}", order_id, status),
+            order_id: Some(order_id.to_string()),
+            product_id: "wurst".to_string(),
+            quantity: 1,
+            price: 3.0,
+            status,
+            event: None,
+        }
+    }
+
+    #[test]
+    fn test_board_stages() {
+        let items = vec![
+            item("preparing", OrderStatus::Ready),
+            item("preparing", OrderStatus::Ordered),
+            item("ready", OrderStatus::Ready),
+            item("ready", OrderStatus::Cancelled),
+            item("handed-out", OrderStatus::Completed),
+            item("handed-out", OrderStatus::Cancelled),
+        ];
+
+        let orders = HashMap::from([(
+            "ready".to_string(),
+            Order { id: "ready".to_string(), created_at: 0, rush: false, name: "Igel".to_string(), number: 7, payment_method: Default::default() },
+        )]);
+
+        let board = board(&items, &orders);
+
+        assert_eq!(board.len(), 2, "Handed out orders leave the board");
+        assert_eq!(board[0].order_id, "preparing");
+        assert_eq!(board[0].stage, PickupStage::Preparing);
+        assert_eq!(board[1].stage, PickupStage::Ready);
+        assert_eq!(board[1].name, "Igel", "Stored names win over the hashed fallback");
+        assert_eq!(board[1].number.as_deref(), Some("#007"));
+    }
+}
@@ -0,0 +1,346 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::common::types::{Category, OrderStatus, Station};
+
+/// Status every item starts in once its order is placed
+pub const ENTRY_STATUS: OrderStatus = OrderStatus::Ordered;
+
+/// Whether items may stay in the status without a station picking them up: ready items wait for
+/// the customer at the counter, completed and cancelled ones are done
+pub fn is_sink(status: &OrderStatus) -> bool {
+    *status == OrderStatus::Ready || status.is_terminal()
+}
+
+/// One hop an item of a category can take through a station
+#[derive(Debug, Clone, PartialEq)]
+pub struct RouteEdge {
+    pub from: OrderStatus,
+    pub to: OrderStatus,
+    pub station_id: String,
+    pub station_name: String,
+}
+
+/// The routing graph of a single category
+#[derive(Debug, Clone)]
+pub struct CategoryRoute {
+    pub category: Category,
+    pub edges: Vec<RouteEdge>,
+    /// Statuses an item of this category can reach, in breadth-first order from the entry status
+    pub reachable: Vec<OrderStatus>,
+}
+
+impl CategoryRoute {
+    /// Number of hops from the entry status, `None` for unreachable statuses
+    pub fn depth(&self, status: &OrderStatus) -> Option<usize> {
+        let mut depth = HashMap::from([(&ENTRY_STATUS, 0)]);
+        for status in &self.reachable {
+            let current = depth[status];
+            for edge in self.edges.iter().filter(|e| e.from == *status) {
+                depth.entry(&edge.to).or_insert(current + 1);
+            }
+        }
+        depth.get(status).copied()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum PipelineIssue {
+    /// No station handles the category at all
+    UnroutedCategory { category: String },
+    /// Items reach a status that is no sink and that no station picks up
+    DeadEnd { category: String, status: OrderStatus },
+    /// A station waits for a status that items of the category never reach
+    UnreachableInput { category: String, station: String, status: OrderStatus },
+    /// Several stations pick up the same items
+    OverlappingStations { category: String, status: OrderStatus, stations: Vec<String> },
+    /// The output status is also an input status, so bumped items never leave the station
+    NoProgress { station: String },
+}
+
+impl PipelineIssue {
+    /// Errors leave items stuck, everything else is a configuration smell
+    pub fn is_error(&self) -> bool {
+        match self {
+            PipelineIssue::UnroutedCategory { .. } | PipelineIssue::NoProgress { .. } => true,
+            PipelineIssue::DeadEnd { status, .. } => *status == ENTRY_STATUS,
+            PipelineIssue::UnreachableInput { .. } | PipelineIssue::OverlappingStations { .. } => false,
+        }
+    }
+
+    pub fn message(&self) -> String {
+        match self {
+            PipelineIssue::UnroutedCategory { category } => {
+                format!("No station handles '{}'", category)
+            }
+            PipelineIssue::DeadEnd { category, status } => {
+                format!("'{}' items get stuck in {}, no station picks them up", category, status)
+            }
+            PipelineIssue::UnreachableInput { category, station, status } => {
+                format!("{} waits for {} '{}' items, which never reach that status", station, status, category)
+            }
+            PipelineIssue::OverlappingStations { category, status, stations } => {
+                format!("{} all pick up {} '{}' items", stations.join(", "), status, category)
+            }
+            PipelineIssue::NoProgress { station } => {
+                format!("{} outputs one of its own input statuses, items never leave it", station)
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PipelineReport {
+    pub routes: Vec<CategoryRoute>,
+    pub issues: Vec<PipelineIssue>,
+}
+
+impl PipelineReport {
+    pub fn has_errors(&self) -> bool {
+        self.issues.iter().any(PipelineIssue::is_error)
+    }
+}
+
+/// Builds the routing graph of every category and reports configurations that leave items stuck
+pub fn analyze(stations: &[Station], categories: &[Category]) -> PipelineReport {
+    let mut report = PipelineReport::default();
+
+    for station in stations {
+        if station.input_statuses.contains(&station.output_status) {
+            report.issues.push(PipelineIssue::NoProgress {
+                station: station.name.clone(),
+            });
+        }
+    }
+
+    for category in categories {
+        // Expo stations take every category, so they move items on without covering any category
+        let (expo, handling): (Vec<&Station>, Vec<&Station>) = stations
+            .iter()
+            .filter(|s| s.handles_category(&category.id))
+            .partition(|s| s.expo);
+        let route_edges = |stations: &[&Station]| -> Vec<RouteEdge> {
+            stations
+                .iter()
+                .flat_map(|station| {
+                    station.input_statuses.iter().map(|from| RouteEdge {
+                        from: from.clone(),
+                        to: station.output_status.clone(),
+                        station_id: station.id.clone(),
+                        station_name: station.name.clone(),
+                    })
+                })
+                .collect()
+        };
+        let mut edges = route_edges(&handling);
+        let expo_edges = route_edges(&expo);
+
+        // Breadth-first walk from the entry status
+        let mut reachable = vec![ENTRY_STATUS];
+        let mut seen = HashSet::from([&ENTRY_STATUS]);
+        let mut queue = VecDeque::from([&ENTRY_STATUS]);
+        while let Some(status) = queue.pop_front() {
+            for edge in edges.iter().chain(&expo_edges).filter(|e| e.from == *status) {
+                if seen.insert(&edge.to) {
+                    reachable.push(edge.to.clone());
+                    queue.push_back(&edge.to);
+                }
+            }
+        }
+
+        if handling.is_empty() {
+            report.issues.push(PipelineIssue::UnroutedCategory {
+                category: category.name.clone(),
+            });
+        } else {
+            for status in reachable.iter().filter(|s| !is_sink(s)) {
+                if !edges.iter().chain(&expo_edges).any(|e| e.from == *status && e.to != *status) {
+                    report.issues.push(PipelineIssue::DeadEnd {
+                        category: category.name.clone(),
+                        status: status.clone(),
+                    });
+                }
+            }
+        }
+
+        let mut consumers: Vec<(OrderStatus, Vec<String>)> = Vec::new();
+        for edge in &edges {
+            if !seen.contains(&edge.from) {
+                report.issues.push(PipelineIssue::UnreachableInput {
+                    category: category.name.clone(),
+                    station: edge.station_name.clone(),
+                    status: edge.from.clone(),
+                });
+            }
+            match consumers.iter_mut().find(|(status, _)| *status == edge.from) {
+                Some((_, names)) => names.push(edge.station_name.clone()),
+                None => consumers.push((edge.from.clone(), vec![edge.station_name.clone()])),
+            }
+        }
+        for (status, names) in consumers {
+            if names.len() > 1 {
+                report.issues.push(PipelineIssue::OverlappingStations {
+                    category: category.name.clone(),
+                    status,
+                    stations: names,
+                });
+            }
+        }
+
+        // Only the hops of expo stations that items of the category actually reach show in the graph
+        let reached: Vec<RouteEdge> = expo_edges.iter().filter(|e| seen.contains(&e.from)).cloned().collect();
+        edges.extend(reached);
+        report.routes.push(CategoryRoute {
+            category: category.clone(),
+            edges,
+            reachable,
+        });
+    }
+
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn category(id: &str) -> Category {
+        Category {
+            id: id.to_string(),
+            name: id.to_string(),
+            tax_rate: crate::common::types::DEFAULT_TAX_RATE,
+        }
+    }
+
+    fn station(name: &str, categories: &[&str], inputs: &[OrderStatus], output: OrderStatus) -> Station {
+        Station {
+            id: name.to_lowercase(),
+            name: name.to_string(),
+            category_ids: categories.iter().map(|c| c.to_string()).collect(),
+            input_statuses: inputs.to_vec(),
+            output_status: output,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_complete_pipeline_has_no_issues() {
+        let stations = vec![
+            station("Grill", &["food"], &[OrderStatus::Ordered], OrderStatus::Ready),
+            station("Counter", &["food"], &[OrderStatus::Ready], OrderStatus::Completed),
+        ];
+
+        let report = analyze(&stations, &[category("food")]);
+
+        assert!(report.issues.is_empty(), "Unexpected issues: {:?}", report.issues);
+        assert_eq!(
+            report.routes[0].reachable,
+            vec![OrderStatus::Ordered, OrderStatus::Ready, OrderStatus::Completed]
+        );
+        assert_eq!(report.routes[0].depth(&OrderStatus::Completed), Some(2));
+    }
+
+    #[test]
+    fn test_default_stations_have_no_issues() {
+        let stations = vec![
+            Station {
+                id: "grill".to_string(),
+                name: "Grill".to_string(),
+                category_ids: vec!["food".to_string()],
+                ..Default::default()
+            },
+            Station {
+                id: "bar".to_string(),
+                name: "Bar".to_string(),
+                category_ids: vec!["drinks".to_string()],
+                ..Default::default()
+            },
+        ];
+
+        let report = analyze(&stations, &[category("food"), category("drinks")]);
+
+        assert!(report.issues.is_empty(), "Ready items wait for pickup, not for a station: {:?}", report.issues);
+    }
+
+    #[test]
+    fn test_unrouted_category_and_dead_end() {
+        let plated = OrderStatus::Custom("Plated".to_string());
+        let stations = vec![station("Grill", &["food"], &[OrderStatus::Ordered], plated.clone())];
+
+        let report = analyze(&stations, &[category("food"), category("drinks")]);
+
+        assert!(report.issues.contains(&PipelineIssue::UnroutedCategory {
+            category: "drinks".to_string()
+        }));
+        let dead_end = PipelineIssue::DeadEnd {
+            category: "food".to_string(),
+            status: plated,
+        };
+        assert!(report.issues.contains(&dead_end));
+        assert!(!dead_end.is_error(), "Waiting in an intermediate status is only a warning");
+        assert!(report.has_errors());
+    }
+
+    #[test]
+    fn test_unreachable_input_and_overlap() {
+        let stations = vec![
+            station("Grill", &["food"], &[OrderStatus::Ordered], OrderStatus::Completed),
+            station("Fryer", &["food"], &[OrderStatus::Ordered, OrderStatus::Draft], OrderStatus::Completed),
+        ];
+
+        let report = analyze(&stations, &[category("food")]);
+
+        assert!(report.issues.contains(&PipelineIssue::UnreachableInput {
+            category: "food".to_string(),
+            station: "Fryer".to_string(),
+            status: OrderStatus::Draft,
+        }));
+        assert!(report.issues.contains(&PipelineIssue::OverlappingStations {
+            category: "food".to_string(),
+            status: OrderStatus::Ordered,
+            stations: vec!["Grill".to_string(), "Fryer".to_string()],
+        }));
+        assert!(!report.has_errors());
+    }
+
+    #[test]
+    fn test_station_without_progress() {
+        let stations = vec![station("Loop", &["food"], &[OrderStatus::Ordered], OrderStatus::Ordered)];
+
+        let report = analyze(&stations, &[category("food")]);
+
+        assert!(report.issues.contains(&PipelineIssue::NoProgress {
+            station: "Loop".to_string()
+        }));
+        assert!(report.issues.contains(&PipelineIssue::DeadEnd {
+            category: "food".to_string(),
+            status: OrderStatus::Ordered,
+        }));
+    }
+
+    #[test]
+    fn test_expo_station_covers_no_category() {
+        let expo = Station {
+            expo: true,
+            ..station("Expo", &[], &[OrderStatus::Ready], OrderStatus::Completed)
+        };
+        let stations = vec![
+            station("Grill", &["food"], &[OrderStatus::Ordered], OrderStatus::Ready),
+            station("Bar", &["drinks"], &[OrderStatus::Ordered], OrderStatus::Completed),
+            expo,
+        ];
+
+        let report = analyze(&stations, &[category("food"), category("drinks"), category("dessert")]);
+
+        assert_eq!(
+            report.issues,
+            vec![PipelineIssue::UnroutedCategory { category: "dessert".to_string() }],
+            "The expo station neither covers dessert nor waits for drinks that never get ready"
+        );
+        assert_eq!(
+            report.routes[0].reachable,
+            vec![OrderStatus::Ordered, OrderStatus::Ready, OrderStatus::Completed],
+            "Food is handed out at the expo station"
+        );
+        assert!(report.routes[1].edges.iter().all(|e| e.station_name != "Expo"));
+    }
+}
@@ -0,0 +1,80 @@
+use std::collections::HashMap;
+use std::net::IpAddr;
+
+/// Address a request is counted against. `X-Forwarded-For` is only believed when the request comes
+/// from a trusted reverse proxy, anybody else could send a different one with every request.
+/// Proxies append the address they saw, so the rightmost entry that isn't a trusted proxy is the client.
+pub fn client_address(peer: IpAddr, forwarded_for: Option<&str>, trusted_proxies: &[IpAddr]) -> IpAddr {
+    if !trusted_proxies.contains(&peer) {
+        return peer;
+    }
+    let mut client = peer;
+    for entry in forwarded_for.unwrap_or_default().rsplit(',') {
+        match entry.trim().parse::<IpAddr>() {
+            Ok(address) if trusted_proxies.contains(&address) => client = address,
+            Ok(address) => return address,
+            Err(_) => break,
+        }
+    }
+    client
+}
+
+/// Fixed window rate limiter, allowing `limit` requests per client within each window
+#[derive(Debug)]
+pub struct RateLimiter {
+    limit: u32,
+    window_secs: i64,
+    windows: HashMap<String, (i64, u32)>, // Client to window start and requests in it
+}
+
+impl RateLimiter {
+    pub fn new(limit: u32, window_secs: i64) -> Self {
+        Self {
+            limit,
+            window_secs,
+            windows: HashMap::new(),
+        }
+    }
+
+    /// Counts a request of the client, returning whether it is still within the limit
+    pub fn allow(&mut self, client: &str, now: i64) -> bool {
+        // Forget clients of past windows so the map doesn't grow forever
+        let window_secs = self.window_secs;
+        self.windows.retain(|_, (start, _)| now - *start < window_secs);
+
+        let (_, count) = self.windows.entry(client.to_string()).or_insert((now, 0));
+        if *count >= self.limit {
+            return false;
+        }
+        *count += 1;
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_limit_per_client_and_window() {
+        let mut limiter = RateLimiter::new(2, 60);
+
+        assert!(limiter.allow("a", 0));
+        assert!(limiter.allow("a", 10));
+        assert!(!limiter.allow("a", 20), "Third request in the window is rejected");
+        assert!(limiter.allow("b", 20), "Other clients have their own budget");
+        assert!(limiter.allow("a", 60), "A new window starts after a minute");
+    }
+
+    #[test]
+    fn test_forwarded_address_only_from_trusted_proxies() {
+        let ip = |address: &str| address.parse::<IpAddr>().unwrap();
+        let proxies = [ip("10.0.0.1"), ip("10.0.0.2")];
+
+        assert_eq!(client_address(ip("1.2.3.4"), Some("9.9.9.9"), &proxies), ip("1.2.3.4"));
+        assert_eq!(client_address(ip("10.0.0.1"), Some("9.9.9.9, 5.6.7.8"), &proxies), ip("5.6.7.8"));
+        assert_eq!(client_address(ip("10.0.0.1"), Some("5.6.7.8, 10.0.0.2"), &proxies), ip("5.6.7.8"));
+        assert_eq!(client_address(ip("10.0.0.1"), Some("9.9.9.9, junk"), &proxies), ip("10.0.0.1"));
+        assert_eq!(client_address(ip("10.0.0.1"), None, &proxies), ip("10.0.0.1"));
+    }
+}
@@ -0,0 +1,169 @@
+use serde::{Deserialize, Serialize};
+
+use crate::common::types::{Category, Item, OrderStatus, PaymentMethod, Product, DEFAULT_TAX_RATE};
+
+/// One product of a receipt, prices include VAT
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ReceiptLine {
+    pub name: String,
+    pub quantity: u32,
+    pub unit_price: f64,
+    pub tax_rate: f64, // Percent
+}
+
+impl ReceiptLine {
+    pub fn total(&self) -> f64 {
+        self.unit_price * self.quantity as f64
+    }
+}
+
+/// Lines of an order's items, cancelled items are not charged and left out
+pub fn receipt_lines(items: &[Item], products: &[Product], categories: &[Category]) -> Vec<ReceiptLine> {
+    items
+        .iter()
+        .filter(|item| item.status != OrderStatus::Cancelled)
+        .map(|item| {
+            let product = products.iter().find(|p| p.id == item.product_id);
+            ReceiptLine {
+                name: product.map(|p| p.name.clone()).unwrap_or_default(),
+                quantity: item.quantity,
+                unit_price: item.price,
+                tax_rate: product
+                    .and_then(|p| categories.iter().find(|c| c.id == p.category_id))
+                    .map(|c| c.tax_rate)
+                    .unwrap_or(DEFAULT_TAX_RATE),
+            }
+        })
+        .collect()
+}
+
+/// VAT contained in all lines of one rate
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TaxLine {
+    pub rate: f64,
+    pub gross: f64,
+    pub net: f64,
+    pub tax: f64,
+}
+
+/// Everything printed on the customer's receipt of an order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Receipt {
+    pub event_name: String,
+    pub order_id: String,
+    pub order_name: String,
+    pub number: String,  // e.g. `#042`
+    pub date: String,    // Local date and time the order was placed
+    pub lines: Vec<ReceiptLine>,
+    pub payment_method: PaymentMethod,
+    pub tracking_url: String,
+}
+
+fn round_cents(amount: f64) -> f64 {
+    (amount * 100.0).round() / 100.0
+}
+
+impl Receipt {
+    pub fn total(&self) -> f64 {
+        round_cents(self.lines.iter().map(|line| line.total()).sum())
+    }
+
+    /// VAT per rate, lowest rate first, with the tax taken out of the gross sums
+    pub fn tax_breakdown(&self) -> Vec<TaxLine> {
+        let mut breakdown: Vec<TaxLine> = Vec::new();
+        for line in &self.lines {
+            match breakdown.iter_mut().find(|tax| tax.rate == line.tax_rate) {
+                Some(tax) => tax.gross += line.total(),
+                None => breakdown.push(TaxLine {
+                    rate: line.tax_rate,
+                    gross: line.total(),
+                    net: 0.0,
+                    tax: 0.0,
+                }),
+            }
+        }
+        for line in breakdown.iter_mut() {
+            line.gross = round_cents(line.gross);
+            line.tax = round_cents(line.gross * line.rate / (100.0 + line.rate));
+            line.net = round_cents(line.gross - line.tax);
+        }
+        breakdown.sort_by(|a, b| a.rate.total_cmp(&b.rate));
+        breakdown
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(name: &str, quantity: u32, unit_price: f64, tax_rate: f64) -> ReceiptLine {
+        ReceiptLine {
+            name: name.to_string(),
+            quantity,
+            unit_price,
+            tax_rate,
+        }
+    }
+
+    #[test]
+    fn test_tax_breakdown_per_rate() {
+        let receipt = Receipt {
+            event_name: "Sommerfest".to_string(),
+            order_id: "order".to_string(),
+            order_name: "Igel".to_string(),
+            number: "#001".to_string(),
+            date: String::new(),
+            lines: vec![
+                line("Cola", 2, 2.5, 19.0),
+                line("Bratwurst", 1, 3.5, 7.0),
+                line("Bier", 1, 3.9, 19.0),
+            ],
+            payment_method: PaymentMethod::Card,
+            tracking_url: String::new(),
+        };
+
+        let breakdown = receipt.tax_breakdown();
+
+        assert_eq!(receipt.total(), 12.4);
+        assert_eq!(breakdown.len(), 2);
+        assert_eq!(breakdown[0], TaxLine { rate: 7.0, gross: 3.5, net: 3.27, tax: 0.23 });
+        assert_eq!(breakdown[1], TaxLine { rate: 19.0, gross: 8.9, net: 7.48, tax: 1.42 });
+    }
+
+    #[test]
+    fn test_cancelled_items_are_not_charged() {
+        let item = |id: &str, product_id: &str, status: OrderStatus| Item {
+            id: id.to_string(),
+            order_id: Some("order".to_string()),
+            product_id: product_id.to_string(),
+            quantity: 2,
+            price: 3.0,
+            status,
+            event: None,
+        };
+        let product = |id: &str| Product {
+            id: id.to_string(),
+            name: id.to_string(),
+            category_id: "food".to_string(),
+            price: 3.0,
+            active: true,
+            stock: None,
+        };
+        let categories = vec![Category {
+            id: "food".to_string(),
+            name: "Food".to_string(),
+            tax_rate: 7.0,
+        }];
+
+        let lines = receipt_lines(
+            &[
+                item("a", "Bratwurst", OrderStatus::Completed),
+                item("b", "Steak", OrderStatus::Cancelled),
+            ],
+            &[product("Bratwurst"), product("Steak")],
+            &categories,
+        );
+
+        assert_eq!(lines, vec![line("Bratwurst", 2, 3.0, 7.0)]);
+    }
+}
@@ -5,12 +5,18 @@ pub mod category {
     pub struct Create {
         #[validate(length(min = 1, max = 64))]
         pub name: String,
+        #[serde(default)]
+        #[validate(range(min = 0.0, max = 100.0))]
+        pub tax_rate: Option<f64>,
     }
 
     #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Validate)]
     pub struct Update {
         #[validate(length(min = 1, max = 64))]
         pub name: Option<String>,
+        #[serde(default)]
+        #[validate(range(min = 0.0, max = 100.0))]
+        pub tax_rate: Option<f64>,
     }
 }
 
@@ -21,12 +27,25 @@ pub mod event {
     pub struct Create {
         #[validate(length(min = 1, max = 64))]
         pub name: String,
+        #[serde(default)]
+        pub starts_at: Option<String>, // `datetime-local` input, empty for none
+        #[serde(default)]
+        pub ends_at: Option<String>,
     }
 
     #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Validate)]
     pub struct Update {
         #[validate(length(min = 1, max = 64))]
         pub name: Option<String>,
+        pub custom_statuses: Option<String>, // Comma separated, in flow order
+        #[serde(default)]
+        pub name_pool: Option<crate::common::types::NamePool>,
+        #[serde(default)]
+        pub state: Option<crate::common::types::EventState>,
+        #[serde(default)]
+        pub starts_at: Option<String>, // `datetime-local` input, empty clears the time
+        #[serde(default)]
+        pub ends_at: Option<String>,
     }
 }
 
@@ -39,6 +58,10 @@ pub mod order {
         #[validate(length(min = 1))]
         pub event: String,
         pub items: Vec<types::Item>,
+        #[serde(default)]
+        pub rush: bool,
+        #[serde(default)]
+        pub payment_method: types::PaymentMethod,
     }
 }
 
@@ -72,6 +95,7 @@ pub mod station {
 }
 
 pub mod product {
+    use serde::{Deserialize, Deserializer, Serializer};
     use validator::Validate;
     
     #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Validate)]
@@ -83,6 +107,36 @@ pub mod product {
         #[validate(range(min = 0.0))]
         pub price: Option<f64>,
         pub active: Option<bool>,
+        // `None` keeps the stock, `Some(None)` stops tracking it, sent as an empty form value
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        #[serde(serialize_with = "serialize_stock", deserialize_with = "deserialize_stock")]
+        pub stock: Option<Option<u32>>,
+    }
+
+    fn serialize_stock<S: Serializer>(stock: &Option<Option<u32>>, serializer: S) -> Result<S::Ok, S::Error> {
+        match stock {
+            Some(Some(stock)) => serializer.serialize_u32(*stock),
+            _ => serializer.serialize_str(""),
+        }
+    }
+
+    fn deserialize_stock<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Option<u32>>, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Stock {
+            Number(u32),
+            Text(String),
+        }
+
+        match Stock::deserialize(deserializer)? {
+            Stock::Number(stock) => Ok(Some(Some(stock))),
+            Stock::Text(text) if text.trim().is_empty() => Ok(Some(None)),
+            Stock::Text(text) => text
+                .trim()
+                .parse()
+                .map(|stock| Some(Some(stock)))
+                .map_err(|_| serde::de::Error::custom(format!("Invalid stock '{}'", text))),
+        }
     }
 
     #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Validate)]
@@ -93,6 +147,7 @@ pub mod product {
         pub category_id: String,
         #[validate(range(min = 0.0))]
         pub price: f64,
+        pub stock: Option<u32>,
     }
 }
 
@@ -117,6 +172,8 @@ pub mod item {
         #[validate(range(min = 1))]
         pub quantity: Option<u32>,
         pub status: Option<OrderStatus>, // Allow updating status
+        #[serde(default)]
+        pub station_id: Option<String>, // Station making the change, recorded in the status history
     }
 }
 
@@ -143,10 +200,12 @@ pub mod user {
 }
 
 pub mod settings {
+    use crate::common::types::AlertRules;
     use validator::Validate;
     
     #[derive(Debug, Clone, serde::Serialize, serde::Deserialize, Validate)]
     pub struct Update {
-        pub active_event_id: Option<String>,
+        pub active_event_ids: Option<Vec<String>>,
+        pub alert_rules: Option<AlertRules>,
     }
 }
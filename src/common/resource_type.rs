@@ -23,17 +23,32 @@ pub use impl_resource_name;
 use crate::common::types::*;
 
 /// Combined trait for resource types that can be used in WebSocket messaging
-pub trait ResourceData: ResourceName + Serialize + Debug {}
+pub trait ResourceData: ResourceName + Serialize + Debug {
+    /// Order the resource belongs to, the tracking page of that order receives its changes
+    fn order_id(&self) -> Option<&str> {
+        None
+    }
+}
 
 // Implement ResourceData for all types
 impl ResourceData for Category {}
 impl ResourceData for User {}
 impl ResourceData for Product {}
-impl ResourceData for Item {}
-impl ResourceData for Order {}
+impl ResourceData for Item {
+    fn order_id(&self) -> Option<&str> {
+        self.order_id.as_deref()
+    }
+}
+impl ResourceData for Order {
+    fn order_id(&self) -> Option<&str> {
+        Some(&self.id)
+    }
+}
 impl ResourceData for Station {}
 impl ResourceData for Event {}
 impl ResourceData for Settings {}
+impl ResourceData for Alert {}
+impl ResourceData for TrackedItem {}
 
 /// Generic message for any resource type
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -52,17 +67,51 @@ where
 {
     pub resource_type: String,
     pub message: Message<T>,
+    // Event of an order or item change, only clients following that event receive it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub event: Option<String>,
+    // Order of an order or item change, order tracking pages only receive the changes of their order
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub order: Option<String>,
 }
 
 impl<T: ResourceData> WebSocketMessage<T> {
     pub fn new(message: Message<T>) -> Self {
+        let order = match &message {
+            Message::Add(resource) | Message::Update(resource) => resource.order_id().map(str::to_string),
+            Message::Delete(_) => None,
+        };
         Self {
             resource_type: T::RESOURCE_NAME.to_string(),
             message,
+            event: None,
+            order,
         }
     }
 }
 
+/// Resource type of client messages choosing the event whose orders and items the connection receives.
+/// The message is the event id, or `null` to receive the changes of every event.
+pub const EVENT_SUBSCRIPTION: &str = "event_subscription";
+
+/// Resource type of client messages limiting the connection to the changes of one order, for the
+/// public order tracking page. The message is the order id, or `null` to lift the limit.
+pub const ORDER_SUBSCRIPTION: &str = "order_subscription";
+
+/// Resource type of client messages announcing which station a connection serves.
+/// The message is the station id, a list of station ids for agents serving several,
+/// or `null` when the client leaves the station.
+pub const STATION_PRESENCE: &str = "station_presence";
+
+/// Station ids announced in a presence message
+pub fn presence_station_ids(message: &serde_json::Value) -> Vec<String> {
+    match message {
+        serde_json::Value::String(id) => vec![id.clone()],
+        serde_json::Value::Array(ids) => ids.iter().filter_map(|id| id.as_str().map(str::to_string)).collect(),
+        _ => Vec::new(),
+    }
+}
+
 /// Type-erased WebSocket message for frontend consumption
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct GenericWebSocketMessage {
@@ -0,0 +1,258 @@
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Quantities typed before a product key, e.g. `3` then the Bratwurst key
+const MAX_QUANTITY_DIGITS: usize = 2;
+
+/// What a key press does on the station board
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StationAction {
+    NextOrder,
+    PreviousOrder,
+    BumpItem,
+    BumpOrder,
+    Undo,
+}
+
+impl StationAction {
+    pub const ALL: [StationAction; 5] = [
+        StationAction::NextOrder,
+        StationAction::PreviousOrder,
+        StationAction::BumpItem,
+        StationAction::BumpOrder,
+        StationAction::Undo,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            StationAction::NextOrder => "Next order",
+            StationAction::PreviousOrder => "Previous order",
+            StationAction::BumpItem => "Bump item",
+            StationAction::BumpOrder => "Bump order",
+            StationAction::Undo => "Undo",
+        }
+    }
+}
+
+/// Keys of one station, saved per device since every bump bar sends different keys.
+/// Keys are `KeyboardEvent.key` values, an empty key is unbound.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct StationKeys {
+    pub next_order: String,
+    pub previous_order: String,
+    pub bump_item: String,
+    pub bump_order: String,
+    pub undo: String,
+}
+
+impl Default for StationKeys {
+    fn default() -> Self {
+        Self {
+            next_order: "ArrowRight".to_string(),
+            previous_order: "ArrowLeft".to_string(),
+            bump_item: " ".to_string(),
+            bump_order: "Enter".to_string(),
+            undo: "Backspace".to_string(),
+        }
+    }
+}
+
+impl StationKeys {
+    pub fn key(&self, action: StationAction) -> &str {
+        match action {
+            StationAction::NextOrder => &self.next_order,
+            StationAction::PreviousOrder => &self.previous_order,
+            StationAction::BumpItem => &self.bump_item,
+            StationAction::BumpOrder => &self.bump_order,
+            StationAction::Undo => &self.undo,
+        }
+    }
+
+    fn key_mut(&mut self, action: StationAction) -> &mut String {
+        match action {
+            StationAction::NextOrder => &mut self.next_order,
+            StationAction::PreviousOrder => &mut self.previous_order,
+            StationAction::BumpItem => &mut self.bump_item,
+            StationAction::BumpOrder => &mut self.bump_order,
+            StationAction::Undo => &mut self.undo,
+        }
+    }
+
+    /// Binds a key to an action, taking it away from the action it was bound to before
+    pub fn bind(&mut self, action: StationAction, key: &str) {
+        for other in StationAction::ALL {
+            if self.key(other) == key {
+                self.key_mut(other).clear();
+            }
+        }
+        *self.key_mut(action) = key.to_string();
+    }
+
+    pub fn action(&self, key: &str) -> Option<StationAction> {
+        StationAction::ALL
+            .into_iter()
+            .find(|action| !key.is_empty() && self.key(*action) == key)
+    }
+}
+
+/// What a key can be bound to at the cashier
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CashierTarget {
+    Product(String), // Product id
+    Submit,
+    Clear,
+}
+
+/// Keys of the cashier on this device. Digits are reserved for entering quantities.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct CashierKeys {
+    pub products: BTreeMap<String, String>, // Product id to key
+    pub submit: String,
+    pub clear: String,
+}
+
+impl Default for CashierKeys {
+    fn default() -> Self {
+        Self {
+            products: BTreeMap::new(),
+            submit: "Enter".to_string(),
+            clear: "Escape".to_string(),
+        }
+    }
+}
+
+fn is_digit(key: &str) -> bool {
+    key.len() == 1 && key.chars().all(|c| c.is_ascii_digit())
+}
+
+impl CashierKeys {
+    pub fn key(&self, target: &CashierTarget) -> &str {
+        match target {
+            CashierTarget::Product(product_id) => self.products.get(product_id).map(String::as_str).unwrap_or(""),
+            CashierTarget::Submit => &self.submit,
+            CashierTarget::Clear => &self.clear,
+        }
+    }
+
+    /// Binds a key, taking it away from the target it was bound to before. Digits can not be bound.
+    pub fn bind(&mut self, target: &CashierTarget, key: &str) -> bool {
+        if key.is_empty() || is_digit(key) {
+            return false;
+        }
+        self.products.retain(|_, bound| bound != key);
+        if self.submit == key {
+            self.submit.clear();
+        }
+        if self.clear == key {
+            self.clear.clear();
+        }
+        match target {
+            CashierTarget::Product(product_id) => {
+                self.products.insert(product_id.clone(), key.to_string());
+            }
+            CashierTarget::Submit => self.submit = key.to_string(),
+            CashierTarget::Clear => self.clear = key.to_string(),
+        }
+        true
+    }
+}
+
+/// What the cashier should do after a key press
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CashierCommand {
+    Add { product_id: String, quantity: u32 },
+    Submit,
+    Clear,
+}
+
+/// Digits typed so far, applied to the next product key
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct QuantityEntry {
+    digits: String,
+}
+
+impl QuantityEntry {
+    pub fn quantity(&self) -> Option<u32> {
+        self.digits.parse().ok().filter(|quantity| *quantity > 0)
+    }
+
+    pub fn press(&mut self, key: &str, keys: &CashierKeys) -> Option<CashierCommand> {
+        if is_digit(key) {
+            if self.digits.len() < MAX_QUANTITY_DIGITS {
+                self.digits.push_str(key);
+            }
+            return None;
+        }
+        if key == "Backspace" && !self.digits.is_empty() {
+            self.digits.pop();
+            return None;
+        }
+
+        let command = if key == keys.submit {
+            CashierCommand::Submit
+        } else if key == keys.clear {
+            CashierCommand::Clear
+        } else {
+            let product_id = keys.products.iter().find(|(_, bound)| *bound == key)?.0.clone();
+            CashierCommand::Add {
+                product_id,
+                quantity: self.quantity().unwrap_or(1),
+            }
+        };
+        self.digits.clear();
+        Some(command)
+    }
+}
+
+/// Readable name of a key for the settings
+pub fn key_label(key: &str) -> String {
+    match key {
+        "" => "–".to_string(),
+        " " => "Space".to_string(),
+        key => key.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_station_rebinding_moves_the_key() {
+        let mut keys = StationKeys::default();
+
+        keys.bind(StationAction::Undo, "Enter");
+
+        assert_eq!(keys.action("Enter"), Some(StationAction::Undo));
+        assert_eq!(keys.bump_order, "");
+        assert_eq!(keys.action(""), None);
+        assert_eq!(keys.action("ArrowRight"), Some(StationAction::NextOrder));
+    }
+
+    #[test]
+    fn test_quantity_applies_to_the_next_product() {
+        let mut keys = CashierKeys::default();
+        assert!(keys.bind(&CashierTarget::Product("wurst".to_string()), "w"));
+        assert!(!keys.bind(&CashierTarget::Product("cola".to_string()), "3"));
+        let mut entry = QuantityEntry::default();
+
+        assert_eq!(entry.press("1", &keys), None);
+        assert_eq!(entry.press("2", &keys), None);
+        assert_eq!(entry.press("5", &keys), None);
+        assert_eq!(entry.press("Backspace", &keys), None);
+        assert_eq!(entry.quantity(), Some(1));
+        assert_eq!(entry.press("3", &keys), None);
+        assert_eq!(
+            entry.press("w", &keys),
+            Some(CashierCommand::Add { product_id: "wurst".to_string(), quantity: 13 })
+        );
+        assert_eq!(
+            entry.press("w", &keys),
+            Some(CashierCommand::Add { product_id: "wurst".to_string(), quantity: 1 })
+        );
+        assert_eq!(entry.press("x", &keys), None);
+        assert_eq!(entry.press("Enter", &keys), Some(CashierCommand::Submit));
+    }
+}
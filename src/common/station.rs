@@ -0,0 +1,151 @@
+use std::collections::HashMap;
+
+use crate::common::resource_type::Message;
+use crate::common::types;
+
+/// Whether an item is currently waiting at the station
+pub fn is_waiting_at(station: &types::Station, products: &[types::Product], item: &types::Item) -> bool {
+    station.input_statuses.contains(&item.status)
+        && products
+            .iter()
+            .find(|p| p.id == item.product_id)
+            .is_some_and(|p| station.handles_category(&p.category_id))
+}
+
+/// Applies a single item change to the station's list, dropping items that moved on to another station.
+/// Items of another event than `event_id` are dropped as well, `None` keeps the items of every event.
+pub fn apply_item_message(
+    items: &mut Vec<types::Item>,
+    message: Message<types::Item>,
+    event_id: Option<&str>,
+    is_waiting: impl Fn(&types::Item) -> bool,
+) {
+    match message {
+        Message::Add(item) | Message::Update(item) => {
+            let position = items.iter().position(|i| i.id == item.id);
+            let of_event = event_id.is_none() || item.event.as_deref() == event_id;
+            match (position, of_event && is_waiting(&item)) {
+                (Some(index), true) => items[index] = item,
+                (Some(index), false) => {
+                    items.remove(index);
+                }
+                (None, true) => items.push(item),
+                (None, false) => {}
+            }
+        }
+        Message::Delete(id) => items.retain(|i| i.id != id),
+    }
+}
+
+/// Groups items by their order, rush orders first and then the longest waiting
+pub fn group_by_order(
+    items: Vec<types::Item>,
+    orders: &HashMap<String, types::Order>,
+) -> Vec<(String, Vec<types::Item>)> {
+    let mut groups: Vec<(String, Vec<types::Item>)> = Vec::new();
+    for item in items {
+        if let Some(order_id) = &item.order_id {
+            match groups.iter_mut().find(|(id, _)| id == order_id) {
+                Some((_, order_items)) => order_items.push(item),
+                None => groups.push((order_id.clone(), vec![item])),
+            }
+        }
+    }
+    // Orders that are not loaded yet keep their arrival position at the end
+    groups.sort_by_key(|(order_id, _)| match orders.get(order_id) {
+        Some(order) => (!order.rush, order.created_at),
+        None => (true, i64::MAX),
+    });
+    groups
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn item(id: &str, status: types::OrderStatus) -> types::Item {
+        types::Item {
+            id: id.to_string(),
+            order_id: Some("order".to_string()),
+            product_id: "wurst".to_string(),
+            quantity: 1,
+            price: 3.0,
+            status,
+            event: Some("fest".to_string()),
+        }
+    }
+
+    fn waiting(item: &types::Item) -> bool {
+        item.status == types::OrderStatus::Ordered
+    }
+
+    #[test]
+    fn test_new_items_only_added_when_waiting() {
+        let mut items = Vec::new();
+
+        apply_item_message(&mut items, Message::Add(item("a", types::OrderStatus::Ordered)), None, waiting);
+        apply_item_message(&mut items, Message::Add(item("b", types::OrderStatus::Ready)), None, waiting);
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(items[0].id, "a");
+    }
+
+    #[test]
+    fn test_items_of_other_events_are_dropped() {
+        let mut items = vec![item("a", types::OrderStatus::Ordered)];
+        let mut other = item("b", types::OrderStatus::Ordered);
+        other.event = Some("bar".to_string());
+        let mut moved = item("a", types::OrderStatus::Ordered);
+        moved.event = Some("bar".to_string());
+
+        apply_item_message(&mut items, Message::Add(other), Some("fest"), waiting);
+        apply_item_message(&mut items, Message::Add(item("c", types::OrderStatus::Ordered)), Some("fest"), waiting);
+        apply_item_message(&mut items, Message::Update(moved), Some("fest"), waiting);
+
+        let ids: Vec<&str> = items.iter().map(|item| item.id.as_str()).collect();
+        assert_eq!(ids, vec!["c"]);
+    }
+
+    #[test]
+    fn test_bumped_items_leave_the_station() {
+        let mut items = vec![item("a", types::OrderStatus::Ordered), item("b", types::OrderStatus::Ordered)];
+
+        apply_item_message(&mut items, Message::Update(item("a", types::OrderStatus::Ready)), None, waiting);
+        apply_item_message(&mut items, Message::Delete("b".to_string()), None, waiting);
+
+        assert!(items.is_empty());
+    }
+
+    #[test]
+    fn test_rush_orders_first_then_oldest() {
+        let order = |id: &str, created_at: i64, rush: bool| {
+            (id.to_string(), types::Order { id: id.to_string(), created_at, rush, name: String::new(), number: 0, payment_method: Default::default() })
+        };
+        let orders: HashMap<String, types::Order> =
+            [order("new", 300, false), order("old", 100, false), order("rush", 200, true)].into();
+        let mut items = Vec::new();
+        for (index, order_id) in ["new", "unknown", "old", "rush", "old"].iter().enumerate() {
+            let mut item = item(&index.to_string(), types::OrderStatus::Ordered);
+            item.order_id = Some(order_id.to_string());
+            items.push(item);
+        }
+
+        let groups = group_by_order(items, &orders);
+
+        let ids: Vec<&str> = groups.iter().map(|(id, _)| id.as_str()).collect();
+        assert_eq!(ids, vec!["rush", "old", "new", "unknown"]);
+        assert_eq!(groups[1].1.len(), 2);
+    }
+
+    #[test]
+    fn test_updates_replace_in_place() {
+        let mut items = vec![item("a", types::OrderStatus::Ordered), item("b", types::OrderStatus::Ordered)];
+        let mut updated = item("a", types::OrderStatus::Ordered);
+        updated.quantity = 3;
+
+        apply_item_message(&mut items, Message::Update(updated), None, waiting);
+
+        assert_eq!(items[0].quantity, 3, "Item should keep its position");
+        assert_eq!(items.len(), 2);
+    }
+}
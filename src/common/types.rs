@@ -11,13 +11,82 @@ pub enum Role {
     Admin,
 }
 
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, Hash)]
 pub enum OrderStatus {
     Draft,     // Being built, not yet ordered
     Ordered,   // Paid and submitted
     Ready,     // All items ready for pickup
     Completed, // Handed out to customer
     Cancelled, // Cancelled before completion
+    #[serde(untagged)]
+    Custom(String), // Intermediate status defined by the event, stored as its plain name
+}
+
+impl OrderStatus {
+    pub const BUILT_IN: [OrderStatus; 5] = [
+        OrderStatus::Draft,
+        OrderStatus::Ordered,
+        OrderStatus::Ready,
+        OrderStatus::Completed,
+        OrderStatus::Cancelled,
+    ];
+
+    /// Items in a terminal status are done and never picked up again
+    pub fn is_terminal(&self) -> bool {
+        matches!(self, OrderStatus::Completed | OrderStatus::Cancelled)
+    }
+
+    pub fn label(&self) -> &str {
+        match self {
+            OrderStatus::Draft => "Draft",
+            OrderStatus::Ordered => "Ordered",
+            OrderStatus::Ready => "Ready",
+            OrderStatus::Completed => "Completed",
+            OrderStatus::Cancelled => "Cancelled",
+            OrderStatus::Custom(name) => name,
+        }
+    }
+
+    /// Inverse of `label`, every unknown name is treated as a custom status
+    pub fn from_label(label: &str) -> Self {
+        Self::BUILT_IN
+            .iter()
+            .find(|status| status.label() == label)
+            .cloned()
+            .unwrap_or_else(|| OrderStatus::Custom(label.to_string()))
+    }
+
+    /// All statuses available in an event, custom statuses sit between `Ordered` and `Ready`
+    pub fn available(custom_statuses: &[String]) -> Vec<OrderStatus> {
+        let mut statuses = vec![OrderStatus::Draft, OrderStatus::Ordered];
+        statuses.extend(custom_statuses.iter().map(|name| OrderStatus::Custom(name.clone())));
+        statuses.extend([OrderStatus::Ready, OrderStatus::Completed, OrderStatus::Cancelled]);
+        statuses
+    }
+
+    /// Parses a comma separated list of custom status names, in flow order
+    pub fn parse_custom(input: &str) -> Result<Vec<String>, String> {
+        let mut names: Vec<String> = Vec::new();
+        for name in input.split(',').map(str::trim).filter(|name| !name.is_empty()) {
+            if name.chars().count() > 32 {
+                return Err(format!("Status '{}' is longer than 32 characters", name));
+            }
+            if Self::BUILT_IN.iter().any(|status| status.label().eq_ignore_ascii_case(name)) {
+                return Err(format!("'{}' is a built-in status", name));
+            }
+            if names.iter().any(|existing| existing.eq_ignore_ascii_case(name)) {
+                return Err(format!("Status '{}' is listed twice", name));
+            }
+            names.push(name.to_string());
+        }
+        Ok(names)
+    }
+}
+
+impl std::fmt::Display for OrderStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.label())
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
@@ -32,6 +101,8 @@ pub struct Item {
     #[validate(range(min = 0.0))]
     pub price: f64,          // Unit price when ordered (historical snapshot)
     pub status: OrderStatus, // Individual item status
+    #[serde(default)]
+    pub event: Option<String>, // Event of the order, boards drop live changes of other events
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
@@ -45,6 +116,7 @@ pub struct Product {
     #[validate(range(min = 0.0))]
     pub price: f64,
     pub active: bool,
+    pub stock: Option<u32>, // None means stock is not tracked
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
@@ -62,15 +134,73 @@ pub struct Category {
     pub id: String,
     #[validate(length(min = 1, max = 64))]
     pub name: String,
+    #[serde(default = "default_tax_rate")]
+    #[validate(range(min = 0.0, max = 100.0))]
+    pub tax_rate: f64, // Percent of VAT included in the prices of the category's products
+}
+
+/// Standard German VAT rate, used for categories that never had a rate set
+pub const DEFAULT_TAX_RATE: f64 = 19.0;
+
+fn default_tax_rate() -> f64 {
+    DEFAULT_TAX_RATE
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct Order {
     #[validate(length(min = 1))]
     pub id: String,
+    #[serde(default)]
+    pub created_at: i64, // Unix timestamp in seconds
+    #[serde(default)]
+    pub rush: bool,      // Pinned to the top of every station board
+    #[serde(default)]
+    pub name: String,    // Pickup name, unique among the open orders of the event
+    #[serde(default)]
+    pub number: u32,     // Sequential within the event, starting at 1
+    #[serde(default)]
+    pub payment_method: PaymentMethod,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+/// How the customer paid at the cashier
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum PaymentMethod {
+    #[default]
+    Cash,
+    Card,
+}
+
+impl PaymentMethod {
+    pub const ALL: [PaymentMethod; 2] = [PaymentMethod::Cash, PaymentMethod::Card];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            PaymentMethod::Cash => "Cash",
+            PaymentMethod::Card => "Card",
+        }
+    }
+}
+
+impl Order {
+    /// Short number for paper lists and receipts, e.g. `#042`
+    pub fn number_label(&self) -> String {
+        format!("#{:03}", self.number)
+    }
+
+    /// Whether the search text is the order's number or part of its pickup name
+    pub fn matches(&self, query: &str) -> bool {
+        let query = query.trim();
+        if query.is_empty() {
+            return true;
+        }
+        match query.trim_start_matches('#').parse::<u32>() {
+            Ok(number) => self.number == number,
+            Err(_) => self.name.to_lowercase().contains(&query.to_lowercase()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate, PartialEq)]
 pub struct Station {
     #[validate(length(min = 1))]
     pub id: String,
@@ -81,6 +211,63 @@ pub struct Station {
     #[validate(length(min = 1))]
     pub input_statuses: Vec<OrderStatus>,
     pub output_status: OrderStatus,
+    #[serde(default)]
+    pub sla: SlaThresholds,
+    #[serde(default)]
+    pub expo: bool, // Hands out whole orders once all their items reached the input statuses
+    #[serde(default)]
+    pub printer: Option<String>, // ESC/POS printer as `host` or `host:port`, tickets print when items arrive
+}
+
+/// A station taking ordered items and marking them ready, for every category it is given
+impl Default for Station {
+    fn default() -> Self {
+        Self {
+            id: String::new(),
+            name: String::new(),
+            category_ids: Vec::new(),
+            input_statuses: vec![OrderStatus::Ordered],
+            output_status: OrderStatus::Ready,
+            sla: SlaThresholds::default(),
+            expo: false,
+            printer: None,
+        }
+    }
+}
+
+impl Station {
+    /// Expo stations consolidate orders across all categories
+    pub fn handles_category(&self, category_id: &str) -> bool {
+        self.expo || self.category_ids.iter().any(|id| id == category_id)
+    }
+}
+
+/// How urgent an order at a station is, based on how long it has been waiting
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SlaLevel {
+    OnTime,
+    Warning,
+    Late,
+}
+
+/// Waiting times after which a station's order cards turn yellow and red, a level is disabled while `None`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct SlaThresholds {
+    pub warn_after_minutes: Option<u32>,
+    pub late_after_minutes: Option<u32>,
+}
+
+impl SlaThresholds {
+    pub fn level(&self, waiting_secs: i64) -> SlaLevel {
+        let exceeded = |minutes: Option<u32>| minutes.is_some_and(|m| waiting_secs >= i64::from(m) * 60);
+        if exceeded(self.late_after_minutes) {
+            SlaLevel::Late
+        } else if exceeded(self.warn_after_minutes) {
+            SlaLevel::Warning
+        } else {
+            SlaLevel::OnTime
+        }
+    }
 }
 
 
@@ -90,13 +277,245 @@ pub struct Event {
     pub id: String,
     #[validate(length(min = 1, max = 64))]
     pub name: String,
+    #[serde(default)]
+    pub custom_statuses: Vec<String>, // Intermediate item statuses, in flow order
+    #[serde(default)]
+    pub name_pool: NamePool,
+    #[serde(default)]
+    pub state: EventState,
+    #[serde(default)]
+    pub starts_at: Option<String>, // Local date and time, `YYYY-MM-DDTHH:MM`
+    #[serde(default)]
+    pub ends_at: Option<String>,
+    #[serde(default)]
+    pub menu: Vec<MenuEntry>, // Empty offers every product at its list price
+}
+
+/// A product on an event's menu, the product's own price and list position apply unless overridden
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct MenuEntry {
+    pub product_id: String,
+    #[serde(default)]
+    pub price: Option<f64>,
+    #[serde(default)]
+    pub sort: Option<i32>, // Lower first, within the product's category
+}
+
+impl Event {
+    /// Why the event takes no orders at the given local time, `None` if it does.
+    /// Planned events open on their own once their start time is reached.
+    pub fn order_refusal(&self, now: &str) -> Option<&'static str> {
+        let started = self.starts_at.as_deref().is_some_and(|start| now >= start);
+        let ended = self.ends_at.as_deref().is_some_and(|end| now >= end);
+        match self.state {
+            EventState::Closed | EventState::Archived => Some("is closed"),
+            _ if ended => Some("has ended"),
+            EventState::Planned if !started => Some("has not started yet"),
+            EventState::Planned | EventState::Open => None,
+        }
+    }
+}
+
+/// Lifecycle of an event, orders and items of closed events are kept unchanged for reporting
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum EventState {
+    Planned,
+    #[default]
+    Open, // Events from before the lifecycle keep taking orders
+    Closed,
+    Archived,
+}
+
+impl EventState {
+    pub fn label(&self) -> &'static str {
+        match self {
+            EventState::Planned => "Planned",
+            EventState::Open => "Open",
+            EventState::Closed => "Closed",
+            EventState::Archived => "Archived",
+        }
+    }
+
+    pub fn is_locked(&self) -> bool {
+        matches!(self, EventState::Closed | EventState::Archived)
+    }
+
+    /// Changes an admin can make directly, closing goes through the close procedure
+    pub fn can_become(&self, next: EventState) -> bool {
+        matches!(
+            (self, next),
+            (EventState::Planned, EventState::Open)
+                | (EventState::Closed, EventState::Open)
+                | (EventState::Closed, EventState::Archived)
+        ) || *self == next
+    }
+}
+
+/// Normalizes a `datetime-local` input to `YYYY-MM-DDTHH:MM`, an empty input clears the time
+pub fn parse_local_datetime(input: &str) -> Result<Option<String>, String> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Ok(None);
+    }
+    let valid = input.len() >= 16
+        && input.char_indices().take(16).all(|(index, c)| match index {
+            4 | 7 => c == '-',
+            10 => c == 'T',
+            13 => c == ':',
+            _ => c.is_ascii_digit(),
+        });
+    if valid {
+        Ok(Some(input[..16].to_string()))
+    } else {
+        Err(format!("'{}' is not a date and time like 2025-07-12T18:00", input))
+    }
+}
+
+/// Word list the pickup names of an event's orders are drawn from
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+pub enum NamePool {
+    #[default]
+    GermanNames,
+    Animals,
+    Colours,
+}
+
+impl NamePool {
+    pub const ALL: [NamePool; 3] = [NamePool::GermanNames, NamePool::Animals, NamePool::Colours];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            NamePool::GermanNames => "German names",
+            NamePool::Animals => "Animals",
+            NamePool::Colours => "Colours",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Validate)]
 pub struct Settings {
     #[validate(length(min = 1))]
     pub id: String,
-    pub active_event_id: Option<String>,
+    #[serde(default)]
+    pub active_event_ids: Vec<String>, // Several in multi-booth mode, the first is the default
+    #[serde(default)]
+    pub alert_rules: AlertRules,
+}
+
+impl Settings {
+    pub fn is_active(&self, event_id: &str) -> bool {
+        self.active_event_ids.iter().any(|id| id == event_id)
+    }
+
+    /// Event a device works for: its own choice while that event is active, otherwise the only
+    /// active event. With several active events and no choice the device has to pick one.
+    pub fn event_for_device(&self, chosen: Option<&str>) -> Option<String> {
+        match chosen {
+            Some(chosen) if self.is_active(chosen) => Some(chosen.to_string()),
+            _ if self.active_event_ids.len() == 1 => self.active_event_ids.first().cloned(),
+            _ => None,
+        }
+    }
+}
+
+/// One status transition of an item, `from` is `None` when the item was created
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct ItemStatusChange {
+    #[validate(length(min = 1))]
+    pub id: String,
+    pub item_id: String,
+    pub order_id: String,
+    pub from: Option<OrderStatus>,
+    pub to: OrderStatus,
+    pub changed_at: i64,            // Unix timestamp in seconds
+    pub user_id: Option<String>,    // Signed in user that made the change
+    pub station_id: Option<String>, // Station the change was made from
+}
+
+/// Items of one order that a station recently moved on, they can still be recalled
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct BumpedOrder {
+    pub order_id: String,
+    pub name: String,   // Pickup name of the order
+    pub item_ids: Vec<String>,
+    pub quantity: u32,  // Units moved on
+    pub bumped_at: i64, // Unix timestamp in seconds of the latest bump
+}
+
+/// An item as shown to the customer tracking their order
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct TrackedItem {
+    pub id: String,
+    pub product_name: String,
+    pub quantity: u32,
+    pub status: OrderStatus,
+}
+
+/// Public view of a single order, without prices or internal ids
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct OrderTracking {
+    pub order_id: String,
+    pub name: String,     // Pickup name called out at the counter
+    pub number: String,   // Short order number, e.g. `#042`
+    pub created_at: i64,  // Unix timestamp in seconds
+    pub items: Vec<TrackedItem>,
+}
+
+/// Median prep time of one product at a station
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProductTiming {
+    pub product_id: String,
+    pub product_name: String,
+    pub median_secs: i64,
+    pub count: usize, // Number of items the median is based on
+}
+
+/// Live performance figures of a single station
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StationMetrics {
+    pub station_id: String,
+    pub station_name: String,
+    pub processed: Vec<u32>,            // Units processed per 15 minute window, oldest first
+    pub median_wait_secs: Option<i64>,  // From Ordered to Ready, `None` until an item got through
+    pub p90_wait_secs: Option<i64>,
+    pub backlog: u32,                   // Units currently waiting at the station
+    pub slowest_products: Vec<ProductTiming>,
+}
+
+/// Figures of a single station over the last throughput window, shown in its header
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct RecentStationMetrics {
+    pub processed: u32,                 // Units the station moved on in the window
+    pub median_wait_secs: Option<i64>,  // From Ordered to Ready of those items
+    pub p90_wait_secs: Option<i64>,
+    pub backlog: u32,                   // Units currently waiting at the station
+}
+
+/// Thresholds for operational alerts, a rule is disabled while its value is `None`
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct AlertRules {
+    pub low_stock_threshold: Option<u32>, // Alert when a product's stock drops below this
+    pub backlog_threshold: Option<u32>,   // Alert when a station has more pending units than this
+    pub max_wait_minutes: Option<u32>,    // Alert when items wait longer than this at a station
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub enum AlertKind {
+    LowStock,
+    StationBacklog,
+    ItemWaiting,
+    StationDisconnected,
+    PrinterError,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, Validate)]
+pub struct Alert {
+    #[validate(length(min = 1))]
+    pub id: String, // Derived from kind and subject, so one condition maps to one alert
+    pub kind: AlertKind,
+    pub message: String,
+    pub station_id: Option<String>,
+    pub product_id: Option<String>,
 }
 
 // Implement ResourceName for all types
@@ -108,4 +527,100 @@ impl_resource_name!(Order, "order");
 impl_resource_name!(Station, "station");
 impl_resource_name!(Event, "event");
 impl_resource_name!(Settings, "settings");
+impl_resource_name!(Alert, "alert");
+impl_resource_name!(TrackedItem, "tracked_item");
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_custom_status_serializes_as_plain_name() {
+        let statuses = vec![OrderStatus::Ordered, OrderStatus::Custom("Grilled".to_string())];
+
+        let json = serde_json::to_string(&statuses).unwrap();
+        assert_eq!(json, r#"["Ordered","Grilled"]"#);
+
+        let parsed: Vec<OrderStatus> = serde_json::from_str(r#"["Ready","Grilled"]"#).unwrap();
+        assert_eq!(parsed, vec![OrderStatus::Ready, OrderStatus::Custom("Grilled".to_string())]);
+    }
+
+    #[test]
+    fn test_parse_custom_statuses() {
+        assert_eq!(
+            OrderStatus::parse_custom(" Grilled, Assembled ,,"),
+            Ok(vec!["Grilled".to_string(), "Assembled".to_string()])
+        );
+        assert!(OrderStatus::parse_custom("Grilled, ready").is_err());
+        assert!(OrderStatus::parse_custom("Grilled, grilled").is_err());
+    }
 
+    #[test]
+    fn test_sla_levels() {
+        let sla = SlaThresholds {
+            warn_after_minutes: Some(5),
+            late_after_minutes: Some(10),
+        };
+        assert_eq!(sla.level(4 * 60 + 59), SlaLevel::OnTime);
+        assert_eq!(sla.level(5 * 60), SlaLevel::Warning);
+        assert_eq!(sla.level(12 * 60), SlaLevel::Late);
+        assert_eq!(SlaThresholds::default().level(3600), SlaLevel::OnTime);
+    }
+
+    #[test]
+    fn test_order_number_and_search() {
+        let order = Order {
+            id: "order".to_string(),
+            created_at: 0,
+            rush: false,
+            name: "Igel".to_string(),
+            number: 42,
+            payment_method: PaymentMethod::Cash,
+        };
+        assert_eq!(order.number_label(), "#042");
+        assert!(order.matches("#042"));
+        assert!(order.matches("42"));
+        assert!(!order.matches("4"), "Numbers match exactly");
+        assert!(order.matches("ige"));
+        assert!(!order.matches("Fuchs"));
+    }
+
+    #[test]
+    fn test_event_schedule() {
+        let mut event = Event {
+            id: "event".to_string(),
+            name: "Sommerfest".to_string(),
+            custom_statuses: Vec::new(),
+            name_pool: NamePool::default(),
+            state: EventState::Planned,
+            starts_at: parse_local_datetime("2025-07-12T16:00:00").unwrap(),
+            ends_at: parse_local_datetime("2025-07-12T23:00").unwrap(),
+            menu: Vec::new(),
+        };
+        assert_eq!(event.order_refusal("2025-07-12T15:59"), Some("has not started yet"));
+        assert_eq!(event.order_refusal("2025-07-12T16:00"), None);
+        assert_eq!(event.order_refusal("2025-07-12T23:00"), Some("has ended"));
+
+        event.state = EventState::Closed;
+        assert_eq!(event.order_refusal("2025-07-12T18:00"), Some("is closed"));
+        assert!(EventState::Closed.can_become(EventState::Archived));
+        assert!(!EventState::Open.can_become(EventState::Closed), "Closing goes through the close procedure");
+        assert!(parse_local_datetime("12.07.2025 16:00").is_err());
+    }
+
+    #[test]
+    fn test_event_for_device() {
+        let mut settings = Settings {
+            id: "global".to_string(),
+            active_event_ids: vec!["truck".to_string()],
+            alert_rules: AlertRules::default(),
+        };
+        assert_eq!(settings.event_for_device(None), Some("truck".to_string()));
+        assert_eq!(settings.event_for_device(Some("old")), Some("truck".to_string()));
+
+        settings.active_event_ids.push("bar".to_string());
+        assert_eq!(settings.event_for_device(None), None);
+        assert_eq!(settings.event_for_device(Some("bar")), Some("bar".to_string()));
+        assert_eq!(settings.event_for_device(Some("old")), None);
+    }
+}
@@ -29,6 +29,17 @@ async fn main() {
     
     // Initialize global WebSocket sender for server functions
     order_stream::backend::websocket::init_websocket_sender(ws_sender.clone());
+
+    // Connections filter event changes by the active events, which are known once the settings are read
+    if let Err(e) = order_stream::backend::settings::get_settings().await {
+        eprintln!("Failed to load settings: {}", e);
+    }
+
+    // Watch for operational alerts (stock, backlogs, waiting times)
+    order_stream::backend::alert::ssr::spawn_alert_monitor();
+
+    // Print kitchen tickets for stations bound to a network printer
+    order_stream::backend::printing::ssr::spawn_printer_service();
     
     // Configure sessions
     let session_store = SurrealSessionStore::new();
@@ -58,7 +69,8 @@ async fn main() {
     let listener = tokio::net::TcpListener::bind(&addr).await.unwrap();
     println!("listening on http://{}", &addr);
     println!("Server functions available at /api/*");
-    axum::serve(listener, app.into_make_service())
+    // Client addresses are needed to rate limit the public tracking page
+    axum::serve(listener, app.into_make_service_with_connect_info::<std::net::SocketAddr>())
         .await
         .unwrap();
 }